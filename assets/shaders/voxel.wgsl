@@ -0,0 +1,41 @@
+#import bevy_pbr::{
+    mesh_functions::{get_world_from_local, mesh_position_local_to_world},
+    view_transformations::position_world_to_clip,
+}
+#import "shaders/voxel_packed.wgsl"::{unpack_voxel, atlas_uv}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> atlas: vec4<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var atlas_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var atlas_sampler: sampler;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) packed: vec2<u32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) shade: f32,
+};
+
+@vertex
+fn vertex(in: Vertex) -> VertexOutput {
+    let v = unpack_voxel(in.packed);
+
+    let world_from_local = get_world_from_local(in.instance_index);
+    let world_position = mesh_position_local_to_world(world_from_local, vec4<f32>(v.position, 1.0));
+
+    var out: VertexOutput;
+    out.clip_position = position_world_to_clip(world_position.xyz);
+    out.uv = atlas_uv(v.tile, v.uv_corner, atlas);
+    out.shade = v.ao * v.light;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // unlit wie vorher beim StandardMaterial, AO/Licht nur als Abdunklung
+    let color = textureSample(atlas_texture, atlas_sampler, in.uv);
+    return vec4<f32>(color.rgb * in.shade, color.a);
+}
@@ -0,0 +1,56 @@
+// Gegenstück zu src/voxel/packed_vertex.rs - Bitlayout muss identisch bleiben.
+// Kein #define_import_path: wird per Asset-Pfad importiert, dann lädt Bevy die Datei selbst.
+
+struct VoxelVertex {
+    position: vec3<f32>,
+    normal: vec3<f32>,
+    face: u32,
+    uv_corner: vec2<f32>,
+    ao: f32,
+    light: f32,
+    tile: vec2<f32>,
+}
+
+// Reihenfolge wie FaceDir::index: +X, -X, +Y, -Y, +Z, -Z
+fn face_normal(face: u32) -> vec3<f32> {
+    switch face {
+        case 0u: { return vec3<f32>( 1.0,  0.0,  0.0); }
+        case 1u: { return vec3<f32>(-1.0,  0.0,  0.0); }
+        case 2u: { return vec3<f32>( 0.0,  1.0,  0.0); }
+        case 3u: { return vec3<f32>( 0.0, -1.0,  0.0); }
+        case 4u: { return vec3<f32>( 0.0,  0.0,  1.0); }
+        default: { return vec3<f32>( 0.0,  0.0, -1.0); }
+    }
+}
+
+fn unpack_voxel(packed: vec2<u32>) -> VoxelVertex {
+    let w0 = packed.x;
+    let w1 = packed.y;
+
+    var v: VoxelVertex;
+    v.position = vec3<f32>(
+        f32(w0 & 31u),
+        f32((w0 >> 5u) & 31u),
+        f32((w0 >> 10u) & 31u),
+    );
+    v.face = (w0 >> 15u) & 7u;
+    v.normal = face_normal(v.face);
+    v.uv_corner = vec2<f32>(f32((w0 >> 18u) & 1u), f32((w0 >> 19u) & 1u));
+    v.ao = f32((w0 >> 20u) & 3u) / 3.0;
+    v.light = f32((w0 >> 22u) & 15u) / 15.0;
+    v.tile = vec2<f32>(f32(w1 & 0xFFFFu), f32(w1 >> 16u));
+    return v;
+}
+
+// wie tile_uv() + corner_uv() in src/voxel/tile.rs
+// atlas = (atlas_w, atlas_h, tile_content, padding)
+fn atlas_uv(tile: vec2<f32>, corner: vec2<f32>, atlas: vec4<f32>) -> vec2<f32> {
+    let cell = atlas.z + 2.0 * atlas.w;
+    let p0 = tile * cell + vec2<f32>(atlas.w);
+    let p1 = p0 + vec2<f32>(atlas.z);
+
+    // halbes Texel Inset
+    let uv0 = (p0 + vec2<f32>(0.5)) / atlas.xy;
+    let uv1 = (p1 - vec2<f32>(0.5)) / atlas.xy;
+    return mix(uv0, uv1, corner);
+}
@@ -0,0 +1,70 @@
+#import bevy_pbr::{
+    mesh_functions::{get_world_from_local, mesh_position_local_to_world, mesh_normal_local_to_world},
+    view_transformations::position_world_to_clip,
+}
+#import "shaders/voxel_packed.wgsl"::unpack_voxel
+
+// Depth/Normal-Prepass (SSAO) für das gepackte Format.
+// Chunks bewegen sich nicht -> Motion Vectors sind immer 0. Deferred wird nicht unterstützt.
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) packed: vec2<u32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_normal: vec3<f32>,
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    @location(1) unclipped_depth: f32,
+#endif
+};
+
+#ifdef PREPASS_FRAGMENT
+struct FragmentOutput {
+#ifdef NORMAL_PREPASS
+    @location(0) normal: vec4<f32>,
+#endif
+#ifdef MOTION_VECTOR_PREPASS
+    @location(1) motion_vector: vec2<f32>,
+#endif
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    @builtin(frag_depth) frag_depth: f32,
+#endif
+};
+#endif
+
+@vertex
+fn vertex(in: Vertex) -> VertexOutput {
+    let v = unpack_voxel(in.packed);
+
+    let world_from_local = get_world_from_local(in.instance_index);
+    let world_position = mesh_position_local_to_world(world_from_local, vec4<f32>(v.position, 1.0));
+
+    var out: VertexOutput;
+    out.position = position_world_to_clip(world_position.xyz);
+    out.world_normal = mesh_normal_local_to_world(v.normal, in.instance_index);
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.unclipped_depth = out.position.z;
+    out.position.z = min(out.position.z, 1.0);
+#endif
+    return out;
+}
+
+// ohne Targets wird kein Fragment-Shader gebraucht (und das Struct wäre leer)
+#ifdef PREPASS_FRAGMENT
+@fragment
+fn fragment(in: VertexOutput) -> FragmentOutput {
+    var out: FragmentOutput;
+#ifdef NORMAL_PREPASS
+    out.normal = vec4<f32>(normalize(in.world_normal) * 0.5 + vec3<f32>(0.5), 1.0);
+#endif
+#ifdef MOTION_VECTOR_PREPASS
+    out.motion_vector = vec2<f32>(0.0);
+#endif
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.frag_depth = in.unclipped_depth;
+#endif
+    return out;
+}
+#endif
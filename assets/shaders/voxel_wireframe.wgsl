@@ -0,0 +1,26 @@
+#import bevy_pbr::{
+    mesh_functions::{get_world_from_local, mesh_position_local_to_world},
+    view_transformations::position_world_to_clip,
+}
+#import "shaders/voxel_packed.wgsl"::unpack_voxel
+
+// Drahtgitter für Chunk-Meshes, Line-Modus setzt VoxelWireframeMaterial::specialize
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> color: vec4<f32>;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) packed: vec2<u32>,
+};
+
+@vertex
+fn vertex(in: Vertex) -> @builtin(position) vec4<f32> {
+    let v = unpack_voxel(in.packed);
+    let world_from_local = get_world_from_local(in.instance_index);
+    let world_position = mesh_position_local_to_world(world_from_local, vec4<f32>(v.position, 1.0));
+    return position_world_to_clip(world_position.xyz);
+}
+
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return color;
+}
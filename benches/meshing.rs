@@ -0,0 +1,37 @@
+use bevy_experiments::voxel::{
+    binary_meshing::binary_mesh,
+    fixtures,
+    greedy_meshing::build_chunk_mesh_greedy_all_axes,
+    lod::ChunkLod,
+    meshing::build_chunk_mesh_with_neighbors,
+    neighborhood::ChunkNeighborhood,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn bench_meshers(c: &mut Criterion) {
+    let cfg = fixtures::blocks_config();
+    let cases = [
+        ("flat_plane", fixtures::flat_plane()),
+        ("pillar", fixtures::pillar()),
+        ("checkerboard", fixtures::checkerboard()),
+        ("random", fixtures::random(7)),
+    ];
+
+    for (name, data) in cases {
+        let nb = ChunkNeighborhood::isolated(&data, ChunkLod(0));
+
+        let mut group = c.benchmark_group(name);
+        group.bench_function("naive", |b| b.iter(|| build_chunk_mesh_with_neighbors(&cfg, black_box(&nb))));
+        group.bench_function("greedy", |b| b.iter(|| build_chunk_mesh_greedy_all_axes(&cfg, black_box(&nb))));
+        group.bench_function("binary", |b| b.iter(|| binary_mesh(&cfg, black_box(&nb))));
+        group.finish();
+    }
+
+    // Nachbarschaft aufbauen gehört beim Remesh mit dazu
+    let data = fixtures::random(7);
+    c.bench_function("neighborhood_isolated", |b| b.iter(|| ChunkNeighborhood::isolated(black_box(&data), ChunkLod(0))));
+}
+
+criterion_group!(benches, bench_meshers);
+criterion_main!(benches);
@@ -1,10 +1,40 @@
 use bevy::prelude::*;
 
+/// Loading -> MainMenu -> WorldSelect -> InGame <-> Paused.
+/// Mit `--world` geht es nach dem Laden direkt ins Spiel.
 #[derive(States, Debug, Clone, Eq, PartialEq, Hash, Default)]
 pub enum AppState {
     #[default]
     Loading,
+    MainMenu,
+    WorldSelect,
     InGame,
+    Paused,
+}
+
+/// Eine Welt ist offen (spielen oder pausiert). Auf- und Abbau der Welt hängen
+/// hieran, nicht an `InGame`, sonst würde jede Pause die Kamera neu spawnen.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct InWorld;
+
+impl ComputedStates for InWorld {
+    type SourceStates = AppState;
+
+    fn compute(state: AppState) -> Option<Self> {
+        matches!(state, AppState::InGame | AppState::Paused).then_some(InWorld)
+    }
+}
+
+/// Keine Welt offen (Laden, Hauptmenü, Weltauswahl) - dafür braucht es eine eigene UI-Kamera.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct InMenu;
+
+impl ComputedStates for InMenu {
+    type SourceStates = AppState;
+
+    fn compute(state: AppState) -> Option<Self> {
+        matches!(state, AppState::Loading | AppState::MainMenu | AppState::WorldSelect).then_some(InMenu)
+    }
 }
 
 #[derive(Resource, Default)]
@@ -12,6 +42,7 @@ pub struct LoadingProgress {
     pub config_loaded: bool,
     pub atlas_loaded: bool,
     pub skybox_loaded: bool,
+    pub worldgen_loaded: bool,
 }
 
 #[derive(Component)]
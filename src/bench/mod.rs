@@ -0,0 +1,16 @@
+//! Reproduzierbare Benchmark-Fahrt: `--bench <script.ron>` erzeugt eine frische
+//! Welt mit festem Seed, fliegt den Pfad aus dem Skript ab und misst dabei
+//! Frame-Zeiten, Lade- und Mesh-Latenz der Chunks und Speicher. Am Ende (oder
+//! beim vorzeitigen Beenden) landet ein Bericht als CSV (pro Frame) und JSON
+//! (Zusammenfassung) unter `exports/`.
+//!
+//! Sichtweite, Lade-Budget und Mesher kommen aus dem Skript (sonst Standardwerte),
+//! nicht aus `settings.ron`; sie stehen auch mit im Bericht.
+
+mod plugin;
+mod report;
+mod script;
+
+pub use plugin::BenchmarkPlugin;
+pub use report::{FrameSample, Recording, Summary};
+pub use script::{BenchmarkScript, BenchmarkSettings};
@@ -0,0 +1,226 @@
+use anyhow::Context;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin, SystemInformationDiagnosticsPlugin};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app_state::AppState;
+use crate::camera::{CameraMode, CameraPathFinished, play_camera_path};
+use crate::save::{SAVES_ROOT, WorldDir, open_world_dir};
+use crate::settings::Settings;
+use crate::voxel::chunk::ChunkPos;
+use crate::voxel::chunk_stream::ChunkLoadQueue;
+use crate::voxel::mesh_export::json_str;
+use crate::voxel::{ChunkMeshStats, MeshingStats, VoxelWorld};
+
+use super::report::{FrameSample, Recording, Summary};
+use super::script::BenchmarkScript;
+
+/// Wegwerf-Welt, wird vor jedem Lauf gelöscht, damit nichts Gespeichertes mitmisst.
+const BENCH_WORLD: &str = ".benchmark";
+const REPORT_DIR: &str = "exports";
+
+/// Nur aktiv mit `script`; sonst tut das Plugin nichts.
+pub struct BenchmarkPlugin {
+    pub script: Option<PathBuf>,
+}
+
+#[derive(Resource)]
+struct BenchRun {
+    script_path: PathBuf,
+    script: BenchmarkScript,
+    /// `Time<Real>` beim Start der Fahrt
+    started: Option<f64>,
+    recording: Recording,
+    queued_at: HashMap<ChunkPos, f64>,
+    spawned_at: HashMap<Entity, f64>,
+    written: bool,
+}
+
+impl Plugin for BenchmarkPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(path) = &self.script else { return; };
+        let loaded = BenchmarkScript::load(path).and_then(|s| open_bench_world(app.world_mut(), s.seed).map(|()| s));
+        let script = match loaded {
+            Ok(script) => script,
+            Err(e) => {
+                // kein Bericht ohne Lauf: gleich im ersten Frame mit Fehlercode beenden
+                error!("Benchmark {}: {e:#}", path.display());
+                app.add_systems(Startup, |mut exit: MessageWriter<AppExit>| {
+                    exit.write(AppExit::error());
+                });
+                return;
+            }
+        };
+
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+        if !app.is_plugin_added::<SystemInformationDiagnosticsPlugin>() {
+            app.add_plugins(SystemInformationDiagnosticsPlugin);
+        }
+
+        info!("Benchmark {} (Seed {}, {:.0} s)", path.display(), script.seed, script.seconds);
+        app.insert_resource(BenchRun {
+            script_path: path.clone(),
+            script,
+            started: None,
+            recording: Recording::default(),
+            queued_at: HashMap::new(),
+            spawned_at: HashMap::new(),
+            written: false,
+        })
+        .add_systems(Startup, pin_settings)
+        .add_systems(OnEnter(AppState::InGame), start_benchmark)
+        .add_systems(
+            Update,
+            (track_chunk_latency, record_frame, finish_benchmark)
+                .chain()
+                .run_if(in_state(AppState::InGame)),
+        )
+        .add_systems(Last, write_report_on_exit);
+    }
+}
+
+/// Frische Wegwerf-Welt mit `seed`. Wie `--world` synchron, bevor irgendein
+/// Loading-System läuft.
+fn open_bench_world(world: &mut World, seed: u32) -> anyhow::Result<()> {
+    let dir = WorldDir::new(SAVES_ROOT, BENCH_WORLD);
+    if dir.path.exists() {
+        std::fs::remove_dir_all(&dir.path).with_context(|| format!("{} löschen", dir.path.display()))?;
+    }
+    // der führende '.' hält die Welt aus dem Menü und von `--world` fern
+    open_world_dir(world, dir, Some(seed))
+}
+
+/// Überschreibt die aus `settings.ron` geladenen Werte, bevor Chunks gestreamt
+/// werden; gespeichert wird davon nichts.
+fn pin_settings(run: Res<BenchRun>, mut settings: ResMut<Settings>) {
+    *settings = run.script.pinned_settings();
+}
+
+fn start_benchmark(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut run: ResMut<BenchRun>,
+    mut mode: ResMut<CameraMode>,
+) {
+    // nach einer Pause geht es einfach weiter
+    if run.started.is_some() {
+        return;
+    }
+    run.started = Some(time.elapsed_secs_f64());
+    play_camera_path(&mut commands, &mut mode, run.script.camera_path());
+}
+
+/// Laden: seit wann ein Chunk in der Warteschlange steht, bis er existiert.
+/// Meshen: ab da bis zum ersten Mesh.
+fn track_chunk_latency(
+    time: Res<Time<Real>>,
+    mut run: ResMut<BenchRun>,
+    queue: Res<ChunkLoadQueue>,
+    spawned: Query<(Entity, &ChunkPos), Added<ChunkPos>>,
+    meshed: Query<Entity, Added<ChunkMeshStats>>,
+) {
+    let now = time.elapsed_secs_f64();
+    let run = &mut *run;
+
+    for &pos in &queue.queued {
+        run.queued_at.entry(pos).or_insert(now);
+    }
+    for (e, pos) in &spawned {
+        if let Some(t0) = run.queued_at.remove(pos) {
+            run.recording.load_ms.push((now - t0) * 1000.0);
+        }
+        run.spawned_at.insert(e, now);
+    }
+    for e in &meshed {
+        if let Some(t0) = run.spawned_at.remove(&e) {
+            run.recording.mesh_ms.push((now - t0) * 1000.0);
+        }
+    }
+}
+
+fn record_frame(
+    time: Res<Time<Real>>,
+    mut run: ResMut<BenchRun>,
+    world: Res<VoxelWorld>,
+    meshing: Res<MeshingStats>,
+    diagnostics: Res<DiagnosticsStore>,
+) {
+    let Some(started) = run.started else { return; };
+    let mem_mib = diagnostics
+        .get(&SystemInformationDiagnosticsPlugin::PROCESS_MEM_USAGE)
+        .and_then(|d| d.value())
+        .map(|gib| gib * 1024.0);
+
+    run.recording.frames.push(FrameSample {
+        t: time.elapsed_secs_f64() - started,
+        frame_ms: time.delta_secs_f64() * 1000.0,
+        chunks: world.chunks.len(),
+        meshed: meshing.chunks_last_frame,
+        mem_mib,
+    });
+}
+
+fn finish_benchmark(
+    mut finished: MessageReader<CameraPathFinished>,
+    mut run: ResMut<BenchRun>,
+    settings: Res<Settings>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    if finished.read().count() == 0 {
+        return;
+    }
+    write_report(&mut run, &settings);
+    exit.write(AppExit::Success);
+}
+
+/// Vorzeitig beendet: bis dahin Gemessenes trotzdem schreiben.
+fn write_report_on_exit(mut exit: MessageReader<AppExit>, mut run: ResMut<BenchRun>, settings: Res<Settings>) {
+    if exit.read().count() > 0 && !run.written {
+        write_report(&mut run, &settings);
+    }
+}
+
+fn write_report(run: &mut BenchRun, settings: &Settings) {
+    run.written = true;
+    if run.recording.frames.is_empty() {
+        warn!("Benchmark: keine Frames gemessen, kein Bericht");
+        return;
+    }
+
+    let name = run.script_path.file_stem().and_then(|s| s.to_str()).unwrap_or("bench");
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    let stem = Path::new(REPORT_DIR).join(format!("bench-{name}-{stamp}"));
+
+    let header = [
+        ("script", json_str(&run.script_path.display().to_string())),
+        ("seed", run.script.seed.to_string()),
+        ("seconds", run.script.seconds.to_string()),
+        ("build", json_str(if cfg!(debug_assertions) { "debug" } else { "release" })),
+        ("version", json_str(env!("CARGO_PKG_VERSION"))),
+        ("view_radius", settings.view_radius.to_string()),
+        ("load_budget", settings.load_budget.to_string()),
+        ("meshing", json_str(&format!("{:?}", settings.meshing))),
+        ("frames", run.recording.frames.len().to_string()),
+    ];
+
+    match run.recording.write(&stem, &header) {
+        Ok((csv, json)) => info!("Benchmark-Bericht: {} / {}", csv.display(), json.display()),
+        Err(e) => error!("Benchmark-Bericht: {e:#}"),
+    }
+
+    let frame_ms: Vec<f64> = run.recording.frames.iter().map(|f| f.frame_ms).collect();
+    if let Some(s) = Summary::of(&frame_ms) {
+        info!(
+            "{} Frames, {:.1} FPS, Frame-Zeit median {:.2} ms, p95 {:.2} ms, max {:.2} ms",
+            s.count,
+            1000.0 / s.mean,
+            s.p50,
+            s.p95,
+            s.max
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::Context;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::voxel::mesh_export::json_str;
+
+/// Ein Frame der Fahrt.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameSample {
+    /// Sekunden seit Start der Fahrt
+    pub t: f64,
+    pub frame_ms: f64,
+    /// geladene Chunks
+    pub chunks: usize,
+    /// in diesem Frame gemeshte Chunks
+    pub meshed: usize,
+    /// Speicher des Prozesses; kommt nur etwa einmal pro Sekunde neu
+    pub mem_mib: Option<f64>,
+}
+
+/// Verteilung einer Messreihe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    pub fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let pick = |q: f64| sorted[((sorted.len() - 1) as f64 * q).round() as usize];
+        Some(Self {
+            count: sorted.len(),
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p50: pick(0.5),
+            p95: pick(0.95),
+            p99: pick(0.99),
+            max: sorted[sorted.len() - 1],
+        })
+    }
+
+    fn json(summary: Option<Self>) -> String {
+        match summary {
+            Some(s) => format!(
+                "{{\"count\": {}, \"mean\": {:.3}, \"p50\": {:.3}, \"p95\": {:.3}, \"p99\": {:.3}, \"max\": {:.3}}}",
+                s.count, s.mean, s.p50, s.p95, s.p99, s.max
+            ),
+            None => "null".to_string(),
+        }
+    }
+}
+
+/// Alles, was während der Fahrt gemessen wird. Latenzen in Millisekunden:
+/// Laden = von der Warteschlange bis der Chunk existiert, Meshen = von da bis
+/// zum ersten Mesh.
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    pub frames: Vec<FrameSample>,
+    pub load_ms: Vec<f64>,
+    pub mesh_ms: Vec<f64>,
+}
+
+impl Recording {
+    pub fn csv(&self) -> String {
+        let mut out = String::from("t_s,frame_ms,chunks,meshed,mem_mib\n");
+        for f in &self.frames {
+            let mem = f.mem_mib.map(|m| format!("{m:.1}")).unwrap_or_default();
+            let _ = writeln!(out, "{:.4},{:.3},{},{},{mem}", f.t, f.frame_ms, f.chunks, f.meshed);
+        }
+        out
+    }
+
+    /// `header`: weitere Felder, Werte schon als JSON.
+    pub fn json(&self, header: &[(&str, String)]) -> String {
+        let frame_ms: Vec<f64> = self.frames.iter().map(|f| f.frame_ms).collect();
+        let frames = Summary::of(&frame_ms);
+        let duration = self.frames.last().map_or(0.0, |f| f.t);
+        let mem: Vec<f64> = self.frames.iter().filter_map(|f| f.mem_mib).collect();
+        let peak = mem.iter().copied().fold(None, |a: Option<f64>, m| Some(a.map_or(m, |a| a.max(m))));
+
+        let mut out = String::from("{\n");
+        for (key, value) in header {
+            let _ = writeln!(out, "  {}: {value},", json_str(key));
+        }
+        let opt = |v: Option<f64>| v.map_or("null".to_string(), |v| format!("{v:.1}"));
+        let _ = writeln!(out, "  \"duration_s\": {duration:.3},");
+        let _ = writeln!(out, "  \"fps_mean\": {},", opt(frames.map(|s| 1000.0 / s.mean)));
+        let _ = writeln!(out, "  \"frame_ms\": {},", Summary::json(frames));
+        let _ = writeln!(out, "  \"chunk_load_ms\": {},", Summary::json(Summary::of(&self.load_ms)));
+        let _ = writeln!(out, "  \"chunk_mesh_ms\": {},", Summary::json(Summary::of(&self.mesh_ms)));
+        let _ = writeln!(out, "  \"memory_mib\": {{\"peak\": {}, \"final\": {}}}", opt(peak), opt(mem.last().copied()));
+        out.push_str("}\n");
+        out
+    }
+
+    /// `<stem>.csv` und `<stem>.json` schreiben.
+    pub fn write(&self, stem: &Path, header: &[(&str, String)]) -> anyhow::Result<(PathBuf, PathBuf)> {
+        if let Some(dir) = stem.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("{} anlegen", dir.display()))?;
+        }
+        let csv = stem.with_extension("csv");
+        let json = stem.with_extension("json");
+        std::fs::write(&csv, self.csv()).with_context(|| format!("{} schreiben", csv.display()))?;
+        std::fs::write(&json, self.json(header)).with_context(|| format!("{} schreiben", json.display()))?;
+        Ok((csv, json))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_percentiles() {
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+        let s = Summary::of(&values).unwrap();
+        assert_eq!((s.count, s.p50, s.p95, s.p99, s.max), (100, 51.0, 95.0, 99.0, 100.0));
+        assert_eq!(s.mean, 50.5);
+        assert_eq!(Summary::of(&[]), None);
+    }
+
+    #[test]
+    fn report_has_a_row_per_frame() {
+        let frame = |t, mem| FrameSample { t, frame_ms: 16.0, chunks: 10, meshed: 2, mem_mib: mem };
+        let rec = Recording {
+            frames: vec![frame(0.0, None), frame(0.016, Some(512.0)), frame(0.032, Some(480.0))],
+            load_ms: vec![3.0],
+            mesh_ms: vec![],
+        };
+
+        let csv = rec.csv();
+        assert_eq!(csv.lines().count(), 4);
+        assert_eq!(csv.lines().nth(1), Some("0.0000,16.000,10,2,"));
+
+        let json = rec.json(&[("seed", "7".to_string())]);
+        assert!(json.contains("\"seed\": 7,"));
+        assert!(json.contains("\"fps_mean\": 62.5,"));
+        assert!(json.contains("\"chunk_mesh_ms\": null,"));
+        assert!(json.contains("\"memory_mib\": {\"peak\": 512.0, \"final\": 480.0}"));
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::camera::CameraPath;
+use crate::save::CameraState;
+use crate::settings::Settings;
+use crate::voxel::MeshingMode;
+
+/// Inhalt der Skript-Datei: Seed, Dauer, feste Einstellungen und die Punkte
+/// des Kamerapfads (gleiches Format wie `paths/*.ron`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkScript {
+    pub seed: u32,
+    /// so lange dauert die Fahrt über alle Punkte
+    pub seconds: f32,
+    #[serde(default)]
+    pub settings: BenchmarkSettings,
+    pub points: Vec<CameraState>,
+}
+
+/// Was die Messung beeinflusst und sonst aus `settings.ron` käme. Fehlt ein
+/// Wert im Skript, gilt der Standardwert - nie der des Spielers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BenchmarkSettings {
+    pub view_radius: i32,
+    pub load_budget: usize,
+    pub meshing: MeshingMode,
+}
+
+impl Default for BenchmarkSettings {
+    fn default() -> Self {
+        let s = Settings::default();
+        Self { view_radius: s.view_radius, load_budget: s.load_budget, meshing: s.meshing }
+    }
+}
+
+impl BenchmarkScript {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let s = std::fs::read_to_string(path).with_context(|| format!("{} lesen", path.display()))?;
+        Self::parse(&s).with_context(|| format!("{} parsen", path.display()))
+    }
+
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let script: BenchmarkScript = ron::from_str(s)?;
+        if script.points.len() < 2 {
+            bail!("mindestens zwei Punkte nötig");
+        }
+        if script.seconds <= 0.0 {
+            bail!("seconds muss > 0 sein");
+        }
+        Ok(script)
+    }
+
+    pub fn camera_path(&self) -> CameraPath {
+        CameraPath { points: self.points.clone(), seconds: self.seconds }
+    }
+
+    /// Einstellungen für den Lauf: Standardwerte plus die aus dem Skript.
+    pub fn pinned_settings(&self) -> Settings {
+        let defaults = Settings::default();
+        let pinned = &self.settings;
+        Settings {
+            view_radius: pinned.view_radius,
+            unload_radius: pinned.view_radius + (defaults.unload_radius - defaults.view_radius),
+            load_budget: pinned.load_budget,
+            meshing: pinned.meshing,
+            ..defaults
+        }
+        .clamped()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_flythrough_loads() {
+        let script = BenchmarkScript::parse(include_str!("../../benches/flythrough.ron")).unwrap();
+        assert!(script.points.len() >= 2);
+        assert_eq!(script.camera_path().seconds, script.seconds);
+
+        assert!(BenchmarkScript::parse("(seed: 1, seconds: 10.0, points: [])").is_err());
+    }
+
+    #[test]
+    fn settings_come_from_the_script_or_defaults() {
+        let script = BenchmarkScript::parse(include_str!("../../benches/flythrough.ron")).unwrap();
+        let s = script.pinned_settings();
+        assert_eq!((s.view_radius, s.load_budget, s.meshing), (16, 16, MeshingMode::Binary));
+        assert!(s.unload_radius > s.view_radius);
+
+        let points = "[(position: (0.0, 0.0, 0.0), yaw: 0.0, pitch: 0.0), (position: (1.0, 0.0, 0.0), yaw: 0.0, pitch: 0.0)]";
+        let bare = BenchmarkScript::parse(&format!("(seed: 1, seconds: 1.0, points: {points})")).unwrap();
+        assert_eq!(bare.pinned_settings(), Settings::default());
+
+        let partial = format!("(seed: 1, seconds: 1.0, settings: (meshing: Naive), points: {points})");
+        let s = BenchmarkScript::parse(&partial).unwrap().pinned_settings();
+        assert_eq!(s.meshing, MeshingMode::Naive);
+        assert_eq!(s.view_radius, Settings::default().view_radius);
+    }
+}
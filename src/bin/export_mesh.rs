@@ -0,0 +1,82 @@
+//! Gelände ohne Fenster generieren und als glTF/OBJ exportieren.
+//!
+//! cargo run --bin export_mesh -- --seed 1337 --from -2,-1,-2 --to 2,2,2 --out exports/terrain.gltf
+//!
+//! `--from`/`--to` sind Chunk-Koordinaten (inklusive), `--assets` das Asset-Verzeichnis.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::{Context, bail};
+use bevy::prelude::*;
+use bevy_experiments::cli::parse_ivec3;
+use bevy_experiments::config::{BlocksConfig, BlocksConfigRes, WorldGenConfig};
+use bevy_experiments::voxel::{chunk::ChunkPos, mesh_export, worldgen::WorldGenerator};
+
+struct Args {
+    seed: u32,
+    from: IVec3,
+    to: IVec3,
+    out: PathBuf,
+    assets: PathBuf,
+}
+
+fn parse_args() -> anyhow::Result<Args> {
+    let mut args = Args {
+        seed: 1337,
+        from: IVec3::new(-2, -1, -2),
+        to: IVec3::new(2, 2, 2),
+        out: PathBuf::from("exports/terrain.gltf"),
+        assets: PathBuf::from("assets"),
+    };
+
+    let mut it = std::env::args().skip(1);
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().with_context(|| format!("{arg} braucht einen Wert"));
+        match arg.as_str() {
+            "--seed" => args.seed = value()?.parse()?,
+            "--from" => args.from = parse_ivec3(&value()?)?,
+            "--to" => args.to = parse_ivec3(&value()?)?,
+            "--out" => args.out = value()?.into(),
+            "--assets" => args.assets = value()?.into(),
+            other => bail!("unbekanntes Argument: {other}"),
+        }
+    }
+    Ok(args)
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = parse_args()?;
+    let (from, to) = (args.from.min(args.to), args.from.max(args.to));
+
+    let blocks = BlocksConfigRes(BlocksConfig::from_file(&args.assets.join("blocks.ron"))?);
+    let worldgen = WorldGenConfig::from_files(&args.assets, "worldgen.ron")?;
+    let generator = WorldGenerator::new(args.seed, &worldgen);
+
+    let t = Instant::now();
+    let mut chunks = HashMap::new();
+    for z in from.z..=to.z {
+        for y in from.y..=to.y {
+            for x in from.x..=to.x {
+                let pos = ChunkPos(IVec3::new(x, y, z));
+                chunks.insert(pos, generator.generate_chunk(pos));
+            }
+        }
+    }
+    println!("{} Chunks generiert in {:.2?}", chunks.len(), t.elapsed());
+
+    let t = Instant::now();
+    let mesh = mesh_export::mesh_chunks(&blocks, chunks.keys().copied(), |p| chunks.get(&p));
+    println!(
+        "gemesht in {:.2?}: {} Vertices, {} Dreiecke",
+        t.elapsed(),
+        mesh.positions.len(),
+        mesh.indices.len() / 3
+    );
+
+    let atlas = args.assets.join("textures").join(&blocks.0.atlas.texture);
+    mesh_export::export_mesh(&mesh, &args.out, &atlas)?;
+    println!("{} geschrieben", args.out.display());
+    Ok(())
+}
@@ -0,0 +1,210 @@
+//! Weltgenerierung + Meshing ohne Fenster und GPU (MinimalPlugins), mit Zeiten
+//! und Statistik - zum Profilen, auch in CI.
+//!
+//! cargo run --release --bin headless -- --seed 1337 --from -4,-4,-4 --to 4,3,4 --mesher binary
+//!
+//! Optionen:
+//!   --seed <n>                     Welt-Seed (1337)
+//!   --from / --to <x,y,z>          Chunk-Bereich, inklusive (-4,-4,-4 bis 4,3,4)
+//!   --mesher naive|greedy|binary   (binary)
+//!   --parallel                     auf dem ComputeTaskPool statt seriell
+//!   --assets <dir>                 (assets)
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, bail};
+use bevy::prelude::*;
+use bevy::tasks::ComputeTaskPool;
+use bevy_experiments::cli::parse_ivec3;
+use bevy_experiments::config::{BlocksConfig, BlocksConfigRes, WorldGenConfig};
+use bevy_experiments::voxel::{
+    MeshingMode,
+    chunk::{ChunkData, ChunkPos},
+    neighborhood::ChunkNeighborhood,
+    worldgen::WorldGenerator,
+};
+
+#[derive(Resource, Debug)]
+struct HeadlessArgs {
+    seed: u32,
+    from: IVec3,
+    to: IVec3,
+    mesher: MeshingMode,
+    parallel: bool,
+    assets: PathBuf,
+}
+
+fn parse_args() -> anyhow::Result<HeadlessArgs> {
+    let mut args = HeadlessArgs {
+        seed: 1337,
+        from: IVec3::new(-4, -4, -4),
+        to: IVec3::new(4, 3, 4),
+        mesher: MeshingMode::default(),
+        parallel: false,
+        assets: PathBuf::from("assets"),
+    };
+
+    let mut it = std::env::args().skip(1);
+    while let Some(arg) = it.next() {
+        if arg == "--parallel" {
+            args.parallel = true;
+            continue;
+        }
+        let value = it.next().with_context(|| format!("{arg} braucht einen Wert"))?;
+        match arg.as_str() {
+            "--seed" => args.seed = value.parse()?,
+            "--from" => args.from = parse_ivec3(&value)?,
+            "--to" => args.to = parse_ivec3(&value)?,
+            "--mesher" => {
+                args.mesher = match value.as_str() {
+                    "naive" => MeshingMode::Naive,
+                    "greedy" => MeshingMode::Greedy,
+                    "binary" => MeshingMode::Binary,
+                    other => bail!("unbekannter Mesher: {other}"),
+                }
+            }
+            "--assets" => args.assets = value.into(),
+            other => bail!("unbekanntes Argument: {other}"),
+        }
+    }
+    Ok(args)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e:#}");
+            std::process::exit(2);
+        }
+    };
+
+    // MinimalPlugins: Zeit, Task-Pools, Schedule-Runner - kein Fenster, kein Renderer
+    App::new()
+        .add_plugins(MinimalPlugins)
+        .insert_resource(args)
+        .add_systems(Startup, run_headless)
+        .run();
+}
+
+fn run_headless(args: Res<HeadlessArgs>, mut exit: MessageWriter<AppExit>) {
+    match run(&args) {
+        Ok(()) => {
+            exit.write(AppExit::Success);
+        }
+        Err(e) => {
+            eprintln!("{e:#}");
+            exit.write(AppExit::error());
+        }
+    }
+}
+
+/// Pro Chunk: wie lange es gedauert hat und was herauskam.
+struct ChunkStats {
+    generate: Duration,
+    mesh: Duration,
+    quads: usize,
+    vertices: usize,
+    triangles: usize,
+}
+
+fn run(args: &HeadlessArgs) -> anyhow::Result<()> {
+    let t_setup = Instant::now();
+    let blocks = BlocksConfigRes(BlocksConfig::from_file(&args.assets.join("blocks.ron"))?);
+    let worldgen = WorldGenConfig::from_files(&args.assets, "worldgen.ron")?;
+    let generator = WorldGenerator::new(args.seed, &worldgen);
+    let setup = t_setup.elapsed();
+
+    let (min, max) = (args.from.min(args.to), args.from.max(args.to));
+    let positions: Vec<ChunkPos> = (min.z..=max.z)
+        .flat_map(|z| (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| ChunkPos(IVec3::new(x, y, z)))))
+        .collect();
+
+    // 1) generieren
+    let t_gen = Instant::now();
+    let generated: Vec<(ChunkPos, ChunkData, Duration)> = run_all(args.parallel, &positions, |&pos| {
+        let t = Instant::now();
+        let data = generator.generate_chunk(pos);
+        (pos, data, t.elapsed())
+    });
+    let gen_wall = t_gen.elapsed();
+
+    let gen_times: HashMap<ChunkPos, Duration> = generated.iter().map(|(p, _, t)| (*p, *t)).collect();
+    let chunks: HashMap<ChunkPos, ChunkData> = generated.into_iter().map(|(p, d, _)| (p, d)).collect();
+
+    // 2) meshen, mit Nachbarn aus dem generierten Bereich
+    let t_mesh = Instant::now();
+    let stats: Vec<ChunkStats> = run_all(args.parallel, &positions, |pos| {
+        let data = &chunks[pos];
+        let t = Instant::now();
+        let nb = ChunkNeighborhood::from_chunks(*pos, data, |p| chunks.get(&p));
+        let mesh = args.mesher.mesh(&blocks, &nb);
+        let mesh_time = t.elapsed();
+        ChunkStats {
+            generate: gen_times[pos],
+            mesh: mesh_time,
+            quads: mesh.indices.len() / 6,
+            vertices: mesh.positions.len(),
+            triangles: mesh.indices.len() / 3,
+        }
+    });
+    let mesh_wall = t_mesh.elapsed();
+
+    report(args, setup, gen_wall, mesh_wall, &stats);
+    Ok(())
+}
+
+/// Seriell oder auf dem ComputeTaskPool; Reihenfolge des Ergebnisses ist egal.
+fn run_all<I: Sync, O: Send + 'static>(parallel: bool, items: &[I], f: impl Fn(&I) -> O + Sync) -> Vec<O> {
+    if !parallel {
+        return items.iter().map(&f).collect();
+    }
+    let f = &f;
+    ComputeTaskPool::get().scope(|s| {
+        for item in items {
+            s.spawn(async move { f(item) });
+        }
+    })
+}
+
+fn report(args: &HeadlessArgs, setup: Duration, gen_wall: Duration, mesh_wall: Duration, stats: &[ChunkStats]) {
+    let n = stats.len();
+    let sum = |f: fn(&ChunkStats) -> usize| stats.iter().map(f).sum::<usize>();
+    let non_empty = stats.iter().filter(|s| s.quads > 0).count();
+
+    println!("seed {}  mesher {:?}  {}", args.seed, args.mesher, if args.parallel { "parallel" } else { "seriell" });
+    println!(
+        "{n} Chunks ({} bis {}), davon {non_empty} mit Mesh; Setup {setup:.1?}",
+        args.from, args.to
+    );
+    println!();
+    println!("{:<10} {:>10} {:>10} {:>10} {:>10} {:>10}", "", "gesamt", "pro Chunk", "median", "p95", "max");
+    print_timing("generieren", gen_wall, stats.iter().map(|s| s.generate).collect());
+    print_timing("meshen", mesh_wall, stats.iter().map(|s| s.mesh).collect());
+    println!();
+    println!("Quads      {:>12}", sum(|s| s.quads));
+    println!("Vertices   {:>12}", sum(|s| s.vertices));
+    println!("Dreiecke   {:>12}", sum(|s| s.triangles));
+    if non_empty > 0 {
+        println!("Quads/Chunk (mit Mesh) {:.1}", sum(|s| s.quads) as f64 / non_empty as f64);
+    }
+}
+
+fn print_timing(label: &str, wall: Duration, mut times: Vec<Duration>) {
+    if times.is_empty() {
+        return;
+    }
+    times.sort();
+    let total: Duration = times.iter().sum();
+    let pick = |q: f64| times[((times.len() - 1) as f64 * q).round() as usize];
+    println!(
+        "{label:<10} {:>10.1?} {:>10.1?} {:>10.1?} {:>10.1?} {:>10.1?}",
+        wall,
+        total / times.len() as u32,
+        pick(0.5),
+        pick(0.95),
+        times[times.len() - 1]
+    );
+}
@@ -1,7 +1,64 @@
 use bevy::prelude::*;
 
+/// Knapp unter senkrecht, sonst kippt die Kamera über den Pol.
+pub const PITCH_LIMIT: f32 = 89.0_f32.to_radians();
+
 #[derive(Component)]
 pub struct FlyCam {
     pub speed: f32,
     pub sensitivity: f32,
-}
\ No newline at end of file
+    /// Radiant um Y
+    pub yaw: f32,
+    /// Radiant, positiv = nach oben, geklemmt auf ±`PITCH_LIMIT`
+    pub pitch: f32,
+}
+
+impl FlyCam {
+    pub fn new(speed: f32, sensitivity: f32) -> Self {
+        Self { speed, sensitivity, yaw: 0.0, pitch: 0.0 }
+    }
+
+    /// Mausbewegung in Pixeln -> Winkel. Kein Roll, Pitch geklemmt.
+    pub fn look(&mut self, delta: Vec2) {
+        self.turn(Vec2::new(delta.x, -delta.y) * self.sensitivity);
+    }
+
+    /// In Radiant, x = nach rechts, y = nach oben (Gamepad).
+    pub fn turn(&mut self, angles: Vec2) {
+        self.yaw = (self.yaw - angles.x).rem_euclid(std::f32::consts::TAU);
+        self.pitch = (self.pitch + angles.y).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.0)
+    }
+
+    /// Winkel aus einer von außen gesetzten Rotation übernehmen (Spawn, Laden, `tp`).
+    pub fn sync_from(&mut self, rotation: Quat) {
+        let (yaw, pitch, _) = rotation.to_euler(EulerRot::YXZ);
+        self.yaw = yaw.rem_euclid(std::f32::consts::TAU);
+        self.pitch = pitch.clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pitch_is_clamped_and_never_rolls() {
+        let mut cam = FlyCam::new(1.0, 0.01);
+        cam.look(Vec2::new(37.0, -10_000.0));
+        assert_eq!(cam.pitch, PITCH_LIMIT);
+        cam.look(Vec2::new(0.0, 20_000.0));
+        assert_eq!(cam.pitch, -PITCH_LIMIT);
+
+        // rechte Achse bleibt waagerecht
+        let right = cam.rotation() * Vec3::X;
+        assert!(right.y.abs() < 1e-5);
+
+        let mut other = FlyCam::new(1.0, 0.01);
+        other.sync_from(cam.rotation());
+        assert!(other.rotation().abs_diff_eq(cam.rotation(), 1e-5));
+    }
+}
@@ -0,0 +1,58 @@
+//! Cursor fangen und freigeben. Gefangen nur im Spiel: beim Betreten, per Klick
+//! ins Fenster (wenn keine UI offen ist). Frei beim Verlassen (Esc = Pause) und
+//! wenn das Fenster den Fokus verliert.
+
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow, WindowFocused};
+
+use crate::console::Console;
+use crate::settings::SettingsMenu;
+
+pub fn set_cursor_grab(cursor: &mut CursorOptions, grab: bool) {
+    cursor.grab_mode = if grab { CursorGrabMode::Locked } else { CursorGrabMode::None };
+    cursor.visible = !grab;
+}
+
+pub fn cursor_grabbed(cursor: &CursorOptions) -> bool {
+    cursor.grab_mode != CursorGrabMode::None
+}
+
+pub fn cursor_is_grabbed(cursor: Single<&CursorOptions, With<PrimaryWindow>>) -> bool {
+    cursor_grabbed(&cursor)
+}
+
+/// Konsole oder Einstellungen offen -> keine Mausbewegung, kein Fangen.
+pub fn ui_open(console: Option<Res<Console>>, settings: Option<Res<SettingsMenu>>) -> bool {
+    console.is_some_and(|c| c.open) || settings.is_some_and(|s| s.open)
+}
+
+pub fn grab_cursor(mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>) {
+    set_cursor_grab(&mut cursor, true);
+}
+
+pub fn release_cursor(mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>) {
+    set_cursor_grab(&mut cursor, false);
+}
+
+/// Klick fängt den Cursor wieder ein. Der Klick selbst soll keinen Block abbauen,
+/// daher läuft das in PreUpdate und leert danach die Maustasten.
+pub fn grab_cursor_on_click(
+    mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+) {
+    if cursor_grabbed(&cursor) || !mouse.get_just_pressed().any(|_| true) {
+        return;
+    }
+    set_cursor_grab(&mut cursor, true);
+    mouse.reset_all();
+}
+
+/// Alt+Tab & Co.: Cursor freigeben, beim Zurückkommen fängt ihn der nächste Klick.
+pub fn release_cursor_on_focus_loss(
+    mut focus: MessageReader<WindowFocused>,
+    mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+) {
+    if focus.read().last().is_some_and(|f| !f.focused) {
+        set_cursor_grab(&mut cursor, false);
+    }
+}
@@ -1,6 +1,13 @@
 mod components;
+mod cursor;
+mod modes;
+mod path;
 mod skybox;
 mod plugin;
 
+pub use components::{FlyCam, PITCH_LIMIT};
+pub use cursor::ui_open;
+pub use modes::{CameraMode, Player};
+pub use path::{CameraPath, CameraPathFinished, PATHS_ROOT, PathPlayback, play_camera_path};
 pub use skybox::Cubemap;
 pub use plugin::CameraPlugin;
\ No newline at end of file
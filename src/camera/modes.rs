@@ -0,0 +1,198 @@
+//! Kameramodi, F5 schaltet weiter: frei fliegen, Third-Person hinter der
+//! Spielfigur (weicht Blöcken aus) und Orbit um einen Punkt. Den Pfad-Modus
+//! gibt es nur während einer Pfad-Wiedergabe (siehe `path`).
+//!
+//! Umschauen läuft in allen Modi über die Winkel der `FlyCam`, die Modi setzen
+//! danach nur noch die Position.
+
+use bevy::prelude::*;
+
+use crate::input::{Action, ActionState};
+use crate::voxel::VoxelWorld;
+use crate::voxel::chunk::{Block, ChunkData, block_to_chunk};
+use crate::voxel::raycast::{RayHit, raycast_blocks};
+use crate::voxel::selection::Selection;
+
+use super::components::FlyCam;
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CameraMode {
+    #[default]
+    Fly,
+    ThirdPerson,
+    Orbit,
+    /// Pfad wird abgespielt, Eingaben bewegen die Kamera nicht
+    Path,
+}
+
+/// Figur für die Third-Person-Kamera. Eine echte Spielfigur gibt es noch nicht,
+/// sie fliegt wie die Flycam (ohne Kollision).
+#[derive(Component)]
+pub struct Player;
+
+/// Höhe des Kopfes über der Mitte der Figur; dort hängt die Kamera dran.
+const PLAYER_HEAD: f32 = 0.7;
+const THIRD_PERSON_DISTANCE: f32 = 6.0;
+/// Abstand zur getroffenen Blockfläche, sonst schneidet die Near-Plane hinein
+const COLLISION_MARGIN: f32 = 0.3;
+
+const ORBIT_MIN: f32 = 2.0;
+const ORBIT_MAX: f32 = 200.0;
+/// Ohne anvisierten Block: Drehpunkt so weit vor der Kamera
+const ORBIT_FALLBACK: f32 = 16.0;
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct Orbit {
+    pub pivot: Vec3,
+    pub distance: f32,
+}
+
+pub fn spawn_player(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Player,
+        DespawnOnExit(crate::app_state::InWorld),
+        Mesh3d(meshes.add(Cuboid::new(0.6, 1.8, 0.6))),
+        MeshMaterial3d(materials.add(Color::srgb(0.9, 0.4, 0.2))),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+}
+
+/// Nächster Block entlang des Strahls, Luft wo nichts geladen ist.
+fn block_getter<'a>(world: &'a VoxelWorld, chunks: &'a Query<&ChunkData>) -> impl Fn(IVec3) -> Block + 'a {
+    move |p: IVec3| {
+        let (c, local) = block_to_chunk(p);
+        world
+            .chunks
+            .get(&c)
+            .and_then(|&e| chunks.get(e).ok())
+            .map(|data| data.get_local(local.x, local.y, local.z))
+            .unwrap_or(Block::Air)
+    }
+}
+
+/// Strecke vom Strahlstart bis zur Fläche, über die `hit` betreten wurde.
+fn hit_distance(origin: Vec3, dir: Vec3, hit: &RayHit) -> f32 {
+    let Some(axis) = (0..3).find(|&a| hit.normal[a] != 0) else {
+        // Start liegt schon im Block
+        return 0.0;
+    };
+    let plane = (hit.block[axis] + (hit.normal[axis] > 0) as i32) as f32;
+    ((plane - origin[axis]) / dir[axis]).max(0.0)
+}
+
+/// Wie weit die Kamera von `head` aus in Richtung `back` zurück darf,
+/// ohne in einem Block zu landen.
+pub fn third_person_distance(head: Vec3, back: Vec3, max: f32, get: impl Fn(IVec3) -> Block) -> f32 {
+    let back = back.normalize_or_zero();
+    match raycast_blocks(head, back, max + COLLISION_MARGIN, get) {
+        Some(hit) => (hit_distance(head, back, &hit) - COLLISION_MARGIN).clamp(0.0, max),
+        None => max,
+    }
+}
+
+/// F5: Fly -> Third-Person -> Orbit -> Fly. Die Figur bzw. der Drehpunkt
+/// landen dort, wo man gerade hinschaut, damit das Bild nicht springt.
+pub fn cycle_camera_mode(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut mode: ResMut<CameraMode>,
+    selection: Res<Selection>,
+    world: Res<VoxelWorld>,
+    chunks: Query<&ChunkData>,
+    cams: Query<&Transform, (With<FlyCam>, Without<Player>)>,
+    mut players: Query<(&mut Transform, &mut Visibility), With<Player>>,
+) {
+    if !actions.just_pressed(Action::CycleCamera) || *mode == CameraMode::Path {
+        return;
+    }
+    let Ok(cam) = cams.single() else { return; };
+    let forward = cam.forward().as_vec3();
+
+    *mode = match *mode {
+        CameraMode::Fly => {
+            for (mut tf, _) in &mut players {
+                tf.translation = cam.translation + forward * THIRD_PERSON_DISTANCE - Vec3::Y * PLAYER_HEAD;
+            }
+            CameraMode::ThirdPerson
+        }
+        CameraMode::ThirdPerson => {
+            let pivot = match selection.region() {
+                Some(region) => (region.min.as_vec3() + region.max.as_vec3() + Vec3::ONE) * 0.5,
+                None => raycast_blocks(cam.translation, forward, ORBIT_MAX, block_getter(&world, &chunks))
+                    .map(|hit| hit.block.as_vec3() + Vec3::splat(0.5))
+                    .unwrap_or(cam.translation + forward * ORBIT_FALLBACK),
+            };
+            let distance = cam.translation.distance(pivot).clamp(ORBIT_MIN, ORBIT_MAX);
+            commands.insert_resource(Orbit { pivot, distance });
+            CameraMode::Orbit
+        }
+        CameraMode::Orbit | CameraMode::Path => CameraMode::Fly,
+    };
+
+    for (_, mut vis) in &mut players {
+        *vis = if *mode == CameraMode::ThirdPerson { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    info!("Kamera: {:?}", *mode);
+}
+
+/// Kamera hinter den Kopf der Figur, bei Blöcken dazwischen näher ran.
+pub fn third_person_camera(
+    world: Res<VoxelWorld>,
+    chunks: Query<&ChunkData>,
+    players: Query<&Transform, With<Player>>,
+    mut cams: Query<&mut Transform, (With<FlyCam>, Without<Player>)>,
+) {
+    let (Ok(player), Ok(mut cam)) = (players.single(), cams.single_mut()) else { return; };
+    let head = player.translation + Vec3::Y * PLAYER_HEAD;
+    let back = -cam.forward().as_vec3();
+
+    let dist = third_person_distance(head, back, THIRD_PERSON_DISTANCE, block_getter(&world, &chunks));
+    cam.translation = head + back * dist;
+}
+
+/// Vor/zurück zoomt, der Rest wie gewohnt über Umschauen.
+pub fn orbit_camera(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut orbit: ResMut<Orbit>,
+    mut cams: Query<&mut Transform, With<FlyCam>>,
+) {
+    let mut zoom = actions.move_axis().y;
+    if actions.pressed(Action::MoveForward) {
+        zoom += 1.0;
+    }
+    if actions.pressed(Action::MoveBack) {
+        zoom -= 1.0;
+    }
+    // exponentiell, fühlt sich auf jeder Entfernung gleich an
+    orbit.distance = (orbit.distance * (-zoom * 1.5 * time.delta_secs()).exp()).clamp(ORBIT_MIN, ORBIT_MAX);
+
+    for mut cam in &mut cams {
+        cam.translation = orbit.pivot - cam.forward().as_vec3() * orbit.distance;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn third_person_stops_in_front_of_walls() {
+        // Wand bei z = 3 (Blöcke 3..4)
+        let wall = |p: IVec3| if p.z == 3 { Block::Stone } else { Block::Air };
+        let head = Vec3::new(0.5, 10.5, 0.5);
+
+        let d = third_person_distance(head, Vec3::Z, THIRD_PERSON_DISTANCE, wall);
+        assert!((d - (2.5 - COLLISION_MARGIN)).abs() < 1e-5, "{d}");
+
+        // frei nach hinten -> volle Entfernung
+        assert_eq!(third_person_distance(head, Vec3::NEG_Z, THIRD_PERSON_DISTANCE, wall), THIRD_PERSON_DISTANCE);
+        // Kopf steckt in der Wand -> Kamera direkt am Kopf
+        assert_eq!(third_person_distance(Vec3::new(0.5, 10.5, 3.5), Vec3::Z, 6.0, wall), 0.0);
+    }
+}
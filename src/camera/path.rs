@@ -0,0 +1,244 @@
+//! Kamerafahrten: Punkte aufnehmen (P), abspielen (Shift+P) oder per Konsole
+//! (`path ...`) speichern und laden. Dateien liegen unter `paths/<name>.ron`.
+//!
+//! Position und Winkel laufen über einen Catmull-Rom-Spline durch alle Punkte,
+//! gleichmäßig über `seconds` verteilt.
+
+use anyhow::{Context, bail};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+use std::path::{Path, PathBuf};
+
+use crate::console::{ConsoleArgs, ConsoleResult};
+use crate::input::{Action, ActionState};
+use crate::save::CameraState;
+
+use super::components::PITCH_LIMIT;
+use super::modes::CameraMode;
+
+pub const PATHS_ROOT: &str = "paths";
+
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CameraPath {
+    pub points: Vec<CameraState>,
+    /// Dauer der ganzen Fahrt
+    #[serde(default = "default_seconds")]
+    pub seconds: f32,
+}
+
+fn default_seconds() -> f32 {
+    20.0
+}
+
+impl Default for CameraPath {
+    fn default() -> Self {
+        Self { points: Vec::new(), seconds: default_seconds() }
+    }
+}
+
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1 + (p2 - p0) * t + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2 + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+impl CameraPath {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let s = std::fs::read_to_string(path).with_context(|| format!("{} lesen", path.display()))?;
+        let out: CameraPath = ron::from_str(&s).with_context(|| format!("{} parsen", path.display()))?;
+        if out.points.len() < 2 {
+            bail!("{}: mindestens zwei Punkte nötig", path.display());
+        }
+        Ok(out)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("{} anlegen", dir.display()))?;
+        }
+        let s = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, s).with_context(|| format!("{} schreiben", path.display()))
+    }
+
+    pub fn file(name: &str) -> PathBuf {
+        Path::new(PATHS_ROOT).join(format!("{name}.ron"))
+    }
+
+    /// Kamera bei `t` (0..=1). Yaw wird vorher "abgewickelt", sonst dreht der
+    /// Spline bei 359° -> 1° einmal ganz herum.
+    pub fn sample(&self, t: f32) -> Option<CameraState> {
+        let n = self.points.len();
+        if n == 0 {
+            return None;
+        }
+
+        let mut yaw = self.points[0].yaw;
+        let keys: Vec<(Vec3, Vec3)> = self
+            .points
+            .iter()
+            .map(|p| {
+                yaw += (p.yaw - yaw + PI).rem_euclid(TAU) - PI;
+                (Vec3::from(p.position), Vec3::new(yaw, p.pitch, 0.0))
+            })
+            .collect();
+
+        let f = t.clamp(0.0, 1.0) * (n - 1) as f32;
+        let i = (f.floor() as usize).min(n.saturating_sub(2));
+        let local = f - i as f32;
+        let at = |k: isize| keys[k.clamp(0, n as isize - 1) as usize];
+        let i = i as isize;
+        let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+
+        let pos = catmull_rom(p0.0, p1.0, p2.0, p3.0, local);
+        let ang = catmull_rom(p0.1, p1.1, p2.1, p3.1, local);
+        Some(CameraState {
+            position: pos.into(),
+            yaw: ang.x.rem_euclid(TAU),
+            pitch: ang.y.clamp(-PITCH_LIMIT, PITCH_LIMIT),
+        })
+    }
+}
+
+/// Läuft gerade eine Wiedergabe.
+#[derive(Resource, Debug)]
+pub struct PathPlayback {
+    pub path: CameraPath,
+    pub elapsed: f32,
+    /// dahin geht es danach zurück
+    pub previous: CameraMode,
+}
+
+/// Wiedergabe ist am Ende angekommen.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct CameraPathFinished;
+
+/// Wiedergabe starten; der Kameramodus steht dann auf `Path`.
+pub fn play_camera_path(commands: &mut Commands, mode: &mut CameraMode, path: CameraPath) {
+    let previous = if *mode == CameraMode::Path { CameraMode::Fly } else { *mode };
+    *mode = CameraMode::Path;
+    commands.insert_resource(PathPlayback { path, elapsed: 0.0, previous });
+}
+
+/// P: aktuelle Kamera als Punkt anhängen, Shift+P: Aufnahme abspielen.
+pub fn path_keys_system(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut recorded: ResMut<CameraPath>,
+    mut mode: ResMut<CameraMode>,
+    cams: Query<&Transform, With<Camera3d>>,
+) {
+    if actions.just_pressed(Action::RecordPathPoint)
+        && let Ok(cam) = cams.single()
+    {
+        recorded.points.push(CameraState::from_transform(cam));
+        info!("Pfadpunkt {} aufgenommen", recorded.points.len());
+    }
+    if actions.just_pressed(Action::PlayPath) {
+        if recorded.points.len() < 2 {
+            warn!("Pfad braucht mindestens zwei Punkte");
+            return;
+        }
+        play_camera_path(&mut commands, &mut mode, recorded.clone());
+    }
+}
+
+pub fn path_playback_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut playback: ResMut<PathPlayback>,
+    mut mode: ResMut<CameraMode>,
+    mut cams: Query<&mut Transform, With<Camera3d>>,
+    mut finished: MessageWriter<CameraPathFinished>,
+) {
+    playback.elapsed += time.delta_secs();
+    let t = playback.elapsed / playback.path.seconds.max(0.001);
+
+    if let Some(state) = playback.path.sample(t) {
+        for mut tf in &mut cams {
+            *tf = Transform { scale: tf.scale, ..state.to_transform() };
+        }
+    }
+
+    if t >= 1.0 {
+        *mode = playback.previous;
+        commands.remove_resource::<PathPlayback>();
+        finished.write(CameraPathFinished);
+    }
+}
+
+/// Aufnahme verwalten: `path` (Info), `path clear`, `path save <name>`,
+/// `path load <name>`, `path play [sekunden]`, `path stop`.
+pub fn path_command(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    mut recorded: ResMut<CameraPath>,
+    mut mode: ResMut<CameraMode>,
+    playback: Option<Res<PathPlayback>>,
+) -> ConsoleResult {
+    if args.is_empty() {
+        return Ok(format!("{} Punkte, {:.1} s", recorded.points.len(), recorded.seconds));
+    }
+    match args.str(0, "Befehl")? {
+        "clear" => {
+            recorded.points.clear();
+            Ok("Pfad geleert".into())
+        }
+        "save" => {
+            let file = CameraPath::file(args.str(1, "Name")?);
+            recorded.save(&file).map_err(|e| format!("{e:#}"))?;
+            Ok(format!("gespeichert: {}", file.display()))
+        }
+        "load" => {
+            let file = CameraPath::file(args.str(1, "Name")?);
+            *recorded = CameraPath::load(&file).map_err(|e| format!("{e:#}"))?;
+            Ok(format!("{} Punkte geladen", recorded.points.len()))
+        }
+        "play" => {
+            if recorded.points.len() < 2 {
+                return Err("mindestens zwei Punkte nötig".into());
+            }
+            if args.len() > 1 {
+                recorded.seconds = args.get::<f32>(1, "Sekunden")?.max(0.1);
+            }
+            play_camera_path(&mut commands, &mut mode, recorded.clone());
+            Ok(format!("spiele {} Punkte über {:.1} s", recorded.points.len(), recorded.seconds))
+        }
+        "stop" => {
+            let playback = playback.ok_or("es läuft nichts")?;
+            *mode = playback.previous;
+            commands.remove_resource::<PathPlayback>();
+            Ok("gestoppt".into())
+        }
+        other => Err(format!("unbekannt: {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, yaw_deg: f32) -> CameraState {
+        CameraState { position: (x, 10.0, 0.0), yaw: yaw_deg.to_radians(), pitch: 0.0 }
+    }
+
+    #[test]
+    fn spline_hits_every_point() {
+        let path = CameraPath { points: vec![point(0.0, 0.0), point(4.0, 10.0), point(6.0, 20.0)], seconds: 1.0 };
+
+        assert_eq!(path.sample(0.0), Some(path.points[0]));
+        let mid = path.sample(0.5).unwrap();
+        assert!((Vec3::from(mid.position) - Vec3::new(4.0, 10.0, 0.0)).length() < 1e-5);
+        let end = path.sample(1.0).unwrap();
+        assert!((end.position.0 - 6.0).abs() < 1e-5);
+        // darüber hinaus bleibt es am Ende
+        assert_eq!(path.sample(2.0), path.sample(1.0));
+    }
+
+    #[test]
+    fn yaw_takes_the_short_way_round() {
+        let path = CameraPath { points: vec![point(0.0, 350.0), point(1.0, 10.0)], seconds: 1.0 };
+        let yaw = path.sample(0.5).unwrap().yaw.to_degrees();
+        assert!((yaw - 360.0).abs() < 0.01 || yaw.abs() < 0.01, "{yaw}");
+    }
+}
@@ -1,10 +1,17 @@
 use bevy::image::TextureFormatPixelInfo;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureViewDescriptor, TextureViewDimension};
-use bevy::{core_pipeline::Skybox, input::mouse::MouseMotion, pbr::ScreenSpaceAmbientOcclusion, prelude::*};
-use crate::app_state::{AppState, LoadingProgress};
+use bevy::{core_pipeline::Skybox, input::{InputSystems, mouse::MouseMotion}, pbr::ScreenSpaceAmbientOcclusion, prelude::*};
+use crate::app_state::{AppState, InWorld, LoadingProgress};
 use crate::config::BlocksConfigRes;
+use crate::console::{ConsoleAppExt, ConsoleArgs, ConsoleResult};
+use crate::input::{Action, ActionState, ActionSystems};
 
 use super::components::FlyCam;
+use super::modes::{CameraMode, Orbit, Player, cycle_camera_mode, orbit_camera, spawn_player, third_person_camera};
+use super::path::{CameraPath, CameraPathFinished, PathPlayback, path_command, path_keys_system, path_playback_system};
+use super::cursor::{
+    cursor_is_grabbed, grab_cursor, grab_cursor_on_click, release_cursor, release_cursor_on_focus_loss, ui_open,
+};
 use super::skybox::Cubemap;
 
 pub struct CameraPlugin;
@@ -12,11 +19,66 @@ pub struct CameraPlugin;
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, (load_skybox, asset_loaded).run_if(in_state(AppState::Loading)))
-           .add_systems(OnEnter(AppState::InGame), setup_camera)
-           .add_systems(Update, (flycam_look, flycam_move).run_if(in_state(AppState::InGame)));
+           .init_resource::<CameraMode>()
+           .init_resource::<CameraPath>()
+           .add_message::<CameraPathFinished>()
+           .add_systems(OnEnter(InWorld), (setup_camera, spawn_player))
+           .add_systems(OnExit(InWorld), reset_camera_mode)
+           .add_systems(OnEnter(AppState::InGame), grab_cursor)
+           .add_systems(OnExit(AppState::InGame), release_cursor)
+           .add_systems(
+               PreUpdate,
+               grab_cursor_on_click
+                   .after(InputSystems)
+                   .before(ActionSystems)
+                   .run_if(in_state(AppState::InGame))
+                   .run_if(not(ui_open)),
+           )
+           .add_systems(Update, release_cursor_on_focus_loss)
+           .add_systems(
+               Update,
+               (
+                   cycle_camera_mode,
+                   path_keys_system,
+                   (
+                       // ohne gefangenen Cursor oder mit offener UI schaut die Kamera nicht herum
+                       flycam_look.run_if(cursor_is_grabbed).run_if(not(ui_open)),
+                       gamepad_look.run_if(not(ui_open)),
+                   )
+                       .run_if(not(resource_equals(CameraMode::Path))),
+                   flycam_move,
+                   // Position erst nach dem Umschauen, sonst hängt sie einen Frame hinterher
+                   third_person_camera.run_if(resource_equals(CameraMode::ThirdPerson)),
+                   orbit_camera.run_if(resource_equals(CameraMode::Orbit)),
+                   path_playback_system.run_if(resource_exists::<PathPlayback>),
+               )
+                   .chain()
+                   .run_if(in_state(AppState::InGame)),
+           )
+           .add_console_command("tp", "<x> <y> <z>", "Kamera an eine Weltposition setzen", tp_command)
+           .add_console_command(
+               "path",
+               "[clear|save <name>|load <name>|play [sekunden]|stop]",
+               "Kamerapfad verwalten und abspielen",
+               path_command,
+           );
     }
 }
 
+fn tp_command(In(args): In<ConsoleArgs>, mut cams: Query<&mut Transform, With<Camera3d>>) -> ConsoleResult {
+    let pos = args.vec3(0, "Position")?;
+    let mut tf = cams.single_mut().map_err(|_| "keine Kamera".to_string())?;
+    tf.translation = pos;
+    Ok(format!("Kamera bei {pos}"))
+}
+
+/// Nächste Welt fängt wieder frei fliegend an.
+fn reset_camera_mode(mut commands: Commands, mut mode: ResMut<CameraMode>) {
+    *mode = CameraMode::Fly;
+    commands.remove_resource::<PathPlayback>();
+    commands.remove_resource::<Orbit>();
+}
+
 fn load_skybox(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -41,15 +103,13 @@ fn setup_camera(
     let cube_map: &Cubemap = &cube_map;
 
     commands.spawn((
+        DespawnOnExit(InWorld),
         Camera3d::default(), 
         Msaa::Off,
         ScreenSpaceAmbientOcclusion::default(),
-        Transform::from_xyz(0.0, 10.0, 20.0)
-                .looking_at(Vec3::ZERO, Vec3::Y),
-        FlyCam {
-            speed: 15.0,
-            sensitivity: 0.002,
-        },
+        Transform::from_xyz(0.0, 48.0, 20.0)
+                .looking_at(Vec3::new(0.0, 24.0, 0.0), Vec3::Y),
+        FlyCam::new(15.0, 0.002),
         Skybox {
             image: cube_map.image_handle.clone(),
             brightness: 1000.0,
@@ -61,7 +121,7 @@ fn setup_camera(
 
 fn flycam_look(
     mut mouse_motion_events: MessageReader<MouseMotion>,
-    mut query: Query<(&FlyCam, &mut Transform)>,
+    mut query: Query<(&mut FlyCam, &mut Transform)>,
 ) {
     let mut delta = Vec2::ZERO;
     for ev in mouse_motion_events.read() {
@@ -72,49 +132,90 @@ fn flycam_look(
         return;
     }
 
-    for (cam, mut transform) in &mut query {
-        let yaw = Quat::from_rotation_y(-delta.x * cam.sensitivity);
-        let pitch = Quat::from_rotation_x(-delta.y * cam.sensitivity);
+    for (mut cam, mut transform) in &mut query {
+        steer(&mut cam, &mut transform, |cam| cam.look(delta));
+    }
+}
+
+/// Rechter Stick; läuft neben der Maus, beides gleichzeitig geht.
+fn gamepad_look(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut query: Query<(&mut FlyCam, &mut Transform)>,
+) {
+    let look = actions.look_axis();
+    if look == Vec2::ZERO {
+        return;
+    }
+    for (mut cam, mut transform) in &mut query {
+        steer(&mut cam, &mut transform, |cam| cam.turn(look * time.delta_secs()));
+    }
+}
 
-        transform.rotation = yaw * transform.rotation;
-        transform.rotation = transform.rotation * pitch;
+fn steer(cam: &mut FlyCam, transform: &mut Transform, turn: impl FnOnce(&mut FlyCam)) {
+    // Rotation wurde von außen gesetzt (Spawn, geladene Kamera) -> Winkel übernehmen
+    if !transform.rotation.abs_diff_eq(cam.rotation(), 1e-4) {
+        cam.sync_from(transform.rotation);
     }
+    turn(cam);
+    transform.rotation = cam.rotation();
 }
 
+/// Fly: die Kamera selbst, Third-Person: die Figur. Richtung kommt in beiden
+/// Fällen von der Kamera. Orbit zoomt selbst, beim Pfad bewegt sich nichts.
 fn flycam_move(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&FlyCam, &mut Transform)>,
+    actions: Res<ActionState>,
+    mode: Res<CameraMode>,
+    mut cams: Query<(&FlyCam, &mut Transform), Without<Player>>,
+    mut players: Query<&mut Transform, (With<Player>, Without<FlyCam>)>,
 ) {
-    for (cam, mut transform) in &mut query {
+    for (cam, mut transform) in &mut cams {
         let mut dir = Vec3::ZERO;
 
-        if keyboard.pressed(KeyCode::KeyW) {
+        if actions.pressed(Action::MoveForward) {
             dir += transform.forward().as_vec3();
         }
-        if keyboard.pressed(KeyCode::KeyS) {
+        if actions.pressed(Action::MoveBack) {
             dir -= transform.forward().as_vec3();
         }
-        if keyboard.pressed(KeyCode::KeyA) {
+        if actions.pressed(Action::MoveLeft) {
             dir -= transform.right().as_vec3();
         }
-        if keyboard.pressed(KeyCode::KeyD) {
+        if actions.pressed(Action::MoveRight) {
             dir += transform.right().as_vec3();
         }
-        if keyboard.pressed(KeyCode::Space) {
+        if actions.pressed(Action::Jump) {
             dir += Vec3::Y;
         }
-        if keyboard.pressed(KeyCode::ControlLeft) {
+        if actions.pressed(Action::Crouch) {
             dir -= Vec3::Y;
         }
+        // Tasten geben volle Geschwindigkeit, der Stick nur so viel wie ausgelenkt
+        let dir = if dir != Vec3::ZERO {
+            dir.normalize()
+        } else {
+            let stick = actions.move_axis();
+            transform.forward().as_vec3() * stick.y + transform.right().as_vec3() * stick.x
+        };
 
         let mut speed = cam.speed;
-        if keyboard.pressed(KeyCode::ShiftLeft) {
+        if actions.pressed(Action::Sprint) {
             speed *= 3.0;
         }
 
-        if dir != Vec3::ZERO {
-            transform.translation += dir.normalize() * speed * time.delta_secs();
+        if dir == Vec3::ZERO {
+            continue;
+        }
+        let step = dir * speed * time.delta_secs();
+        match *mode {
+            CameraMode::Fly => transform.translation += step,
+            CameraMode::ThirdPerson => {
+                for mut player in &mut players {
+                    player.translation += step;
+                }
+            }
+            CameraMode::Orbit | CameraMode::Path => {}
         }
     }
 }
@@ -142,8 +243,8 @@ fn asset_loaded(
     // Source: Cross PNG
     let image = images.get_mut(&cubemap.image_handle).unwrap();
 
-    let w = image.size().x as u32;
-    let h = image.size().y as u32;
+    let w = image.size().x;
+    let h = image.size().y;
 
     info!("Skybox cross image loaded: {}x{}", w, h);    
 
@@ -0,0 +1,17 @@
+//! Kleinkram für die Kommandozeilen-Tools unter `src/bin`.
+
+use anyhow::{Context, bail};
+use bevy::prelude::*;
+
+/// "x,y,z" -> IVec3
+pub fn parse_ivec3(s: &str) -> anyhow::Result<IVec3> {
+    let parts: Vec<i32> = s
+        .split(',')
+        .map(|p| p.trim().parse())
+        .collect::<Result<_, _>>()
+        .with_context(|| format!("'{s}' ist kein x,y,z"))?;
+    match parts[..] {
+        [x, y, z] => Ok(IVec3::new(x, y, z)),
+        _ => bail!("'{s}' ist kein x,y,z"),
+    }
+}
@@ -1,5 +1,9 @@
+use anyhow::{Context, bail};
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::Path;
+
+use crate::voxel::chunk::Block;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct BlocksConfig {
@@ -8,6 +12,26 @@ pub struct BlocksConfig {
     pub blocks: HashMap<String, BlockDef>,
 }
 
+impl BlocksConfig {
+    /// Ohne AssetServer von der Platte laden (Tools, CLI).
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let s = std::fs::read_to_string(path).with_context(|| format!("{} lesen", path.display()))?;
+        ron::from_str(&s).with_context(|| path.display().to_string())
+    }
+
+    /// Jeder Block außer Luft braucht eine Kachel für jede Seite (`all` oder
+    /// `top`/`bottom`/`side`), sonst bricht das Meshen ab.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for block in Block::ALL.into_iter().filter(|&b| b != Block::Air) {
+            let def = self.blocks.get(block.key()).with_context(|| format!("Block '{}' fehlt", block.key()))?;
+            if def.all.is_none() && (def.top.is_none() || def.bottom.is_none() || def.side.is_none()) {
+                bail!("Block '{}': Kachel fehlt (all oder top/bottom/side)", block.key());
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct SkyboxInfo {
     pub texture: String,
@@ -27,3 +51,19 @@ pub struct BlockDef {
     pub bottom: Option<(u32, u32)>,
     pub side: Option<(u32, u32)>,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::voxel::fixtures;
+
+    #[test]
+    fn every_block_needs_tiles() {
+        let mut cfg = fixtures::blocks_config().0;
+        cfg.validate().unwrap();
+
+        cfg.blocks.get_mut("stone").unwrap().all = None;
+        assert!(cfg.validate().is_err());
+        cfg.blocks.remove("stone");
+        assert!(cfg.validate().is_err());
+    }
+}
@@ -1,5 +1,10 @@
 mod atlas;
 mod plugin;
+mod worldgen;
+mod vox_palette;
 
-pub use plugin::BlocksConfigRes;
+pub use atlas::BlocksConfig;
+pub use plugin::{BlocksConfigRes, WorldGenConfigRes};
+pub use worldgen::{BiomeDef, CaveSettings, ClimateSettings, FeatureDef, OreRule, StructureSettings, StructureTemplate, WorldGenConfig};
+pub use vox_palette::{VoxColor, VoxPalette};
 pub use plugin::AtlasConfigPlugin;
\ No newline at end of file
@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 use bevy::asset::{AssetLoader, io::Reader, LoadContext};
 use crate::app_state::{AppState, LoadingProgress};
+use crate::save::WorldMeta;
 
 use super::atlas::BlocksConfig;
+use super::worldgen::{StructureTemplate, WorldGenConfig};
 
 pub struct AtlasConfigPlugin;
 
@@ -11,9 +13,11 @@ impl Plugin for AtlasConfigPlugin {
         app
         .init_asset::<BlocksConfigAsset>()
         .init_asset_loader::<BlocksRonLoader>()
-        .add_systems(OnEnter(AppState::Loading), load_blocks_config)
+        .init_asset::<WorldGenConfigAsset>()
+        .init_asset_loader::<WorldGenRonLoader>()
+        .add_systems(OnEnter(AppState::Loading), (load_blocks_config, load_worldgen_config))
         .add_systems(Update, 
-            promote_blocks_config_to_resource.run_if(in_state(AppState::Loading))
+            (promote_blocks_config_to_resource, promote_worldgen_config_to_resource).run_if(in_state(AppState::Loading))
         );
     }
 }
@@ -30,6 +34,19 @@ pub struct BlocksConfigHandle(pub Handle<BlocksConfigAsset>);
 #[derive(Resource, Clone)]
 pub struct BlocksConfigRes(pub BlocksConfig);
 
+// Beide Loader hängen an ".ron" - Bevy nimmt den passenden über den Handle-Typ.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct WorldGenConfigAsset(pub WorldGenConfig);
+
+#[derive(Default)]
+pub struct WorldGenRonLoader;
+
+#[derive(Resource)]
+pub struct WorldGenConfigHandle(pub Handle<WorldGenConfigAsset>);
+
+#[derive(Resource, Clone)]
+pub struct WorldGenConfigRes(pub WorldGenConfig);
+
 fn load_blocks_config(mut commands: Commands, asset_server: Res<AssetServer>) {
     // assets/blocks.ron
     let handle: Handle<BlocksConfigAsset> = asset_server.load("blocks.ron");
@@ -37,6 +54,16 @@ fn load_blocks_config(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(LoadingProgress::default());
 }
 
+fn load_worldgen_config(mut commands: Commands, asset_server: Res<AssetServer>, meta: Option<Res<WorldMeta>>) {
+    // eine schon offene Welt bringt ihre Generator-Config mit (`open_world`)
+    if meta.is_some() {
+        return;
+    }
+    // assets/worldgen.ron
+    let handle: Handle<WorldGenConfigAsset> = asset_server.load("worldgen.ron");
+    commands.insert_resource(WorldGenConfigHandle(handle));
+}
+
 fn promote_blocks_config_to_resource(
     mut commands: Commands,
     handle: Option<Res<BlocksConfigHandle>>,
@@ -75,4 +102,59 @@ impl AssetLoader for BlocksRonLoader {
         let cfg: BlocksConfig = ron::from_str(s)?;
         Ok(BlocksConfigAsset(cfg))
     }
-}
\ No newline at end of file
+}
+fn promote_worldgen_config_to_resource(
+    mut commands: Commands,
+    handle: Option<Res<WorldGenConfigHandle>>,
+    assets: Res<Assets<WorldGenConfigAsset>>,
+    cfg: Option<Res<WorldGenConfigRes>>,
+    mut progress: ResMut<LoadingProgress>,
+) {
+    let Some(handle) = handle else {
+        // nichts zu laden: Config kam mit der Welt
+        progress.worldgen_loaded = cfg.is_some();
+        return;
+    };
+
+    if let Some(asset) = assets.get(&handle.0) {
+        commands.insert_resource(WorldGenConfigRes(asset.0.clone()));
+        commands.remove_resource::<WorldGenConfigHandle>();
+        progress.worldgen_loaded = true
+    }
+}
+
+impl AssetLoader for WorldGenRonLoader {
+    type Asset = WorldGenConfigAsset;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let s = std::str::from_utf8(&bytes)?;
+        let mut cfg: WorldGenConfig = ron::from_str(s)?;
+
+        // Vorlagen gleich mitladen, der Generator braucht sie alle auf einmal
+        for feature in &cfg.structures.features {
+            if cfg.templates.contains_key(&feature.template) {
+                continue;
+            }
+            let bytes = load_context.read_asset_bytes(feature.template.clone()).await?;
+            let template: StructureTemplate = ron::from_str(std::str::from_utf8(&bytes)?)
+                .map_err(|e| anyhow::anyhow!("{}: {e}", feature.template))?;
+            cfg.templates.insert(feature.template.clone(), template);
+        }
+        cfg.validate()?;
+
+        Ok(WorldGenConfigAsset(cfg))
+    }
+}
@@ -0,0 +1,41 @@
+use serde::Deserialize;
+
+use crate::voxel::chunk::Block;
+
+/// Inhalt von `assets/vox_palette.ron`: welche Farbe in einer MagicaVoxel-Palette
+/// welchem Block entspricht.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VoxPalette {
+    /// Reihenfolge = Palettenplatz beim Export (Eintrag 0 -> Farbindex 1)
+    pub colors: Vec<VoxColor>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct VoxColor {
+    pub block: Block,
+    pub rgb: (u8, u8, u8),
+}
+
+impl VoxPalette {
+    pub const PATH: &'static str = "assets/vox_palette.ron";
+
+    /// Direkt von der Platte, ohne AssetServer (für Import/Export und CLI).
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let s = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(&s)?)
+    }
+
+    /// Block mit der ähnlichsten Farbe (RGB-Abstand).
+    pub fn nearest(&self, rgb: (u8, u8, u8)) -> Option<Block> {
+        let dist = |c: &VoxColor| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(c.rgb.0, rgb.0) + d(c.rgb.1, rgb.1) + d(c.rgb.2, rgb.2)
+        };
+        self.colors.iter().min_by_key(|c| dist(c)).map(|c| c.block)
+    }
+
+    /// Palettenplatz (0-basiert) für den Export.
+    pub fn slot_of(&self, block: Block) -> Option<usize> {
+        self.colors.iter().position(|c| c.block == block)
+    }
+}
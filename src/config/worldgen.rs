@@ -0,0 +1,164 @@
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::voxel::chunk::{Block, CHUNK_SIZE};
+
+/// Inhalt von `assets/worldgen.ron`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldGenConfig {
+    pub climate: ClimateSettings,
+    pub biomes: Vec<BiomeDef>,
+    pub caves: CaveSettings,
+    pub ores: Vec<OreRule>,
+    pub structures: StructureSettings,
+    /// Vorlagen aus `structures.features[..].template`, vom Loader nachgeladen (Key = Pfad).
+    /// In `world.ron` stehen sie mit drin, damit die Welt ohne die Dateien auskommt.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub templates: HashMap<String, StructureTemplate>,
+}
+
+/// Temperatur/Feuchte kommen aus 2D-Noise, daraus wird das Biom gewählt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClimateSettings {
+    /// Größe der Klimazonen in Blöcken (Wellenlänge des Noise)
+    pub scale: f64,
+    /// Wellenlänge des Höhen-Noise in Blöcken
+    pub height_scale: f64,
+    /// Breite der Übergänge im Klimaraum (0..1); 0 = harte Kanten
+    pub blend: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BiomeDef {
+    pub name: String,
+    /// Lage im Klimaraum, jeweils 0..1; das nächstgelegene Biom gewinnt
+    pub temperature: f32,
+    pub humidity: f32,
+    /// oberster Block der Säule
+    pub surface: Block,
+    /// darunter, `filler_depth` Blöcke tief, dann Stein
+    pub filler: Block,
+    pub filler_depth: i32,
+    /// Oberfläche liegt zwischen min und max (Welt-Y)
+    pub height: (i32, i32),
+    /// Bäume/Pflanzen pro Säule (0..1), für die Feature-Platzierung
+    pub vegetation: f32,
+}
+
+/// Zwei Arten Höhlen aus 3D-Noise: große Kammern ("Käse") und lange Tunnel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaveSettings {
+    /// Größe der Kammern in Blöcken
+    pub cheese_scale: f64,
+    /// Noise-Wert (0..1), ab dem ausgehöhlt wird; höher = weniger Kammern
+    pub cheese_threshold: f64,
+    /// Länge der Tunnel-Windungen in Blöcken
+    pub tunnel_scale: f64,
+    /// Tunneldicke im Noise-Raum; ~0.05 ergibt 2-4 Blöcke
+    pub tunnel_width: f64,
+    /// so viele Blöcke unter der Oberfläche bleibt alles fest
+    pub surface_margin: i32,
+}
+
+/// Erzadern: `veins_per_chunk` Adern pro Chunk, nur zwischen `min_y` und `max_y`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OreRule {
+    pub block: Block,
+    pub min_y: i32,
+    pub max_y: i32,
+    /// Mittelwert, Nachkommastellen werden per Zufall aufgerundet
+    pub veins_per_chunk: f32,
+    /// Blöcke pro Ader (Schritte des Random Walks), höchstens [`MAX_VEIN_SIZE`]
+    pub vein_size: u32,
+}
+
+/// Eine Ader darf höchstens bis in den Nachbarchunk wachsen, weiter schaut
+/// `place_ores` nicht; längere würden an der Grenze abgeschnitten.
+pub const MAX_VEIN_SIZE: u32 = CHUNK_SIZE.x as u32 - 1;
+
+/// Bäume, Felsen, Hütten usw. - alles, was über Chunkgrenzen ragen kann.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructureSettings {
+    /// Kantenlänge einer Region in Chunks; pro Region wird einmal gewürfelt
+    pub region_chunks: i32,
+    pub features: Vec<FeatureDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureDef {
+    /// Pfad unter `assets/`, z.B. "structures/tree.ron"
+    pub template: String,
+    /// Namen der Biome, in denen das Feature vorkommt
+    pub biomes: Vec<String>,
+    /// Pro Säule; `None` = `vegetation` des Bioms
+    #[serde(default)]
+    pub density: Option<f32>,
+}
+
+/// Inhalt einer Vorlagen-Datei (`assets/structures/*.ron`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructureTemplate {
+    pub name: String,
+    /// Zeichen -> Block; Zeichen, die fehlen (z.B. '.'), werden nicht gesetzt
+    pub palette: HashMap<char, Block>,
+    /// Diese Zelle der Vorlage landet auf dem Block über der Oberfläche
+    pub anchor: (i32, i32, i32),
+    /// false: nur Luft (und Laub) wird überschrieben, Gelände bleibt
+    #[serde(default)]
+    pub replace: bool,
+    /// Lagen von unten nach oben; je Lage Zeilen in Z-Richtung, Zeichen in X-Richtung
+    pub layers: Vec<Vec<String>>,
+}
+
+impl WorldGenConfig {
+    /// Ohne AssetServer von der Platte laden (Tools, CLI), inkl. Vorlagen.
+    /// `path` und die Vorlagen-Pfade sind relativ zu `assets`, wie beim Loader.
+    pub fn from_files(assets: &Path, path: &str) -> anyhow::Result<Self> {
+        let read = |p: &str| {
+            let full = assets.join(p);
+            std::fs::read_to_string(&full).with_context(|| format!("{} lesen", full.display()))
+        };
+
+        let mut cfg: WorldGenConfig = ron::from_str(&read(path)?).with_context(|| path.to_string())?;
+        for feature in &cfg.structures.features {
+            let template: StructureTemplate =
+                ron::from_str(&read(&feature.template)?).with_context(|| feature.template.clone())?;
+            cfg.templates.insert(feature.template.clone(), template);
+        }
+        cfg.validate().with_context(|| path.to_string())?;
+        Ok(cfg)
+    }
+
+    /// Was der Generator sonst mit einem Panic oder abgeschnittenen Erzadern quittieren würde.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.biomes.is_empty() {
+            bail!("keine Biome");
+        }
+        for ore in &self.ores {
+            if ore.vein_size > MAX_VEIN_SIZE {
+                bail!("Erz {:?}: vein_size {} über dem Maximum {MAX_VEIN_SIZE}", ore.block, ore.vein_size);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voxel::fixtures;
+
+    #[test]
+    fn rejects_configs_the_generator_cannot_use() {
+        let mut cfg = fixtures::worldgen_config();
+        cfg.validate().unwrap();
+
+        let mut long_veins = cfg.clone();
+        long_veins.ores[0].vein_size = super::MAX_VEIN_SIZE + 1;
+        assert!(long_veins.validate().is_err());
+
+        cfg.biomes.clear();
+        assert!(cfg.validate().is_err());
+    }
+}
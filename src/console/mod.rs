@@ -0,0 +1,14 @@
+//! Entwickler-Konsole: `^` (Taste links neben der 1) öffnet sie, Befehle mit
+//! Argumenten, Tab-Vervollständigung, History (Pfeil hoch/runter) und Ausgabe-Log.
+//!
+//! Befehle gehören den Plugins, die auch die Daten dazu haben - sie registrieren
+//! sie selbst mit [`ConsoleAppExt::add_console_command`]. Ohne `ConsolePlugin`
+//! (z.B. headless) sind sie einfach nicht erreichbar.
+
+mod plugin;
+mod registry;
+mod ui;
+
+pub use plugin::ConsolePlugin;
+pub use registry::{ConsoleAppExt, ConsoleArgs, ConsoleCommand, ConsoleCommands, ConsoleResult, Completion, complete, execute, tokenize};
+pub use ui::Console;
@@ -0,0 +1,56 @@
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+
+use crate::input::ActionSystems;
+
+use super::registry::{ConsoleAppExt, ConsoleArgs, ConsoleCommands, ConsoleResult, execute};
+use super::ui::{Console, console_input_system, spawn_console_ui, update_console_ui};
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .init_resource::<ConsoleCommands>()
+            .add_systems(Startup, spawn_console_ui)
+            .add_systems(PreUpdate, console_input_system.after(InputSystems).before(ActionSystems))
+            .add_systems(Update, (run_console_commands, update_console_ui).chain())
+            .add_console_command("help", "[<command>]", "Befehle auflisten oder einen erklären", help_command)
+            .add_console_command("clear", "", "Log leeren", clear_command);
+    }
+}
+
+/// Abgeschickte Zeilen ausführen, Ausgabe ins Log.
+fn run_console_commands(world: &mut World) {
+    if world.resource::<Console>().submitted.is_empty() {
+        return;
+    }
+    let lines = std::mem::take(&mut world.resource_mut::<Console>().submitted);
+
+    for line in lines {
+        let result = execute(world, &line);
+        let mut console = world.resource_mut::<Console>();
+        match result {
+            Ok(out) if out.is_empty() => {}
+            Ok(out) => console.print(out),
+            Err(e) => console.print(format!("Fehler: {e}")),
+        }
+    }
+}
+
+fn help_command(In(args): In<ConsoleArgs>, commands: Res<ConsoleCommands>) -> ConsoleResult {
+    if let Ok(name) = args.str(0, "Befehl") {
+        let cmd = commands.get(name).ok_or_else(|| format!("unbekannter Befehl '{name}'"))?;
+        return Ok(format!("{} {}\n  {}", cmd.name, cmd.usage, cmd.help));
+    }
+    Ok(commands
+        .iter()
+        .map(|c| format!("{} {} - {}", c.name, c.usage, c.help))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn clear_command(_: In<ConsoleArgs>, mut console: ResMut<Console>) -> ConsoleResult {
+    console.log.clear();
+    Ok(String::new())
+}
@@ -0,0 +1,260 @@
+use bevy::ecs::system::SystemId;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::voxel::chunk::Block;
+
+/// Ausgabe für das Log; `Err` wird als Fehler samt Usage angezeigt.
+pub type ConsoleResult = Result<String, String>;
+
+/// Argumente hinter dem Befehlsnamen.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConsoleArgs(pub Vec<String>);
+
+impl ConsoleArgs {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn str(&self, i: usize, what: &str) -> Result<&str, String> {
+        self.0.get(i).map(String::as_str).ok_or_else(|| format!("{what} fehlt"))
+    }
+
+    pub fn get<T: FromStr>(&self, i: usize, what: &str) -> Result<T, String> {
+        let s = self.str(i, what)?;
+        s.parse().map_err(|_| format!("{what}: '{s}' ist ungültig"))
+    }
+
+    /// Drei Argumente ab `i` als Vektor.
+    pub fn ivec3(&self, i: usize, what: &str) -> Result<IVec3, String> {
+        Ok(IVec3::new(self.get(i, what)?, self.get(i + 1, what)?, self.get(i + 2, what)?))
+    }
+
+    pub fn vec3(&self, i: usize, what: &str) -> Result<Vec3, String> {
+        Ok(Vec3::new(self.get(i, what)?, self.get(i + 1, what)?, self.get(i + 2, what)?))
+    }
+
+    /// Blockname wie in `blocks.ron`
+    pub fn block(&self, i: usize) -> Result<Block, String> {
+        let s = self.str(i, "Block")?;
+        Block::ALL
+            .into_iter()
+            .find(|b| b.key() == s)
+            .ok_or_else(|| format!("unbekannter Block '{s}'"))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConsoleCommand {
+    pub name: &'static str,
+    /// z.B. `"<x> <y> <z> <block>"`; `<block>` und `<command>` werden per Tab ergänzt
+    pub usage: &'static str,
+    pub help: &'static str,
+    pub system: SystemId<In<ConsoleArgs>, ConsoleResult>,
+}
+
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(BTreeMap<&'static str, ConsoleCommand>);
+
+impl ConsoleCommands {
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.0.get(name)
+    }
+
+    /// alphabetisch
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleCommand> {
+        self.0.values()
+    }
+}
+
+pub trait ConsoleAppExt {
+    /// Befehl als One-Shot-System registrieren; es bekommt die Argumente als `In<ConsoleArgs>`.
+    fn add_console_command<M>(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl ConsoleAppExt for App {
+    fn add_console_command<M>(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self {
+        let world = self.world_mut();
+        let system = world.register_system(system);
+        let mut commands = world.get_resource_or_init::<ConsoleCommands>();
+        if commands.0.insert(name, ConsoleCommand { name, usage, help, system }).is_some() {
+            warn!("Konsolenbefehl '{name}' doppelt registriert");
+        }
+        self
+    }
+}
+
+/// Leerzeichen trennen, `"..."` hält zusammen.
+pub fn tokenize(line: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut quoted = false;
+    let mut any = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                any = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if any {
+                    out.push(std::mem::take(&mut cur));
+                    any = false;
+                }
+            }
+            c => {
+                cur.push(c);
+                any = true;
+            }
+        }
+    }
+    if any {
+        out.push(cur);
+    }
+    out
+}
+
+/// Zeile ausführen. Läuft exklusiv, damit die Befehle beliebige Systemparameter haben können.
+pub fn execute(world: &mut World, line: &str) -> ConsoleResult {
+    let mut tokens = tokenize(line);
+    if tokens.is_empty() {
+        return Ok(String::new());
+    }
+    let name = tokens.remove(0);
+
+    let Some((system, usage)) = world
+        .get_resource::<ConsoleCommands>()
+        .and_then(|c| c.get(&name))
+        .map(|c| (c.system, c.usage))
+    else {
+        return Err(format!("unbekannter Befehl '{name}' (help listet alle)"));
+    };
+
+    match world.run_system_with(system, ConsoleArgs(tokens)) {
+        Ok(Ok(out)) => Ok(out),
+        Ok(Err(e)) => Err(format!("{e}\n  {name} {usage}")),
+        // z.B. Ressource fehlt noch (Welt lädt)
+        Err(e) => Err(format!("{name} geht gerade nicht: {e}")),
+    }
+}
+
+/// Ergebnis von Tab: neue Eingabe und, falls mehrdeutig, die Kandidaten.
+#[derive(Debug, PartialEq)]
+pub struct Completion {
+    pub input: String,
+    pub candidates: Vec<String>,
+}
+
+/// Erstes Wort: Befehlsnamen. Danach je nach Platzhalter in `usage`
+/// an dieser Stelle: `<block>` Blocknamen, `<command>` Befehlsnamen.
+pub fn complete(commands: &ConsoleCommands, input: &str) -> Completion {
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    let partial = if input.ends_with(char::is_whitespace) || input.is_empty() {
+        ""
+    } else {
+        tokens.last().copied().unwrap_or_default()
+    };
+    let index = if partial.is_empty() { tokens.len() } else { tokens.len() - 1 };
+
+    let names = || commands.iter().map(|c| c.name.to_string()).collect::<Vec<_>>();
+    let pool: Vec<String> = if index == 0 {
+        names()
+    } else {
+        let placeholder = commands
+            .get(tokens[0])
+            .and_then(|c| c.usage.split_whitespace().nth(index - 1))
+            .map(|p| p.trim_matches(['[', ']']));
+        match placeholder {
+            Some("<block>") => Block::ALL.iter().map(|b| b.key().to_string()).collect(),
+            Some("<command>") => names(),
+            _ => Vec::new(),
+        }
+    };
+
+    let matches: Vec<String> = pool.into_iter().filter(|c| c.starts_with(partial)).collect();
+    let head = &input[..input.len() - partial.len()];
+
+    match matches.as_slice() {
+        [] => Completion { input: input.to_string(), candidates: Vec::new() },
+        [only] => Completion { input: format!("{head}{only} "), candidates: Vec::new() },
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |acc, m| {
+                let n = acc.chars().zip(m.chars()).take_while(|(a, b)| a == b).count();
+                &acc[..acc.char_indices().nth(n).map_or(acc.len(), |(i, _)| i)]
+            });
+            Completion { input: format!("{head}{common}"), candidates: matches.clone() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Counter(i32);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Counter>()
+            .add_console_command("add", "<n>", "", |In(args): In<ConsoleArgs>, mut c: ResMut<Counter>| {
+                c.0 += args.get::<i32>(0, "n")?;
+                Ok(format!("= {}", c.0))
+            })
+            .add_console_command("setblock", "<x> <y> <z> <block>", "", |_: In<ConsoleArgs>| Ok(String::new()))
+            .add_console_command("save", "", "", |_: In<ConsoleArgs>| Ok(String::new()));
+        app
+    }
+
+    #[test]
+    fn tokenize_keeps_quoted_words() {
+        assert_eq!(tokenize("  tp 1  -2 3.5 "), vec!["tp", "1", "-2", "3.5"]);
+        assert_eq!(tokenize(r#"say "hallo welt" """#), vec!["say", "hallo welt", ""]);
+    }
+
+    #[test]
+    fn runs_registered_commands() {
+        let mut app = app();
+        let world = app.world_mut();
+        assert_eq!(execute(world, "add 2"), Ok("= 2".into()));
+        assert_eq!(execute(world, "add 40"), Ok("= 42".into()));
+        assert!(execute(world, "add zwei").unwrap_err().contains("add <n>"));
+        assert!(execute(world, "nope").is_err());
+        assert_eq!(world.resource::<Counter>().0, 42);
+    }
+
+    #[test]
+    fn tab_completes_commands_and_blocks() {
+        let app = app();
+        let cmds = app.world().resource::<ConsoleCommands>();
+
+        assert_eq!(complete(cmds, "ad").input, "add ");
+        // s -> save/setblock: nur gemeinsamer Anfang, Kandidaten werden gelistet
+        let c = complete(cmds, "s");
+        assert_eq!(c.input, "s");
+        assert_eq!(c.candidates, vec!["save", "setblock"]);
+
+        assert_eq!(complete(cmds, "setblock 1 2 3 dia").input, "setblock 1 2 3 diamond_ore ");
+        // an Zahl-Positionen gibt es nichts zu ergänzen
+        assert_eq!(complete(cmds, "setblock 1").input, "setblock 1");
+        assert_eq!(complete(cmds, "setblock 1 2 3 ").candidates.len(), Block::ALL.len());
+    }
+}
@@ -0,0 +1,168 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use super::registry::{ConsoleCommands, complete};
+
+/// `^` auf deutscher, `` ` `` auf US-Tastatur
+const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const LOG_LIMIT: usize = 200;
+const HISTORY_LIMIT: usize = 100;
+const VISIBLE_LINES: usize = 16;
+
+#[derive(Resource, Default)]
+pub struct Console {
+    pub open: bool,
+    pub input: String,
+    pub log: VecDeque<String>,
+    history: Vec<String>,
+    /// beim Blättern: Position in `history`
+    history_pos: Option<usize>,
+    /// abgeschickt, aber noch nicht ausgeführt
+    pub(super) submitted: Vec<String>,
+}
+
+impl Console {
+    pub fn print(&mut self, text: impl AsRef<str>) {
+        for line in text.as_ref().lines() {
+            self.log.push_back(line.to_string());
+        }
+        while self.log.len() > LOG_LIMIT {
+            self.log.pop_front();
+        }
+    }
+
+    fn submit(&mut self, line: String) {
+        self.print(format!("> {line}"));
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+            if self.history.len() > HISTORY_LIMIT {
+                self.history.remove(0);
+            }
+        }
+        self.history_pos = None;
+        self.submitted.push(line);
+    }
+
+    fn history_step(&mut self, back: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let last = self.history.len() - 1;
+        self.history_pos = match (self.history_pos, back) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.input = self.history_pos.map(|i| self.history[i].clone()).unwrap_or_default();
+    }
+}
+
+/// Läuft in PreUpdate nach der Eingabe: solange die Konsole offen ist, bekommt
+/// das Spiel keine Tasten und Klicks (sonst fliegt man beim Tippen von WASD los).
+pub fn console_input_system(
+    mut events: MessageReader<KeyboardInput>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    mut console: ResMut<Console>,
+    commands: Res<ConsoleCommands>,
+) {
+    let was_open = console.open;
+
+    for ev in events.read() {
+        if ev.state != ButtonState::Pressed {
+            continue;
+        }
+        if ev.key_code == TOGGLE_KEY {
+            if !ev.repeat {
+                console.open = !console.open;
+            }
+            continue;
+        }
+        if !console.open {
+            continue;
+        }
+
+        match ev.key_code {
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                let line = std::mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    console.submit(line);
+                }
+            }
+            KeyCode::Backspace => {
+                console.input.pop();
+            }
+            KeyCode::Escape => console.open = false,
+            KeyCode::Tab => {
+                let c = complete(&commands, &console.input);
+                console.input = c.input;
+                if !c.candidates.is_empty() {
+                    console.print(c.candidates.join("  "));
+                }
+            }
+            KeyCode::ArrowUp => console.history_step(true),
+            KeyCode::ArrowDown => console.history_step(false),
+            _ => {
+                if let Some(text) = &ev.text {
+                    console.input.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+
+    if console.open || was_open {
+        keyboard.reset_all();
+        mouse.reset_all();
+    }
+}
+
+#[derive(Component)]
+pub struct ConsoleRoot;
+
+#[derive(Component)]
+pub struct ConsoleText;
+
+pub fn spawn_console_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            ConsoleRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                top: px(0),
+                left: px(0),
+                width: percent(100),
+                padding: UiRect::all(px(8)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            // über allem anderen UI
+            GlobalZIndex(100),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((ConsoleText, Text::default(), TextFont { font_size: 14.0, ..default() }));
+        });
+}
+
+pub fn update_console_ui(
+    console: Res<Console>,
+    mut root: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut text: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    for mut vis in &mut root {
+        *vis = if console.open { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    let Ok(mut text) = text.single_mut() else { return; };
+
+    let skip = console.log.len().saturating_sub(VISIBLE_LINES);
+    let mut s: String = console.log.iter().skip(skip).map(|l| format!("{l}\n")).collect();
+    s.push_str(&format!("> {}_", console.input));
+    text.0 = s;
+}
@@ -0,0 +1,266 @@
+//! F4: Chunk-Grenzen um die Kamera als Gizmo-Boxen, eingefärbt nach Zustand,
+//! dazu unten rechts eine Minimap (von oben, ein Pixel pro Chunk-Säule) mit
+//! `view_radius` und `unload_radius` aus `ChunkStreamConfig`.
+//!
+//! Generiert wird synchron beim Laden - "generiert" heißt hier: Daten da,
+//! aber noch nie gemesht.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::collections::HashMap;
+
+use crate::input::{Action, ActionState};
+use crate::app_state::InWorld;
+use crate::voxel::ChunkMeshStats;
+use crate::voxel::chunk::{CHUNK_SIZE, ChunkDirty, ChunkPos, chunk_origin_world, world_to_chunk_pos};
+use crate::voxel::chunk_store::ChunkModified;
+use crate::voxel::chunk_stream::{ChunkLoadQueue, ChunkStreamConfig};
+
+/// Boxen nur so viele Chunks um die Kamera (horizontal), sonst wird es unlesbar.
+const BORDER_RADIUS: i32 = 3;
+/// ... und so viele Ebenen darüber/darunter.
+const BORDER_RADIUS_Y: i32 = 1;
+/// Kantenlänge der Minimap auf dem Bildschirm.
+const MINIMAP_PX: f32 = 220.0;
+
+#[derive(Resource, Default, Debug)]
+pub struct ChunkGizmos {
+    pub enabled: bool,
+}
+
+/// Zustand eines Chunks, in der Reihenfolge, in der er auf der Minimap gewinnt
+/// (mehrere Chunks pro Säule -> der "interessanteste").
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChunkState {
+    Clean,
+    Queued,
+    Generated,
+    Meshing,
+    Modified,
+}
+
+impl ChunkState {
+    pub const ALL: [ChunkState; 5] =
+        [ChunkState::Clean, ChunkState::Queued, ChunkState::Generated, ChunkState::Meshing, ChunkState::Modified];
+
+    pub fn color(self) -> Color {
+        match self {
+            ChunkState::Clean => Color::srgb(0.2, 0.8, 0.3),
+            ChunkState::Queued => Color::srgb(0.5, 0.5, 0.5),
+            ChunkState::Generated => Color::srgb(1.0, 0.9, 0.2),
+            ChunkState::Meshing => Color::srgb(1.0, 0.5, 0.1),
+            ChunkState::Modified => Color::srgb(0.9, 0.2, 0.9),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ChunkState::Clean => "fertig",
+            ChunkState::Queued => "in Queue",
+            ChunkState::Generated => "generiert",
+            ChunkState::Meshing => "wartet auf Mesh",
+            ChunkState::Modified => "geändert",
+        }
+    }
+}
+
+pub fn chunk_state(dirty: bool, modified: bool, meshed: bool) -> ChunkState {
+    if modified {
+        ChunkState::Modified
+    } else if !meshed {
+        ChunkState::Generated
+    } else if dirty {
+        ChunkState::Meshing
+    } else {
+        ChunkState::Clean
+    }
+}
+
+#[derive(Component)]
+pub struct MinimapRoot;
+
+#[derive(Resource)]
+pub struct MinimapImage(pub Handle<Image>);
+
+const EMPTY: [u8; 4] = [0, 0, 0, 140];
+const VIEW_RING: [u8; 4] = [255, 255, 255, 200];
+const UNLOAD_RING: [u8; 4] = [200, 40, 40, 200];
+const CAMERA: [u8; 4] = [255, 255, 255, 255];
+
+fn rgba(color: Color) -> [u8; 4] {
+    color.to_srgba().to_u8_array()
+}
+
+/// RGBA-Pixel der Minimap, Kantenlänge `2 * unload_radius + 1`, Kamera in der Mitte,
+/// -Z oben. `columns` sind Chunk-Säulen (x, z) relativ zur Kamera.
+pub fn minimap_pixels(cfg: &ChunkStreamConfig, columns: &HashMap<IVec2, ChunkState>) -> (u32, Vec<u8>) {
+    let r = cfg.unload_radius.max(cfg.view_radius).max(0);
+    let side = 2 * r + 1;
+    let mut px = Vec::with_capacity((side * side * 4) as usize);
+
+    for z in -r..=r {
+        for x in -r..=r {
+            let d = x.abs().max(z.abs());
+            let c = match columns.get(&IVec2::new(x, z)) {
+                _ if x == 0 && z == 0 => CAMERA,
+                Some(&state) => rgba(state.color()),
+                None if d == cfg.view_radius => VIEW_RING,
+                None if d == cfg.unload_radius => UNLOAD_RING,
+                None => EMPTY,
+            };
+            px.extend_from_slice(&c);
+        }
+    }
+    (side as u32, px)
+}
+
+fn minimap_image(side: u32) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d { width: side, height: side, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &EMPTY,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::nearest();
+    image
+}
+
+pub fn spawn_minimap(mut commands: Commands, mut images: ResMut<Assets<Image>>, gizmos: Res<ChunkGizmos>) {
+    let handle = images.add(minimap_image(1));
+    commands.insert_resource(MinimapImage(handle.clone()));
+
+    commands
+        .spawn((
+            MinimapRoot,
+            DespawnOnExit(InWorld),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: px(12),
+                right: px(12),
+                flex_direction: FlexDirection::Row,
+                column_gap: px(8),
+                ..default()
+            },
+            if gizmos.enabled { Visibility::Inherited } else { Visibility::Hidden },
+        ))
+        .with_children(|p| {
+            // Legende in den Farben der Zustände
+            p.spawn((Text::default(), TextFont { font_size: 12.0, ..default() }))
+                .with_children(|t| {
+                    for state in ChunkState::ALL {
+                        t.spawn((
+                            TextSpan::new(format!("{}\n", state.label())),
+                            TextFont { font_size: 12.0, ..default() },
+                            TextColor(state.color()),
+                        ));
+                    }
+                });
+            p.spawn((
+                ImageNode::new(handle),
+                Node { width: px(MINIMAP_PX), height: px(MINIMAP_PX), ..default() },
+            ));
+        });
+}
+
+pub fn toggle_chunk_gizmos(
+    actions: Res<ActionState>,
+    mut gizmos: ResMut<ChunkGizmos>,
+    mut q: Query<&mut Visibility, With<MinimapRoot>>,
+) {
+    if !actions.just_pressed(Action::ToggleChunkGizmos) {
+        return;
+    }
+    gizmos.enabled = !gizmos.enabled;
+    for mut vis in &mut q {
+        *vis = if gizmos.enabled { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+pub fn draw_chunk_gizmos(
+    enabled: Res<ChunkGizmos>,
+    cfg: Res<ChunkStreamConfig>,
+    queue: Res<ChunkLoadQueue>,
+    chunks: Query<(&ChunkPos, Has<ChunkDirty>, Has<ChunkModified>, Has<ChunkMeshStats>)>,
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
+    minimap: Res<MinimapImage>,
+    mut images: ResMut<Assets<Image>>,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.enabled {
+        return;
+    }
+    let Ok(cam) = cam_q.single() else { return; };
+    let center = world_to_chunk_pos(cam.translation()).0;
+
+    let states = chunks
+        .iter()
+        .map(|(pos, dirty, modified, meshed)| (*pos, chunk_state(dirty, modified, meshed)))
+        .chain(queue.queued.iter().map(|&pos| (pos, ChunkState::Queued)));
+
+    let size = CHUNK_SIZE.as_vec3();
+    let mut columns: HashMap<IVec2, ChunkState> = HashMap::new();
+    for (pos, state) in states {
+        let d = pos.0 - center;
+        let col = columns.entry(d.xz()).or_insert(state);
+        *col = (*col).max(state);
+
+        if d.x.abs() <= BORDER_RADIUS && d.z.abs() <= BORDER_RADIUS && d.y.abs() <= BORDER_RADIUS_Y {
+            // minimal kleiner, damit sich Nachbarkanten nicht überdecken
+            let tf = Transform::from_translation(chunk_origin_world(pos) + size * 0.5).with_scale(size - 0.1);
+            gizmos.cuboid(tf, state.color());
+        }
+    }
+
+    let (side, pixels) = minimap_pixels(&cfg, &columns);
+    let Some(image) = images.get_mut(&minimap.0) else { return; };
+    if image.width() != side {
+        *image = minimap_image(side);
+    }
+    image.data = Some(pixels);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimap_marks_camera_rings_and_states() {
+        let cfg = ChunkStreamConfig {
+            view_radius: 2,
+            unload_radius: 3,
+            tick_seconds: 0.2,
+            y_min: 0,
+            y_max: 0,
+            load_budget: 1,
+            lod_rings: [4, 8, 12],
+        };
+        let columns: HashMap<IVec2, ChunkState> =
+            [(IVec2::new(1, 0), ChunkState::Modified), (IVec2::new(-3, -3), ChunkState::Queued)].into_iter().collect();
+
+        let (side, px) = minimap_pixels(&cfg, &columns);
+        assert_eq!(side, 7);
+        assert_eq!(px.len(), 7 * 7 * 4);
+
+        let at = |x: i32, z: i32| {
+            let i = (((z + 3) * 7 + (x + 3)) * 4) as usize;
+            [px[i], px[i + 1], px[i + 2], px[i + 3]]
+        };
+        assert_eq!(at(0, 0), CAMERA);
+        assert_eq!(at(1, 0), rgba(ChunkState::Modified.color()));
+        assert_eq!(at(-3, -3), rgba(ChunkState::Queued.color()));
+        assert_eq!(at(2, 0), VIEW_RING);
+        assert_eq!(at(3, 1), UNLOAD_RING);
+        assert_eq!(at(1, 1), EMPTY);
+    }
+
+    #[test]
+    fn modified_wins_over_everything() {
+        assert_eq!(chunk_state(true, true, false), ChunkState::Modified);
+        assert_eq!(chunk_state(true, false, false), ChunkState::Generated);
+        assert_eq!(chunk_state(true, false, true), ChunkState::Meshing);
+        assert_eq!(chunk_state(false, false, true), ChunkState::Clean);
+    }
+}
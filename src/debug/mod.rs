@@ -0,0 +1,10 @@
+//! Entwickler-Werkzeuge im Spiel: F3-Overlay mit Chunk- und Performance-Zahlen,
+//! F4 Chunk-Grenzen nach Zustand eingefärbt plus Minimap.
+
+mod chunk_gizmos;
+mod overlay;
+mod plugin;
+
+pub use chunk_gizmos::{ChunkGizmos, ChunkState};
+pub use overlay::DebugOverlay;
+pub use plugin::DebugPlugin;
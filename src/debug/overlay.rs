@@ -0,0 +1,136 @@
+//! F3: Text oben rechts mit FPS, Kamera, Chunk-Zahlen, Mesh-Größe und dem
+//! anvisierten Block. Wird nur neu geschrieben, solange es sichtbar ist.
+
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use std::fmt::Write as _;
+
+use crate::app_state::InWorld;
+use crate::input::{Action, ActionState};
+use crate::voxel::chunk::{Block, ChunkData, ChunkDirty, block_to_chunk, world_to_chunk_pos};
+use crate::voxel::chunk_stream::ChunkLoadQueue;
+use crate::voxel::raycast::raycast_blocks;
+use crate::voxel::{ChunkMeshStats, MeshingStats, VoxelWorld};
+
+/// Wie weit der Block unter dem Fadenkreuz gesucht wird.
+const LOOK_REACH: f32 = 64.0;
+
+#[derive(Resource, Default, Debug)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+#[derive(Component)]
+pub struct DebugOverlayText;
+
+pub fn spawn_debug_overlay(mut commands: Commands, overlay: Res<DebugOverlay>) {
+    commands.spawn((
+        DebugOverlayText,
+        DespawnOnExit(InWorld),
+        Text::default(),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            right: px(12),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        if overlay.visible { Visibility::Inherited } else { Visibility::Hidden },
+    ));
+}
+
+pub fn toggle_debug_overlay(
+    actions: Res<ActionState>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut q: Query<&mut Visibility, With<DebugOverlayText>>,
+) {
+    if !actions.just_pressed(Action::ToggleDebugOverlay) {
+        return;
+    }
+    overlay.visible = !overlay.visible;
+    for mut vis in &mut q {
+        *vis = if overlay.visible { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+pub fn update_debug_overlay(
+    overlay: Res<DebugOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    world: Res<VoxelWorld>,
+    queue: Res<ChunkLoadQueue>,
+    meshing: Res<MeshingStats>,
+    chunks: Query<&ChunkData>,
+    dirty: Query<(), With<ChunkDirty>>,
+    mesh_stats: Query<&ChunkMeshStats>,
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
+    mut text: Query<&mut Text, With<DebugOverlayText>>,
+) {
+    if !overlay.visible {
+        return;
+    }
+    let Ok(mut text) = text.single_mut() else { return; };
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|d| d.smoothed())
+        .unwrap_or_default();
+    let frame_ms = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|d| d.smoothed())
+        .unwrap_or_default();
+
+    let (vertices, triangles) = mesh_stats
+        .iter()
+        .fold((0u64, 0u64), |(v, t), s| (v + s.vertices as u64, t + s.triangles as u64));
+
+    let mut s = String::new();
+    let _ = writeln!(s, "{fps:.0} FPS ({frame_ms:.2} ms)");
+
+    if let Ok(cam) = cam_q.single() {
+        let pos = cam.translation();
+        let chunk = world_to_chunk_pos(pos).0;
+        let _ = writeln!(s, "XYZ {:.1} / {:.1} / {:.1}", pos.x, pos.y, pos.z);
+        let _ = writeln!(s, "Chunk {} {} {}", chunk.x, chunk.y, chunk.z);
+
+        let get = |p: IVec3| {
+            let (c, local) = block_to_chunk(p);
+            world
+                .chunks
+                .get(&c)
+                .and_then(|&e| chunks.get(e).ok())
+                .map(|data| data.get_local(local.x, local.y, local.z))
+                .unwrap_or(Block::Air)
+        };
+        match raycast_blocks(pos, cam.forward().as_vec3(), LOOK_REACH, get) {
+            Some(hit) => {
+                let b = hit.block;
+                let _ = writeln!(s, "Block {} bei {} {} {}", get(b).key(), b.x, b.y, b.z);
+            }
+            None => {
+                let _ = writeln!(s, "Block -");
+            }
+        }
+    }
+
+    let _ = writeln!(s);
+    let _ = writeln!(
+        s,
+        "Chunks {}  Queue {}  dirty {}",
+        world.chunks.len(),
+        queue.fifo.len(),
+        dirty.iter().count()
+    );
+    let _ = writeln!(s, "Vertices {vertices}  Dreiecke {triangles}");
+    let _ = write!(
+        s,
+        "Meshing {:.2} ms ({} Chunks)",
+        meshing.last_frame.as_secs_f64() * 1000.0,
+        meshing.chunks_last_frame
+    );
+
+    text.0 = s;
+}
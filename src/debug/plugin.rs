@@ -0,0 +1,29 @@
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::prelude::*;
+
+use crate::app_state::{AppState, InWorld};
+
+use super::chunk_gizmos::{ChunkGizmos, draw_chunk_gizmos, spawn_minimap, toggle_chunk_gizmos};
+use super::overlay::{DebugOverlay, spawn_debug_overlay, toggle_debug_overlay, update_debug_overlay};
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+
+        app.init_resource::<DebugOverlay>()
+            .init_resource::<ChunkGizmos>()
+            .add_systems(OnEnter(InWorld), (spawn_debug_overlay, spawn_minimap))
+            .add_systems(
+                Update,
+                (
+                    (toggle_debug_overlay, update_debug_overlay).chain(),
+                    (toggle_chunk_gizmos, draw_chunk_gizmos).chain(),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
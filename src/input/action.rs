@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    /// Flycam: nach oben
+    Jump,
+    /// Flycam: nach unten
+    Crouch,
+    Sprint,
+    /// anvisierten Block abbauen
+    Break,
+    /// `PlaceBlock` vor den anvisierten Block setzen
+    Place,
+    /// nächster / vorheriger Block für `Place`
+    HotbarNext,
+    HotbarPrev,
+    Undo,
+    Redo,
+    SelectCornerA,
+    SelectCornerB,
+    ClearSelection,
+    RotatePaste,
+    Fill,
+    Hollow,
+    Replace,
+    Copy,
+    Paste,
+    ExportVox,
+    ImportVox,
+    ExportMesh,
+    ExportMeshObj,
+    ToggleWireframe,
+    ToggleDebugOverlay,
+    ToggleChunkGizmos,
+    /// Fly -> Third-Person -> Orbit
+    CycleCamera,
+    /// Kamera als Pfadpunkt anhängen
+    RecordPathPoint,
+    PlayPath,
+    /// Pause im Spiel, in Menüs eine Ebene zurück
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 33] = [
+        Action::MoveForward,
+        Action::MoveBack,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Crouch,
+        Action::Sprint,
+        Action::Break,
+        Action::Place,
+        Action::HotbarNext,
+        Action::HotbarPrev,
+        Action::Undo,
+        Action::Redo,
+        Action::SelectCornerA,
+        Action::SelectCornerB,
+        Action::ClearSelection,
+        Action::RotatePaste,
+        Action::Fill,
+        Action::Hollow,
+        Action::Replace,
+        Action::Copy,
+        Action::Paste,
+        Action::ExportVox,
+        Action::ImportVox,
+        Action::ExportMesh,
+        Action::ExportMeshObj,
+        Action::ToggleWireframe,
+        Action::ToggleDebugOverlay,
+        Action::ToggleChunkGizmos,
+        Action::CycleCamera,
+        Action::RecordPathPoint,
+        Action::PlayPath,
+        Action::Pause,
+    ];
+}
+
+/// Zustand aller Aktionen in diesem Frame, wie `ButtonInput`. Dazu die
+/// analogen Sticks, schon mit Deadzone und Kurve.
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    just_released: HashSet<Action>,
+    /// x = rechts, y = vorwärts, Länge <= 1
+    move_axis: Vec2,
+    /// Radiant pro Sekunde, x = nach rechts, y = nach oben
+    look_axis: Vec2,
+    /// von `release_all_except` in diesem bzw. im letzten Frame losgelassen,
+    /// obwohl die Taste noch gehalten wird
+    force_released: HashSet<Action>,
+    force_released_before: HashSet<Action>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    pub fn move_axis(&self) -> Vec2 {
+        self.move_axis
+    }
+
+    pub fn look_axis(&self) -> Vec2 {
+        self.look_axis
+    }
+
+    pub fn set_axes(&mut self, move_axis: Vec2, look_axis: Vec2) {
+        self.move_axis = move_axis;
+        self.look_axis = look_axis;
+    }
+
+    /// Neuen Frame übernehmen; losgelassen ist, was vorher gehalten war und jetzt nicht mehr.
+    pub fn update(&mut self, pressed: HashSet<Action>, just_pressed: HashSet<Action>) {
+        self.just_released = self.pressed.difference(&pressed).copied().collect();
+        self.pressed = pressed;
+        self.just_pressed = just_pressed;
+        self.force_released_before = std::mem::take(&mut self.force_released);
+    }
+
+    /// Alles außer `keep` loslassen (z.B. solange UI den Fokus hat). Nach `update`
+    /// im selben Frame aufrufen; `just_released` meldet eine gehaltene Taste nur
+    /// im ersten Frame, nicht in jedem, in dem sie weiter unterdrückt wird.
+    pub fn release_all_except(&mut self, keep: &[Action]) {
+        let released: Vec<Action> = self.pressed.iter().copied().filter(|a| !keep.contains(a)).collect();
+        for action in released {
+            self.pressed.remove(&action);
+            if !self.force_released_before.contains(&action) {
+                self.just_released.insert(action);
+            }
+            self.force_released.insert(action);
+        }
+        self.just_pressed.retain(|a| keep.contains(a));
+        self.set_axes(Vec2::ZERO, Vec2::ZERO);
+    }
+}
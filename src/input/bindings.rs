@@ -0,0 +1,355 @@
+use anyhow::Context;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use super::action::Action;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+}
+
+impl Modifier {
+    /// links und rechts zählen gleich
+    pub fn keys(self) -> [KeyCode; 2] {
+        match self {
+            Modifier::Ctrl => [KeyCode::ControlLeft, KeyCode::ControlRight],
+            Modifier::Shift => [KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            Modifier::Alt => [KeyCode::AltLeft, KeyCode::AltRight],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputButton {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// irgendein angeschlossenes Gamepad
+    Gamepad(GamepadButton),
+}
+
+/// Eine Taste, optional mit gehaltenen Modifiern. In RON:
+/// `(button: Key(KeyZ), modifiers: [Ctrl])`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Binding {
+    pub button: InputButton,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<Modifier>,
+}
+
+impl Binding {
+    pub fn new(button: InputButton) -> Self {
+        Self { button, modifiers: Vec::new() }
+    }
+
+    pub fn key(key: KeyCode) -> Self {
+        Self::new(InputButton::Key(key))
+    }
+
+    pub fn with(mut self, modifier: Modifier) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    /// Gleiche Taste, aber zusätzliche Modifier -> spezifischer. Strg+Z schlägt Z.
+    fn more_specific_than(&self, other: &Binding) -> bool {
+        self.button == other.button
+            && self.modifiers.len() > other.modifiers.len()
+            && other.modifiers.iter().all(|m| self.modifiers.contains(m))
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in &self.modifiers {
+            match m {
+                Modifier::Ctrl => write!(f, "Strg+")?,
+                Modifier::Shift => write!(f, "Shift+")?,
+                Modifier::Alt => write!(f, "Alt+")?,
+            }
+        }
+        match self.button {
+            InputButton::Key(k) => {
+                let name = format!("{k:?}");
+                let short = name.strip_prefix("Key").or(name.strip_prefix("Digit")).unwrap_or(&name);
+                write!(f, "{short}")
+            }
+            InputButton::Mouse(b) => write!(f, "Maus {b:?}"),
+            InputButton::Gamepad(b) => write!(f, "Pad {b:?}"),
+        }
+    }
+}
+
+/// Sticks: linker bewegt, rechter schaut.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamepadConfig {
+    /// Auslenkung (0..1), unter der ein Stick als losgelassen gilt
+    pub deadzone: f32,
+    /// Exponent nach der Deadzone: 1 = linear, größer = feiner um die Mitte
+    pub move_curve: f32,
+    pub look_curve: f32,
+    /// Drehgeschwindigkeit bei Vollausschlag, Radiant pro Sekunde
+    pub look_speed: f32,
+    pub invert_y: bool,
+}
+
+impl Default for GamepadConfig {
+    fn default() -> Self {
+        Self { deadzone: 0.15, move_curve: 1.0, look_curve: 2.0, look_speed: 3.0, invert_y: false }
+    }
+}
+
+impl GamepadConfig {
+    /// Radiale Deadzone, danach wieder auf 0..1 gestreckt (kein Sprung an der
+    /// Kante) und durch die Kurve geschickt. Richtung bleibt erhalten.
+    pub fn shape(&self, stick: Vec2, curve: f32) -> Vec2 {
+        let len = stick.length();
+        if len <= self.deadzone {
+            return Vec2::ZERO;
+        }
+        let t = ((len - self.deadzone) / (1.0 - self.deadzone)).min(1.0);
+        stick / len * t.powf(curve)
+    }
+}
+
+/// Inhalt von `keybindings.ron`. Aktionen, die in der Datei fehlen, behalten
+/// ihre Standardbelegung; eine leere Liste heißt "nicht belegt".
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyBindings {
+    pub bindings: BTreeMap<Action, Vec<Binding>>,
+    #[serde(default)]
+    pub gamepad: GamepadConfig,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use Action::*;
+        use KeyCode as K;
+        use Modifier::{Ctrl, Shift};
+
+        let key = Binding::key;
+        let pad = |b| Binding::new(InputButton::Gamepad(b));
+        let mouse = |b| Binding::new(InputButton::Mouse(b));
+
+        let bindings = [
+            (MoveForward, vec![key(K::KeyW)]),
+            (MoveBack, vec![key(K::KeyS)]),
+            (MoveLeft, vec![key(K::KeyA)]),
+            (MoveRight, vec![key(K::KeyD)]),
+            (Jump, vec![key(K::Space), pad(GamepadButton::South)]),
+            // nicht Strg: das gehört den Editor-Kombinationen
+            (Crouch, vec![key(K::KeyC), pad(GamepadButton::East)]),
+            (Sprint, vec![key(K::ShiftLeft), pad(GamepadButton::LeftThumb)]),
+            (Break, vec![mouse(MouseButton::Left), pad(GamepadButton::RightTrigger2)]),
+            (Place, vec![mouse(MouseButton::Right), pad(GamepadButton::LeftTrigger2)]),
+            (HotbarNext, vec![key(K::BracketRight), pad(GamepadButton::RightTrigger)]),
+            (HotbarPrev, vec![key(K::BracketLeft), pad(GamepadButton::LeftTrigger)]),
+            (Undo, vec![key(K::KeyZ).with(Ctrl)]),
+            (Redo, vec![key(K::KeyY).with(Ctrl), key(K::KeyZ).with(Ctrl).with(Shift)]),
+            (SelectCornerA, vec![key(K::Digit1)]),
+            (SelectCornerB, vec![key(K::Digit2)]),
+            (ClearSelection, vec![key(K::KeyE)]),
+            (RotatePaste, vec![key(K::KeyT)]),
+            (Fill, vec![key(K::KeyF)]),
+            (Hollow, vec![key(K::KeyH)]),
+            (Replace, vec![key(K::KeyR)]),
+            (Copy, vec![key(K::KeyC).with(Ctrl)]),
+            (Paste, vec![key(K::KeyV).with(Ctrl)]),
+            (ExportVox, vec![key(K::KeyE).with(Ctrl)]),
+            (ImportVox, vec![key(K::KeyI).with(Ctrl)]),
+            (ExportMesh, vec![key(K::F8)]),
+            (ExportMeshObj, vec![key(K::F8).with(Shift)]),
+            (ToggleWireframe, vec![key(K::KeyZ)]),
+            (ToggleDebugOverlay, vec![key(K::F3)]),
+            (ToggleChunkGizmos, vec![key(K::F4)]),
+            (CycleCamera, vec![key(K::F5), pad(GamepadButton::Select)]),
+            (RecordPathPoint, vec![key(K::KeyP)]),
+            (PlayPath, vec![key(K::KeyP).with(Shift)]),
+            (Pause, vec![key(K::Escape), pad(GamepadButton::Start)]),
+        ];
+        Self { bindings: bindings.into_iter().collect(), gamepad: GamepadConfig::default() }
+    }
+}
+
+impl KeyBindings {
+    /// Fehlt die Datei, wird sie mit den Standardbelegungen angelegt (als Vorlage zum Editieren).
+    pub fn load_or_create(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            let bindings = Self::default();
+            bindings.save(path)?;
+            return Ok(bindings);
+        }
+        let s = std::fs::read_to_string(path).with_context(|| format!("{} lesen", path.display()))?;
+        let loaded: KeyBindings = ron::from_str(&s).with_context(|| format!("{} parsen", path.display()))?;
+        Ok(loaded.with_defaults())
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let s = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, s).with_context(|| format!("{} schreiben", path.display()))
+    }
+
+    /// Fehlende Aktionen mit der Standardbelegung auffüllen.
+    fn with_defaults(mut self) -> Self {
+        for (action, bindings) in Self::default().bindings {
+            self.bindings.entry(action).or_insert(bindings);
+        }
+        self
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Für Hilfetexte: "Strg+Y / Strg+Shift+Z", "-" wenn nicht belegt.
+    pub fn describe(&self, action: Action) -> String {
+        let list = self.get(action);
+        if list.is_empty() {
+            return "-".to_string();
+        }
+        list.iter().map(ToString::to_string).collect::<Vec<_>>().join(" / ")
+    }
+
+    /// Gedrückte und frisch gedrückte Aktionen aus dem Zustand der Tasten.
+    /// Eine Belegung zählt nicht, wenn gerade eine spezifischere mit derselben
+    /// Taste greift (Strg+Z ist Undo, nicht auch noch Wireframe). Ebenso zählt
+    /// ein Modifier als eigene Taste nicht, solange er Teil einer gedrückten
+    /// Kombination ist (Strg+Z ist Undo, nicht auch noch Strg allein).
+    pub fn evaluate(
+        &self,
+        pressed: impl Fn(InputButton) -> bool,
+        just_pressed: impl Fn(InputButton) -> bool,
+    ) -> (HashSet<Action>, HashSet<Action>) {
+        let held = |m: Modifier| m.keys().iter().any(|&k| pressed(InputButton::Key(k)));
+        let active = |b: &Binding| b.modifiers.iter().all(|&m| held(m));
+        let chord_with = |key: KeyCode| {
+            self.bindings.values().flatten().any(|c| {
+                c.modifiers.iter().any(|m| m.keys().contains(&key)) && pressed(c.button) && active(c)
+            })
+        };
+        let shadowed = |b: &Binding| {
+            self.bindings.values().flatten().any(|other| other.more_specific_than(b) && active(other))
+                || matches!(b.button, InputButton::Key(k) if chord_with(k))
+        };
+
+        let mut out = (HashSet::new(), HashSet::new());
+        for (&action, list) in &self.bindings {
+            for b in list {
+                if !pressed(b.button) || !active(b) || shadowed(b) {
+                    continue;
+                }
+                out.0.insert(action);
+                if just_pressed(b.button) {
+                    out.1.insert(action);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(held: &[KeyCode], new: KeyCode) -> (HashSet<Action>, HashSet<Action>) {
+        let mut all: Vec<KeyCode> = held.to_vec();
+        all.push(new);
+        KeyBindings::default().evaluate(
+            |b| matches!(b, InputButton::Key(k) if all.contains(&k)),
+            |b| b == InputButton::Key(new),
+        )
+    }
+
+    #[test]
+    fn modifiers_pick_the_most_specific_binding() {
+        let (_, just) = eval(&[], KeyCode::KeyZ);
+        assert!(just.contains(&Action::ToggleWireframe));
+
+        let (_, just) = eval(&[KeyCode::ControlRight], KeyCode::KeyZ);
+        assert!(just.contains(&Action::Undo));
+        assert!(!just.contains(&Action::ToggleWireframe));
+
+        let (_, just) = eval(&[KeyCode::ControlLeft, KeyCode::ShiftLeft], KeyCode::KeyZ);
+        assert!(just.contains(&Action::Redo));
+        assert!(!just.contains(&Action::Undo));
+
+        // Sprinten schließt Laufen nicht aus
+        let (held, _) = eval(&[KeyCode::ShiftLeft], KeyCode::KeyW);
+        assert!(held.contains(&Action::Sprint) && held.contains(&Action::MoveForward));
+    }
+
+    #[test]
+    fn default_chords_do_not_move() {
+        use Action::*;
+        let movement = [MoveForward, MoveBack, MoveLeft, MoveRight, Jump, Crouch, Sprint];
+        let defaults = KeyBindings::default();
+
+        for (action, b) in defaults.bindings.iter().flat_map(|(a, list)| list.iter().map(move |b| (a, b))) {
+            let InputButton::Key(key) = b.button else { continue; };
+            if b.modifiers.is_empty() {
+                continue;
+            }
+            let held: Vec<KeyCode> = b.modifiers.iter().map(|m| m.keys()[0]).collect();
+            let (pressed, just) = eval(&held, key);
+            assert!(just.contains(action), "{b} löst {action:?} nicht aus");
+            for m in movement {
+                assert!(!pressed.contains(&m), "{b} ({action:?}) löst auch {m:?} aus");
+            }
+        }
+    }
+
+    #[test]
+    fn chords_swallow_their_bare_modifier() {
+        // z.B. aus einer älteren keybindings.ron: runter auf Strg
+        let mut bindings = KeyBindings::default();
+        bindings.bindings.insert(Action::Crouch, vec![Binding::key(KeyCode::ControlLeft)]);
+        let eval = |keys: &[KeyCode]| {
+            bindings.evaluate(|b| matches!(b, InputButton::Key(k) if keys.contains(&k)), |_| false).0
+        };
+
+        assert!(eval(&[KeyCode::ControlLeft]).contains(&Action::Crouch));
+        for key in [KeyCode::KeyZ, KeyCode::KeyY, KeyCode::KeyC, KeyCode::KeyV, KeyCode::KeyE, KeyCode::KeyI] {
+            let held = eval(&[KeyCode::ControlLeft, key]);
+            assert!(!held.contains(&Action::Crouch), "Strg+{key:?}");
+            assert!(!held.is_empty(), "Strg+{key:?} löst nichts aus");
+        }
+        // Taste ohne Strg-Belegung: Strg bleibt Strg
+        assert!(eval(&[KeyCode::ControlLeft, KeyCode::KeyW]).contains(&Action::Crouch));
+    }
+
+    #[test]
+    fn file_overrides_and_falls_back_to_defaults() {
+        let ron = "(bindings: { MoveForward: [(button: Key(ArrowUp))], Pause: [] })";
+        let loaded: KeyBindings = ron::from_str(ron).unwrap();
+        let b = loaded.with_defaults();
+        assert_eq!(b.gamepad, GamepadConfig::default());
+
+        assert_eq!(b.get(Action::MoveForward), &[Binding::key(KeyCode::ArrowUp)]);
+        assert!(b.get(Action::Pause).is_empty());
+        assert_eq!(b.get(Action::MoveBack), KeyBindings::default().get(Action::MoveBack));
+        assert_eq!(b.describe(Action::Redo), "Strg+Y / Strg+Shift+Z");
+
+        // die beim ersten Start geschriebene Vorlage muss sich wieder laden lassen
+        let template = ron::ser::to_string_pretty(&KeyBindings::default(), Default::default()).unwrap();
+        assert_eq!(ron::from_str::<KeyBindings>(&template).unwrap(), KeyBindings::default());
+    }
+
+    #[test]
+    fn sticks_have_deadzone_and_curve() {
+        let cfg = GamepadConfig { deadzone: 0.2, ..default() };
+
+        assert_eq!(cfg.shape(Vec2::new(0.1, -0.15), 1.0), Vec2::ZERO);
+        // direkt hinter der Deadzone fängt es bei 0 an, Vollausschlag bleibt 1
+        assert!(cfg.shape(Vec2::new(0.21, 0.0), 1.0).x < 0.02);
+        assert!((cfg.shape(Vec2::new(0.0, -1.0), 2.0) - Vec2::NEG_Y).length() < 1e-6);
+        // Kurve: halber Weg -> ein Viertel
+        assert!((cfg.shape(Vec2::new(0.6, 0.0), 2.0).x - 0.25).abs() < 1e-6);
+    }
+}
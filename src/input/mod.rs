@@ -0,0 +1,15 @@
+//! Eingabe über Aktionen statt fester Tasten. Welche Taste, Maustaste oder
+//! Gamepad-Taste eine Aktion auslöst, steht in `keybindings.ron`
+//! (Arbeitsverzeichnis, wird beim ersten Start mit den Standardbelegungen angelegt).
+//! Systeme fragen nur noch `ActionState` ab, dort liegen auch die Gamepad-Sticks
+//! (Deadzone und Kurve aus `GamepadConfig`, ebenfalls in `keybindings.ron`).
+//!
+//! Die Konsole liest weiter rohe Tastatur-Events - sie ist ein Textfeld.
+
+mod action;
+mod bindings;
+mod plugin;
+
+pub use action::{Action, ActionState};
+pub use bindings::{Binding, GamepadConfig, InputButton, KeyBindings, Modifier};
+pub use plugin::{ActionSystems, InputActionsPlugin};
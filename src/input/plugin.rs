@@ -0,0 +1,132 @@
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+use std::path::Path;
+
+use crate::camera::ui_open;
+use crate::console::{ConsoleAppExt, ConsoleArgs, ConsoleResult};
+
+use super::action::{Action, ActionState};
+use super::bindings::{InputButton, KeyBindings};
+
+const BINDINGS_FILE: &str = "keybindings.ron";
+
+/// `ActionState` wird hier aktualisiert. Wer Tasten für den Rest des Frames
+/// schlucken will (Konsole, Menüs), läuft davor.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSystems;
+
+pub struct InputActionsPlugin;
+
+impl Plugin for InputActionsPlugin {
+    fn build(&self, app: &mut App) {
+        let bindings = KeyBindings::load_or_create(Path::new(BINDINGS_FILE)).unwrap_or_else(|e| {
+            error!("Tastenbelegung: {e:#}, nehme Standardbelegung");
+            KeyBindings::default()
+        });
+
+        app.insert_resource(bindings)
+            .init_resource::<ActionState>()
+            .configure_sets(PreUpdate, ActionSystems.after(InputSystems))
+            .add_systems(
+                PreUpdate,
+                (update_action_state, release_actions_while_ui_open.run_if(ui_open)).chain().in_set(ActionSystems),
+            )
+            .add_console_command("keys", "", "Tastenbelegung anzeigen", keys_command);
+    }
+}
+
+fn update_action_state(
+    bindings: Res<KeyBindings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut state: ResMut<ActionState>,
+) {
+    let (pressed, just_pressed) = bindings.evaluate(
+        |b| match b {
+            InputButton::Key(k) => keys.pressed(k),
+            InputButton::Mouse(m) => mouse.pressed(m),
+            InputButton::Gamepad(g) => gamepads.iter().any(|pad| pad.pressed(g)),
+        },
+        |b| match b {
+            InputButton::Key(k) => keys.just_pressed(k),
+            InputButton::Mouse(m) => mouse.just_pressed(m),
+            InputButton::Gamepad(g) => gamepads.iter().any(|pad| pad.just_pressed(g)),
+        },
+    );
+    state.update(pressed, just_pressed);
+
+    // mehrere Gamepads: der stärker ausgelenkte Stick gewinnt
+    let strongest = |stick: fn(&Gamepad) -> Vec2| {
+        gamepads.iter().map(stick).max_by(|a, b| a.length_squared().total_cmp(&b.length_squared())).unwrap_or_default()
+    };
+    let pad = &bindings.gamepad;
+    let move_axis = pad.shape(strongest(Gamepad::left_stick), pad.move_curve);
+    let mut look_axis = pad.shape(strongest(Gamepad::right_stick), pad.look_curve) * pad.look_speed;
+    if pad.invert_y {
+        look_axis.y = -look_axis.y;
+    }
+    state.set_axes(move_axis, look_axis);
+}
+
+/// Konsole oder Einstellungen offen: die Welt bekommt keine Aktionen, auch nicht
+/// vom Gamepad (Trigger, Sticks). Nur `Pause` bleibt, das schließt die Einstellungen.
+fn release_actions_while_ui_open(mut state: ResMut<ActionState>) {
+    state.release_all_except(&[Action::Pause]);
+}
+
+fn keys_command(_: In<ConsoleArgs>, bindings: Res<KeyBindings>) -> ConsoleResult {
+    let lines: Vec<String> = Action::ALL
+        .iter()
+        .map(|&a| format!("{a:?}: {}", bindings.describe(a)))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::SettingsMenu;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.insert_resource(KeyBindings::default())
+            .init_resource::<ActionState>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<SettingsMenu>()
+            .add_systems(Update, (update_action_state, release_actions_while_ui_open.run_if(ui_open)).chain());
+        app
+    }
+
+    #[test]
+    fn open_ui_releases_world_actions() {
+        let mut app = app();
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyW);
+        app.world_mut().resource_mut::<ButtonInput<MouseButton>>().press(MouseButton::Left);
+        app.update();
+        let state = app.world().resource::<ActionState>();
+        assert!(state.pressed(Action::MoveForward) && state.just_pressed(Action::Break));
+
+        // Einstellungen auf, Tasten bleiben gehalten
+        app.world_mut().resource_mut::<SettingsMenu>().open = true;
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Escape);
+        app.update();
+        let state = app.world().resource::<ActionState>();
+        assert!(!state.pressed(Action::MoveForward) && !state.pressed(Action::Break));
+        assert!(state.just_released(Action::MoveForward) && state.just_released(Action::Break));
+        // Esc schließt die Einstellungen, muss also durchkommen
+        assert!(state.just_pressed(Action::Pause));
+
+        // zweiter Frame mit offenem Menü: weiter losgelassen, aber nicht noch einmal gemeldet
+        app.update();
+        let state = app.world().resource::<ActionState>();
+        assert!(!state.pressed(Action::MoveForward) && !state.pressed(Action::Break));
+        assert!(!state.just_released(Action::MoveForward) && !state.just_released(Action::Break));
+
+        // zu -> wieder da
+        app.world_mut().resource_mut::<SettingsMenu>().open = false;
+        app.update();
+        assert!(app.world().resource::<ActionState>().pressed(Action::MoveForward));
+    }
+}
@@ -0,0 +1,12 @@
+pub mod app_state;
+pub mod bench;
+pub mod camera;
+pub mod cli;
+pub mod config;
+pub mod console;
+pub mod debug;
+pub mod input;
+pub mod menu;
+pub mod save;
+pub mod settings;
+pub mod voxel;
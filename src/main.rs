@@ -1,20 +1,20 @@
 use bevy::{color::palettes::css::WHITE, pbr::wireframe::{WireframeConfig, WireframePlugin}, prelude::*};
 
-use crate::{app_state::{AppState, LoadingProgress, despawn_loading_ui, spawn_loading_ui}, camera::Cubemap};
+use bevy_experiments::{app_state::{AppState, InMenu, InWorld, LoadingProgress, despawn_loading_ui, spawn_loading_ui}, bench, camera, config, console, debug, input::{self, Action, ActionState, KeyBindings}, menu, save, settings, voxel::{self, edit::PlaceBlock}};
 
-mod app_state;
-mod config;
-mod voxel;
-mod camera;
 
+/// Der Exit-Code kommt aus `AppExit` (z.B. Benchmark mit kaputtem Skript).
+fn main() -> AppExit {
+    let args = save::WorldArgs::from_env();
 
-fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
             WireframePlugin::default(),
         ))
         .init_state::<AppState>()
+        .add_computed_state::<InWorld>()
+        .add_computed_state::<InMenu>()
         .insert_resource(WireframeConfig {
             // The global wireframe config enables drawing of wireframes on every mesh,
             // except those with `NoWireframe`. Meshes with `Wireframe` will always have a wireframe,
@@ -26,18 +26,26 @@ fn main() {
         })
         .add_systems(OnEnter(AppState::Loading), spawn_loading_ui)
         .add_systems(OnExit(AppState::Loading), despawn_loading_ui)
+        .add_plugins(input::InputActionsPlugin)
+        .add_plugins(save::WorldSavePlugin { args: args.clone() })
+        .add_plugins(bench::BenchmarkPlugin { script: args.bench })
         .add_plugins((config::AtlasConfigPlugin, voxel::VoxelPlugin))
         .add_plugins(camera::CameraPlugin)
+        .add_plugins((debug::DebugPlugin, console::ConsolePlugin, settings::SettingsPlugin, menu::MenuPlugin))
         .add_systems(Startup, setup_scene)
-        .add_systems(Update, update_colors)
-        .add_systems(Update, exit_on_esc)
-        .add_systems(Update, advance_to_ingame_when_ready.run_if(in_state(AppState::Loading)))
+        .add_systems(OnEnter(InWorld), spawn_controls_text)
+        .add_systems(Update, update_colors.run_if(in_state(AppState::InGame)))
+        .add_systems(Update, leave_loading_when_ready.run_if(in_state(AppState::Loading)))
 
         .insert_resource(ClearColor(Color::BLACK))
-        .run();
+        .run()
 }
 
 
+/// Steuerungs-Text oben links (das F3-Overlay ist auch ein `Text`)
+#[derive(Component)]
+struct ControlsText;
+
 // setup system
 fn setup_scene(
     mut commands: Commands,
@@ -51,8 +59,13 @@ fn setup_scene(
         Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.6, 0.7, 0.0)),
     ));
 
-    // Text used to show controls
+}
+
+// Text used to show controls
+fn spawn_controls_text(mut commands: Commands) {
     commands.spawn((
+        ControlsText,
+        DespawnOnExit(InWorld),
         Text::default(),
         TextFont {
             font_size: 14.0,
@@ -65,47 +78,55 @@ fn setup_scene(
             ..default()
         },
     ));
-
 }
 
 /// This system let's you toggle various wireframe settings
 fn update_colors(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
+    bindings: Res<KeyBindings>,
+    place: Res<PlaceBlock>,
     mut config: ResMut<WireframeConfig>,
-    mut text: Single<&mut Text>,
+    mut text: Single<&mut Text, With<ControlsText>>,
 ) {
     text.0 = format!(
         "Controls
 ---------------
-Z - Toggle global
+{} - Toggle global
+{} - Debug overlay
+{} - Chunk gizmos
+{} - Camera mode
+^ - Console
+{} - Pause
+{} / {} - Block: {}
 
 WireframeConfig
 -------------
 Global: {}",
+        bindings.describe(Action::ToggleWireframe),
+        bindings.describe(Action::ToggleDebugOverlay),
+        bindings.describe(Action::ToggleChunkGizmos),
+        bindings.describe(Action::CycleCamera),
+        bindings.describe(Action::Pause),
+        bindings.describe(Action::HotbarPrev),
+        bindings.describe(Action::HotbarNext),
+        place.0.key(),
         config.global
     );
 
     // Toggle showing a wireframe on all meshes
-    if keyboard_input.just_pressed(KeyCode::KeyZ) {
+    if actions.just_pressed(Action::ToggleWireframe) {
         config.global = !config.global;
     }
 
 }
 
-fn exit_on_esc(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut app_exit_events: MessageWriter<AppExit>,
-) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        app_exit_events.write(AppExit::Success);
-    }
-}
-
-fn advance_to_ingame_when_ready(
+/// Welt per `--world` schon offen -> direkt ins Spiel, sonst ins Hauptmenü.
+fn leave_loading_when_ready(
     progress: Res<LoadingProgress>,
+    world: Option<Res<save::WorldMeta>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
-    if progress.config_loaded && progress.atlas_loaded && progress.skybox_loaded {
-        next_state.set(AppState::InGame);
+    if progress.config_loaded && progress.atlas_loaded && progress.skybox_loaded && progress.worldgen_loaded {
+        next_state.set(if world.is_some() { AppState::InGame } else { AppState::MainMenu });
     }
 }
\ No newline at end of file
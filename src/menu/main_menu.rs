@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
+use crate::settings::SettingsMenuControl;
+
+use super::widgets::{panel, screen_root, spawn_button, title};
+
+#[derive(Component, Clone, Copy, Debug)]
+pub(super) enum MainMenuButton {
+    Play,
+    Settings,
+    Quit,
+}
+
+pub(super) fn spawn_main_menu(mut commands: Commands) {
+    commands.spawn((DespawnOnExit(AppState::MainMenu), screen_root())).with_children(|root| {
+        root.spawn(panel()).with_children(|p| {
+            p.spawn(title("bevy_experiments"));
+            spawn_button(p, MainMenuButton::Play, "Spielen", 220.0);
+            spawn_button(p, MainMenuButton::Settings, "Einstellungen", 220.0);
+            spawn_button(p, MainMenuButton::Quit, "Beenden", 220.0);
+        });
+    });
+}
+
+pub(super) fn main_menu_buttons(
+    buttons: Query<(&Interaction, &MainMenuButton), Changed<Interaction>>,
+    mut next: ResMut<NextState<AppState>>,
+    mut settings: SettingsMenuControl,
+    mut exit: MessageWriter<AppExit>,
+) {
+    // Einstellungen liegen darüber
+    if settings.is_open() {
+        return;
+    }
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            MainMenuButton::Play => next.set(AppState::WorldSelect),
+            MainMenuButton::Settings => settings.set_open(true),
+            MainMenuButton::Quit => {
+                exit.write(AppExit::Success);
+            }
+        }
+    }
+}
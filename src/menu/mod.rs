@@ -0,0 +1,11 @@
+//! Menüs: Hauptmenü, Weltauswahl und Pause (Esc im Spiel). Die Einstellungen
+//! selbst kommen aus `settings`, hier wird nur geöffnet und geschlossen.
+
+mod main_menu;
+mod pause;
+mod plugin;
+mod widgets;
+mod world_select;
+
+pub use plugin::MenuPlugin;
+pub use widgets::{panel, screen_root, spawn_button, title};
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
+use crate::settings::SettingsMenuControl;
+
+use super::widgets::{panel, screen_root, spawn_button, title};
+
+#[derive(Component, Clone, Copy, Debug)]
+pub(super) enum PauseButton {
+    Resume,
+    Settings,
+    SaveAndQuit,
+}
+
+pub(super) fn spawn_pause_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            DespawnOnExit(AppState::Paused),
+            screen_root(),
+            // Spiel dahinter abdunkeln
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+        ))
+        .with_children(|root| {
+            root.spawn(panel()).with_children(|p| {
+                p.spawn(title("Pause"));
+                spawn_button(p, PauseButton::Resume, "Weiter", 240.0);
+                spawn_button(p, PauseButton::Settings, "Einstellungen", 240.0);
+                spawn_button(p, PauseButton::SaveAndQuit, "Speichern und zum Menü", 240.0);
+            });
+        });
+}
+
+pub(super) fn pause_buttons(
+    buttons: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut next: ResMut<NextState<AppState>>,
+    mut settings: SettingsMenuControl,
+) {
+    if settings.is_open() {
+        return;
+    }
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            PauseButton::Resume => next.set(AppState::InGame),
+            PauseButton::Settings => settings.set_open(true),
+            // Speichern übernimmt das Save-Plugin beim Verlassen der Welt
+            PauseButton::SaveAndQuit => next.set(AppState::MainMenu),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::app_state::{AppState, InMenu};
+use crate::input::{Action, ActionState};
+use crate::settings::SettingsMenuControl;
+
+use super::main_menu::{main_menu_buttons, spawn_main_menu};
+use super::pause::{pause_buttons, spawn_pause_menu};
+use super::widgets::button_colors;
+use super::world_select::{spawn_world_select, world_select_buttons};
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(InMenu), spawn_menu_camera)
+            .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+            .add_systems(OnEnter(AppState::WorldSelect), spawn_world_select)
+            .add_systems(OnEnter(AppState::Paused), spawn_pause_menu)
+            .add_systems(OnExit(AppState::Paused), close_settings)
+            .add_systems(OnExit(AppState::MainMenu), close_settings)
+            .add_systems(
+                Update,
+                (
+                    pause_or_back,
+                    button_colors,
+                    main_menu_buttons.run_if(in_state(AppState::MainMenu)),
+                    world_select_buttons.run_if(in_state(AppState::WorldSelect)),
+                    pause_buttons.run_if(in_state(AppState::Paused)),
+                ),
+            );
+    }
+}
+
+/// Ohne Welt gibt es keine 3D-Kamera, die UI braucht aber eine.
+fn spawn_menu_camera(mut commands: Commands) {
+    commands.spawn((DespawnOnExit(InMenu), Camera2d));
+}
+
+/// `Pause` (Esc): offene Einstellungen schließen, sonst eine Ebene zurück.
+/// Im Hauptmenü beendet es das Spiel.
+fn pause_or_back(
+    actions: Res<ActionState>,
+    state: Res<State<AppState>>,
+    mut next: ResMut<NextState<AppState>>,
+    mut settings: SettingsMenuControl,
+    mut exit: MessageWriter<AppExit>,
+) {
+    if !actions.just_pressed(Action::Pause) {
+        return;
+    }
+    if settings.is_open() {
+        settings.set_open(false);
+        return;
+    }
+    match state.get() {
+        AppState::InGame => next.set(AppState::Paused),
+        AppState::Paused => next.set(AppState::InGame),
+        AppState::WorldSelect => next.set(AppState::MainMenu),
+        AppState::MainMenu => {
+            exit.write(AppExit::Success);
+        }
+        AppState::Loading => {}
+    }
+}
+
+fn close_settings(mut settings: SettingsMenuControl) {
+    settings.set_open(false);
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+pub const BUTTON: Color = Color::srgb(0.2, 0.2, 0.25);
+pub const BUTTON_HOVER: Color = Color::srgb(0.3, 0.3, 0.4);
+pub const BUTTON_PRESSED: Color = Color::srgb(0.45, 0.45, 0.6);
+
+/// Knopf mit Text; `action` ist die Komponente, auf die das Menü-System reagiert.
+pub fn spawn_button(p: &mut ChildSpawnerCommands, action: impl Bundle, label: &str, width: f32) {
+    p.spawn((
+        Button,
+        action,
+        Node {
+            width: px(width),
+            height: px(28),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON),
+    ))
+    .with_children(|b| {
+        b.spawn((Text::new(label), TextFont { font_size: 14.0, ..default() }));
+    });
+}
+
+/// Vollbild, Inhalt zentriert.
+pub fn screen_root() -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        width: percent(100),
+        height: percent(100),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+/// Spalte mit Abstand zwischen den Einträgen und dunklem Hintergrund.
+pub fn panel() -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(8),
+            padding: UiRect::all(px(16)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.92)),
+    )
+}
+
+pub fn title(text: &str) -> impl Bundle {
+    (Text::new(text), TextFont { font_size: 22.0, ..default() })
+}
+
+/// Hover/Pressed-Farben für alle Knöpfe.
+pub fn button_colors(mut buttons: Query<(&Interaction, &mut BackgroundColor), (With<Button>, Changed<Interaction>)>) {
+    for (interaction, mut bg) in &mut buttons {
+        bg.0 = match interaction {
+            Interaction::Pressed => BUTTON_PRESSED,
+            Interaction::Hovered => BUTTON_HOVER,
+            Interaction::None => BUTTON,
+        };
+    }
+}
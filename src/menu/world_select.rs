@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
+use crate::save::{SAVES_ROOT, WorldDir, open_world};
+
+use super::widgets::{panel, screen_root, spawn_button, title};
+
+#[derive(Component, Clone, Debug)]
+pub(super) enum WorldSelectButton {
+    Open(String),
+    New,
+    Back,
+}
+
+pub(super) fn spawn_world_select(mut commands: Commands) {
+    // `.name` sind interne Welten (Benchmark)
+    let worlds: Vec<_> = WorldDir::list(SAVES_ROOT).into_iter().filter(|(d, _)| !d.name.starts_with('.')).collect();
+
+    commands.spawn((DespawnOnExit(AppState::WorldSelect), screen_root())).with_children(|root| {
+        root.spawn(panel()).with_children(|p| {
+            p.spawn(title("Welt wählen"));
+            if worlds.is_empty() {
+                p.spawn((Text::new("Noch keine Welten"), TextFont { font_size: 14.0, ..default() }));
+            }
+            for (dir, meta) in &worlds {
+                let label = format!("{}  (Seed {})", dir.name, meta.seed);
+                spawn_button(p, WorldSelectButton::Open(dir.name.clone()), &label, 280.0);
+            }
+            spawn_button(p, WorldSelectButton::New, "Neue Welt", 280.0);
+            spawn_button(p, WorldSelectButton::Back, "Zurück", 280.0);
+        });
+    });
+}
+
+pub(super) fn world_select_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &WorldSelectButton), Changed<Interaction>>,
+    mut next: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let name = match button {
+            WorldSelectButton::Back => {
+                next.set(AppState::MainMenu);
+                continue;
+            }
+            WorldSelectButton::Open(name) => name.clone(),
+            WorldSelectButton::New => {
+                let existing: Vec<String> = WorldDir::list(SAVES_ROOT).into_iter().map(|(d, _)| d.name).collect();
+                new_world_name(&existing)
+            }
+        };
+        commands.queue(move |world: &mut World| enter_world(world, &name));
+        // nur ein Klick pro Frame zählt
+        return;
+    }
+}
+
+/// Welt öffnen (bringt ihre eigene Generator-Config mit, kann eine andere
+/// als die beim Start geladene sein) und ins Spiel wechseln.
+fn enter_world(world: &mut World, name: &str) {
+    match open_world(world, name, None) {
+        Ok(()) => world.resource_mut::<NextState<AppState>>().set(AppState::InGame),
+        Err(e) => error!("Welt '{name}' öffnen: {e:#}"),
+    }
+}
+
+/// `welt-1`, `welt-2`, ... - die erste freie Nummer.
+fn new_world_name(existing: &[String]) -> String {
+    (1..)
+        .map(|i| format!("welt-{i}"))
+        .find(|n| !existing.contains(n))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_world_takes_first_free_number() {
+        assert_eq!(new_world_name(&[]), "welt-1");
+        let existing = ["welt-1".to_string(), "welt-3".to_string(), "default".to_string()];
+        assert_eq!(new_world_name(&existing), "welt-2");
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task, block_on, futures::check_ready};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::voxel::chunk::{Block, ChunkData, ChunkPos};
+use crate::voxel::chunk_store::{ChunkModified, ChunkSaveStore, write_chunk_file};
+
+/// Wie oft automatisch gespeichert wird (Welt-Metadaten + geänderte Chunks).
+#[derive(Resource, Clone, Debug)]
+pub struct AutosaveConfig {
+    pub interval_seconds: f32,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self { interval_seconds: 30.0 }
+    }
+}
+
+#[derive(Resource)]
+pub(super) struct AutosaveTimer(pub Timer);
+
+/// Sofort speichern statt aufs nächste Intervall zu warten (z.B. Konsole `save`).
+#[derive(Message, Clone, Copy, Debug)]
+pub struct SaveWorld;
+
+/// In diesem Frame wurde `SaveWorld` geschickt.
+#[derive(Resource, Default)]
+pub(super) struct SaveRequested(bool);
+
+/// Stand eines Chunks zum Zeitpunkt des Speicherns.
+/// Nach dem Schreiben wird verglichen: nur wenn der Chunk inzwischen nicht
+/// wieder geändert wurde, darf `ChunkModified` weg.
+struct Snapshot {
+    entity: Option<Entity>,
+    blocks: Arc<Vec<Block>>,
+}
+
+struct ChunkWriteJob {
+    snapshots: HashMap<ChunkPos, Snapshot>,
+    /// liefert die erfolgreich geschriebenen Chunks
+    task: Task<Vec<ChunkPos>>,
+}
+
+/// Höchstens ein Schreibjob gleichzeitig, sonst könnte ein älterer Stand
+/// einen neueren überholen.
+#[derive(Resource, Default)]
+pub(super) struct PendingChunkWrites(Option<ChunkWriteJob>);
+
+pub(super) fn tick_autosave_timer(
+    time: Res<Time>,
+    cfg: Res<AutosaveConfig>,
+    mut timer: ResMut<AutosaveTimer>,
+    mut save_now: MessageReader<SaveWorld>,
+    mut requested: ResMut<SaveRequested>,
+) {
+    requested.0 = save_now.read().count() > 0;
+
+    let interval = std::time::Duration::from_secs_f32(cfg.interval_seconds.max(1.0));
+    if timer.0.duration() != interval {
+        timer.0.set_duration(interval);
+    }
+    timer.0.tick(time.delta());
+}
+
+pub(super) fn autosave_due(timer: Res<AutosaveTimer>, requested: Res<SaveRequested>) -> bool {
+    timer.0.just_finished() || requested.0
+}
+
+/// Alle geänderten Chunks einsammeln und im Hintergrund schreiben.
+pub(super) fn start_chunk_autosave(
+    mut pending: ResMut<PendingChunkWrites>,
+    mut store: ResMut<ChunkSaveStore>,
+    modified: Query<(Entity, &ChunkPos, &ChunkData), With<ChunkModified>>,
+) {
+    // letzter Job läuft noch -> nächstes Intervall
+    if pending.0.is_some() {
+        return;
+    }
+    let Some(dir) = store.dir().map(Path::to_path_buf) else { return; };
+
+    let snapshots = collect_snapshots(&mut store, &modified);
+    if snapshots.is_empty() {
+        return;
+    }
+
+    let work: Vec<(ChunkPos, Arc<Vec<Block>>)> =
+        snapshots.iter().map(|(&pos, s)| (pos, s.blocks.clone())).collect();
+    let task = IoTaskPool::get().spawn(async move { write_chunks(&dir, &work) });
+
+    pending.0 = Some(ChunkWriteJob { snapshots, task });
+}
+
+pub(super) fn poll_chunk_autosave(
+    mut commands: Commands,
+    mut pending: ResMut<PendingChunkWrites>,
+    mut store: ResMut<ChunkSaveStore>,
+    modified: Query<&ChunkData, With<ChunkModified>>,
+) {
+    let Some(job) = pending.0.as_mut() else { return; };
+    let Some(written) = check_ready(&mut job.task) else { return; };
+
+    let job = pending.0.take().unwrap();
+    apply_written(&mut commands, &mut store, &modified, &job.snapshots, &written);
+}
+
+/// Beim Beenden alles schreiben.
+pub(super) fn save_chunks_on_exit(
+    mut exit: MessageReader<AppExit>,
+    mut commands: Commands,
+    mut pending: ResMut<PendingChunkWrites>,
+    mut store: ResMut<ChunkSaveStore>,
+    modified: Query<(Entity, &ChunkPos, &ChunkData), With<ChunkModified>>,
+    modified_data: Query<&ChunkData, With<ChunkModified>>,
+) {
+    if exit.read().count() == 0 {
+        return;
+    }
+    flush_chunks(&mut commands, &mut pending, &mut store, &modified, &modified_data);
+}
+
+/// Laufenden Job abwarten, dann den Rest synchron schreiben.
+pub(super) fn flush_chunks(
+    commands: &mut Commands,
+    pending: &mut PendingChunkWrites,
+    store: &mut ChunkSaveStore,
+    modified: &Query<(Entity, &ChunkPos, &ChunkData), With<ChunkModified>>,
+    modified_data: &Query<&ChunkData, With<ChunkModified>>,
+) {
+    let Some(dir) = store.dir().map(Path::to_path_buf) else { return; };
+
+    if let Some(job) = pending.0.take() {
+        let written = block_on(job.task);
+        apply_written(commands, store, modified_data, &job.snapshots, &written);
+    }
+
+    let snapshots = collect_snapshots(store, modified);
+    if snapshots.is_empty() {
+        return;
+    }
+
+    let work: Vec<(ChunkPos, Arc<Vec<Block>>)> =
+        snapshots.iter().map(|(&pos, s)| (pos, s.blocks.clone())).collect();
+    let written = write_chunks(&dir, &work);
+    info!("{} von {} Chunks gespeichert", written.len(), work.len());
+    apply_written(commands, store, modified_data, &snapshots, &written);
+}
+
+/// Geladene `ChunkModified`-Chunks plus alles, was beim Entladen nur im Speicher gelandet ist.
+fn collect_snapshots(
+    store: &mut ChunkSaveStore,
+    modified: &Query<(Entity, &ChunkPos, &ChunkData), With<ChunkModified>>,
+) -> HashMap<ChunkPos, Snapshot> {
+    let mut snapshots = HashMap::new();
+
+    for (entity, &pos, data) in modified {
+        store.save_chunk(pos, data);
+        snapshots.insert(pos, Snapshot { entity: Some(entity), blocks: Arc::new(data.blocks.clone()) });
+    }
+
+    for &pos in &store.unflushed {
+        if snapshots.contains_key(&pos) {
+            continue;
+        }
+        if let Some(blocks) = store.saved.get(&pos) {
+            snapshots.insert(pos, Snapshot { entity: None, blocks: Arc::new(blocks.clone()) });
+        }
+    }
+
+    snapshots
+}
+
+fn write_chunks(dir: &Path, work: &[(ChunkPos, Arc<Vec<Block>>)]) -> Vec<ChunkPos> {
+    let mut written = Vec::with_capacity(work.len());
+    for (pos, blocks) in work {
+        match write_chunk_file(dir, *pos, blocks) {
+            Ok(()) => written.push(*pos),
+            Err(e) => error!("Chunk {:?} speichern fehlgeschlagen: {e:#}", pos.0),
+        }
+    }
+    written
+}
+
+fn apply_written(
+    commands: &mut Commands,
+    store: &mut ChunkSaveStore,
+    modified: &Query<&ChunkData, With<ChunkModified>>,
+    snapshots: &HashMap<ChunkPos, Snapshot>,
+    written: &[ChunkPos],
+) {
+    for pos in written {
+        let Some(snap) = snapshots.get(pos) else { continue; };
+
+        store.mark_flushed(*pos, &snap.blocks);
+
+        if let Some(entity) = snap.entity
+            && let Ok(data) = modified.get(entity)
+            && data.blocks == *snap.blocks
+        {
+            commands.entity(entity).remove::<ChunkModified>();
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use anyhow::{Context, bail};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::WorldGenConfig;
+
+/// Version des Speicherformats; ältere Welten werden (später) migriert,
+/// neuere lehnen wir ab statt sie kaputtzuschreiben.
+pub const WORLD_FORMAT_VERSION: u32 = 1;
+
+const META_FILE: &str = "world.ron";
+
+/// Verzeichnis der aktiven Welt, z.B. `saves/default`.
+#[derive(Resource, Clone, Debug)]
+pub struct WorldDir {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl WorldDir {
+    pub fn new(root: impl AsRef<Path>, name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            path: root.as_ref().join(name),
+        }
+    }
+
+    /// Weltnamen kommen von `--world` und aus dem Menü und landen als genau ein
+    /// Verzeichnis unter `root`: keine Pfadtrenner, kein `..`, nichts Absolutes.
+    /// Ein führender `.` ist internen Welten (`.benchmark`) vorbehalten.
+    pub fn validate_name(name: &str) -> anyhow::Result<()> {
+        if name.is_empty() {
+            bail!("Weltname ist leer");
+        }
+        if name.starts_with('.') {
+            bail!("Weltname '{name}' darf nicht mit '.' beginnen");
+        }
+        let mut components = Path::new(name).components();
+        let single = matches!((components.next(), components.next()), (Some(Component::Normal(_)), None));
+        if !single || name.contains(['/', '\\']) {
+            bail!("Weltname '{name}' muss ein einfacher Verzeichnisname sein");
+        }
+        Ok(())
+    }
+
+    pub fn meta_path(&self) -> PathBuf {
+        self.path.join(META_FILE)
+    }
+
+    /// Alle Welten unter `root`, deren `world.ron` sich lesen lässt, nach Name sortiert.
+    pub fn list(root: impl AsRef<Path>) -> Vec<(WorldDir, WorldMeta)> {
+        let Ok(entries) = std::fs::read_dir(root.as_ref()) else { return Vec::new(); };
+
+        let mut worlds: Vec<(WorldDir, WorldMeta)> = entries
+            .flatten()
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| {
+                let dir = WorldDir::new(root.as_ref(), e.file_name().to_str()?);
+                match WorldMeta::load(&dir) {
+                    Ok(meta) => Some((dir, meta)),
+                    Err(e) => {
+                        warn!("{e:#}");
+                        None
+                    }
+                }
+            })
+            .collect();
+        worlds.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+        worlds
+    }
+}
+
+/// Inhalt von `world.ron`.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorldMeta {
+    pub format_version: u32,
+    pub name: String,
+    pub seed: u32,
+    pub generator: GeneratorSettings,
+    /// wird beim ersten Betreten aus der Geländehöhe bestimmt
+    #[serde(default)]
+    pub spawn: Option<(f32, f32, f32)>,
+    /// letzte Kameraposition; fehlt bei neuen Welten -> Start am Spawn
+    #[serde(default)]
+    pub camera: Option<CameraState>,
+    /// Stunden, 0..24
+    #[serde(default = "default_time_of_day")]
+    pub time_of_day: f32,
+}
+
+/// Womit die Welt generiert wird; bleibt pro Welt fest.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeneratorSettings {
+    /// Asset-Pfad der Generator-Konfiguration, aus der `params` stammen
+    pub config: String,
+    /// Aufgelöste Konfiguration samt Vorlagen, beim ersten Öffnen festgeschrieben.
+    /// Spätere Änderungen an `config` verändern die Welt dadurch nicht mehr.
+    #[serde(default)]
+    pub params: Option<WorldGenConfig>,
+}
+
+impl Default for GeneratorSettings {
+    fn default() -> Self {
+        Self { config: "worldgen.ron".to_string(), params: None }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CameraState {
+    pub position: (f32, f32, f32),
+    /// Radiant, um Y
+    pub yaw: f32,
+    /// Radiant, positiv = nach oben
+    pub pitch: f32,
+}
+
+impl CameraState {
+    pub fn from_transform(tf: &Transform) -> Self {
+        let (yaw, pitch, _) = tf.rotation.to_euler(EulerRot::YXZ);
+        Self { position: tf.translation.into(), yaw, pitch }
+    }
+
+    pub fn to_transform(self) -> Transform {
+        Transform::from_translation(self.position.into())
+            .with_rotation(Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.0))
+    }
+}
+
+fn default_time_of_day() -> f32 {
+    8.0
+}
+
+impl WorldMeta {
+    pub fn new(name: &str, seed: u32) -> Self {
+        Self {
+            format_version: WORLD_FORMAT_VERSION,
+            name: name.to_string(),
+            seed,
+            generator: GeneratorSettings::default(),
+            spawn: None,
+            camera: None,
+            time_of_day: default_time_of_day(),
+        }
+    }
+
+    pub fn load(dir: &WorldDir) -> anyhow::Result<Self> {
+        let path = dir.meta_path();
+        let s = std::fs::read_to_string(&path)
+            .with_context(|| format!("{} lesen", path.display()))?;
+        let meta: WorldMeta = ron::from_str(&s)
+            .with_context(|| format!("{} parsen", path.display()))?;
+
+        if meta.format_version > WORLD_FORMAT_VERSION {
+            bail!(
+                "{}: Format-Version {} ist neuer als unterstützt ({})",
+                path.display(),
+                meta.format_version,
+                WORLD_FORMAT_VERSION
+            );
+        }
+        Ok(meta)
+    }
+
+    /// Vorhandene Welt laden oder eine neue mit `seed` anlegen (und gleich schreiben).
+    pub fn load_or_create(dir: &WorldDir, seed: impl FnOnce() -> u32) -> anyhow::Result<Self> {
+        if dir.meta_path().exists() {
+            return Self::load(dir);
+        }
+        let meta = Self::new(&dir.name, seed());
+        meta.save(dir)?;
+        Ok(meta)
+    }
+
+    /// Schreibt über eine Temp-Datei + rename, damit ein Absturz mitten im
+    /// Schreiben nicht die alte `world.ron` zerstört.
+    pub fn save(&self, dir: &WorldDir) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&dir.path)
+            .with_context(|| format!("{} anlegen", dir.path.display()))?;
+
+        let s = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        let path = dir.meta_path();
+        let tmp = path.with_extension("ron.tmp");
+        std::fs::write(&tmp, s).with_context(|| format!("{} schreiben", tmp.display()))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("{} schreiben", path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(tag: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("bevy_experiments_{tag}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn meta_round_trip() {
+        let root = temp_root("meta");
+        let dir = WorldDir::new(&root, "test");
+
+        let mut meta = WorldMeta::load_or_create(&dir, || 42).unwrap();
+        assert_eq!(meta.seed, 42);
+        assert_eq!(meta.format_version, WORLD_FORMAT_VERSION);
+
+        meta.spawn = Some((0.5, 40.0, 0.5));
+        meta.camera = Some(CameraState { position: (1.0, 2.0, 3.0), yaw: 0.5, pitch: -0.25 });
+        meta.time_of_day = 13.5;
+        meta.generator.params = Some(crate::voxel::fixtures::worldgen_config());
+        meta.save(&dir).unwrap();
+
+        // vorhandene Welt: Seed-Closure darf nicht mehr greifen
+        let loaded = WorldMeta::load_or_create(&dir, || unreachable!()).unwrap();
+        assert_eq!(loaded, meta);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn lists_only_readable_worlds() {
+        let root = temp_root("list");
+        for (name, seed) in [("b", 2), ("a", 1)] {
+            WorldMeta::new(name, seed).save(&WorldDir::new(&root, name)).unwrap();
+        }
+        std::fs::create_dir_all(root.join("keine_welt")).unwrap();
+
+        let worlds = WorldDir::list(&root);
+        let names: Vec<_> = worlds.iter().map(|(d, m)| (d.name.as_str(), m.seed)).collect();
+        assert_eq!(names, vec![("a", 1), ("b", 2)]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn world_names_stay_inside_the_saves_root() {
+        for name in ["default", "welt-2", "Meine Welt"] {
+            assert!(WorldDir::validate_name(name).is_ok(), "{name}");
+        }
+        for name in ["", ".", "..", "../x", "a/b", "a\\b", "/tmp/x", ".benchmark"] {
+            assert!(WorldDir::validate_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn newer_format_is_rejected() {
+        let root = temp_root("meta_version");
+        let dir = WorldDir::new(&root, "test");
+
+        let mut meta = WorldMeta::new("test", 1);
+        meta.format_version = WORLD_FORMAT_VERSION + 1;
+        meta.save(&dir).unwrap();
+
+        assert!(WorldMeta::load(&dir).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn camera_state_keeps_yaw_and_pitch() {
+        let state = CameraState { position: (4.0, 5.0, 6.0), yaw: 1.2, pitch: -0.7 };
+        let back = CameraState::from_transform(&state.to_transform());
+        assert!((back.yaw - state.yaw).abs() < 1e-5);
+        assert!((back.pitch - state.pitch).abs() < 1e-5);
+        assert_eq!(back.position, state.position);
+    }
+}
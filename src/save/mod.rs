@@ -0,0 +1,13 @@
+//! Welten auf der Platte: `saves/<name>/world.ron` mit Seed, Generator, Spawn,
+//! Kamera und Tageszeit, geänderte Chunks unter `saves/<name>/chunks/`.
+//! Die Welt wird im Menü oder per Kommandozeile gewählt oder angelegt. Autosave
+//! läuft im Intervall im Hintergrund, beim Verlassen und Beenden wird alles geschrieben.
+
+mod autosave;
+mod meta;
+mod plugin;
+
+pub use autosave::{AutosaveConfig, SaveWorld};
+pub use meta::{CameraState, GeneratorSettings, WORLD_FORMAT_VERSION, WorldDir, WorldMeta};
+pub use plugin::{SAVES_ROOT, TimeOfDay, WorldArgs, WorldSavePlugin, open_world};
+pub(crate) use plugin::open_world_dir;
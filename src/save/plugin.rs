@@ -0,0 +1,303 @@
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::app_state::{AppState, InWorld};
+use crate::config::{WorldGenConfig, WorldGenConfigRes};
+use crate::console::{ConsoleAppExt, ConsoleArgs, ConsoleResult};
+use crate::voxel::chunk::{ChunkData, ChunkPos};
+use crate::voxel::chunk_store::{ChunkModified, ChunkSaveStore};
+use crate::voxel::worldgen::{WorldGenerator, WorldSeed};
+
+use super::autosave::{
+    AutosaveConfig, AutosaveTimer, PendingChunkWrites, SaveRequested, SaveWorld, autosave_due, poll_chunk_autosave,
+    flush_chunks, save_chunks_on_exit, start_chunk_autosave, tick_autosave_timer,
+};
+use super::meta::{CameraState, WorldDir, WorldMeta};
+
+/// Alle Welten liegen hier drunter, relativ zum Arbeitsverzeichnis.
+pub const SAVES_ROOT: &str = "saves";
+const ASSETS: &str = "assets";
+
+/// Sekunden pro Spieltag (24 Stunden).
+const DAY_LENGTH_SECONDS: f32 = 20.0 * 60.0;
+
+/// Kommandozeile: `--world <name>` öffnet (oder erzeugt) die Welt direkt, ohne Menü,
+/// `--seed <n>` gilt nur beim Anlegen einer neuen Welt. `--bench <script.ron>`
+/// startet stattdessen die Benchmark-Fahrt (siehe `bench`).
+#[derive(Clone, Debug, Default)]
+pub struct WorldArgs {
+    pub world: Option<String>,
+    pub seed: Option<u32>,
+    pub bench: Option<PathBuf>,
+}
+
+impl WorldArgs {
+    pub fn from_env() -> Self {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Self {
+        let mut out = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--world" => match args.next() {
+                    Some(name) => out.world = Some(name),
+                    None => warn!("--world ohne Namen"),
+                },
+                "--seed" => match args.next().map(|s| s.parse::<u32>()) {
+                    Some(Ok(seed)) => out.seed = Some(seed),
+                    _ => warn!("--seed erwartet eine Zahl"),
+                },
+                "--bench" => match args.next() {
+                    Some(script) => out.bench = Some(script.into()),
+                    None => warn!("--bench ohne Skript"),
+                },
+                other => warn!("unbekanntes Argument: {other}"),
+            }
+        }
+        out
+    }
+}
+
+/// Tageszeit in Stunden (0..24), läuft im Spiel weiter und wird mitgespeichert.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TimeOfDay(pub f32);
+
+pub struct WorldSavePlugin {
+    pub args: WorldArgs,
+}
+
+impl Plugin for WorldSavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AutosaveConfig>()
+            .init_resource::<PendingChunkWrites>()
+            .init_resource::<SaveRequested>()
+            .add_message::<SaveWorld>()
+            .add_systems(
+                Update,
+                (place_camera_from_meta, advance_time_of_day).run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    tick_autosave_timer,
+                    (autosave_world_meta, start_chunk_autosave).run_if(autosave_due),
+                    poll_chunk_autosave,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            )
+            // Kamera steht während der Pause still -> hier festhalten, beim
+            // Verlassen der Welt ist sie schon weg
+            .add_systems(OnEnter(AppState::Paused), capture_meta_on_pause)
+            .add_systems(OnExit(InWorld), close_world)
+            // blockiert, bis alles geschrieben ist - danach beendet Bevy die App
+            .add_systems(
+                Last,
+                (save_chunks_on_exit, save_world_meta_on_exit).run_if(resource_exists::<WorldDir>),
+            )
+            .add_console_command("save", "", "Welt jetzt speichern", save_command)
+            .add_console_command("time", "[set <hour>]", "Tageszeit anzeigen/setzen (0-24)", time_command);
+
+        // Synchron beim Aufbau, damit Seed und Generator-Config schon feststehen,
+        // bevor irgendein Loading-System läuft. Klappt das nicht, geht es ohne
+        // offene Welt ins Hauptmenü.
+        if let Some(name) = &self.args.world
+            && let Err(e) = open_world(app.world_mut(), name, self.args.seed)
+        {
+            error!("Welt '{name}' konnte nicht geladen werden, weiter zum Menü: {e:#}");
+        }
+    }
+}
+
+/// Welt `name` unter `saves/` laden (oder mit `seed` anlegen) und ihre
+/// Ressourcen einsetzen, inklusive der Generator-Config aus `world.ron`.
+/// Danach kann es nach `InGame` gehen.
+pub fn open_world(world: &mut World, name: &str, seed: Option<u32>) -> anyhow::Result<()> {
+    WorldDir::validate_name(name)?;
+    open_world_dir(world, WorldDir::new(SAVES_ROOT, name), seed)
+}
+
+/// Wie [`open_world`], aber ohne Namensprüfung; für interne Welten wie `.benchmark`.
+pub(crate) fn open_world_dir(world: &mut World, dir: WorldDir, seed: Option<u32>) -> anyhow::Result<()> {
+    let mut meta = WorldMeta::load_or_create(&dir, || seed.unwrap_or_else(random_seed))?;
+
+    // neue Welt (oder alte world.ron ohne Parameter): Config jetzt festschreiben
+    let params = match &meta.generator.params {
+        Some(params) => params.clone(),
+        None => {
+            let params = WorldGenConfig::from_files(Path::new(ASSETS), &meta.generator.config)?;
+            meta.generator.params = Some(params.clone());
+            meta.save(&dir)?;
+            params
+        }
+    };
+
+    info!("Welt '{}' (Seed {}) aus {}", meta.name, meta.seed, dir.path.display());
+
+    let interval = world.get_resource::<AutosaveConfig>().cloned().unwrap_or_default().interval_seconds;
+    world.insert_resource(WorldSeed(meta.seed));
+    world.insert_resource(WorldGenConfigRes(params));
+    world.insert_resource(TimeOfDay(meta.time_of_day));
+    world.insert_resource(ChunkSaveStore::in_dir(dir.path.join("chunks")));
+    world.insert_resource(AutosaveTimer(Timer::from_seconds(interval, TimerMode::Repeating)));
+    world.insert_resource(meta);
+    world.insert_resource(dir);
+    Ok(())
+}
+
+fn save_command(_: In<ConsoleArgs>, mut ev: MessageWriter<SaveWorld>, dir: Res<WorldDir>) -> ConsoleResult {
+    ev.write(SaveWorld);
+    Ok(format!("speichere nach {}", dir.path.display()))
+}
+
+fn time_command(In(args): In<ConsoleArgs>, mut tod: ResMut<TimeOfDay>) -> ConsoleResult {
+    if args.is_empty() {
+        return Ok(format!("{:.2} Uhr", tod.0));
+    }
+    if args.str(0, "set")? != "set" {
+        return Err("nur 'time' oder 'time set <hour>'".into());
+    }
+    let hour: f32 = args.get(1, "Stunde")?;
+    if !(0.0..=24.0).contains(&hour) {
+        return Err("Stunde muss zwischen 0 und 24 liegen".into());
+    }
+    tod.0 = hour.rem_euclid(24.0);
+    Ok(format!("{:.2} Uhr", tod.0))
+}
+
+/// Welt verlassen (zurück ins Menü): alles schreiben, Welt-Ressourcen weg.
+/// Die Chunk-Entities räumt das Voxel-Plugin ab; dessen Commands laufen erst
+/// nach diesem System, die Daten sind hier also noch da.
+fn close_world(
+    mut commands: Commands,
+    mut pending: ResMut<PendingChunkWrites>,
+    mut store: ResMut<ChunkSaveStore>,
+    modified: Query<(Entity, &ChunkPos, &ChunkData), With<ChunkModified>>,
+    modified_data: Query<&ChunkData, With<ChunkModified>>,
+    meta: Option<Res<WorldMeta>>,
+    dir: Option<Res<WorldDir>>,
+    tod: Option<Res<TimeOfDay>>,
+) {
+    flush_chunks(&mut commands, &mut pending, &mut store, &modified, &modified_data);
+
+    if let (Some(meta), Some(dir)) = (meta, dir) {
+        let mut meta = meta.clone();
+        if let Some(tod) = tod {
+            capture_meta(&mut meta, &tod, None);
+        }
+        write_meta(&meta, &dir);
+        info!("Welt '{}' geschlossen", dir.name);
+    }
+
+    commands.remove_resource::<WorldMeta>();
+    commands.remove_resource::<WorldDir>();
+    commands.insert_resource(ChunkSaveStore::default());
+}
+
+/// Neue Welt ohne `--seed`: irgendwas aus der Uhrzeit.
+fn random_seed() -> u32 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    (nanos ^ (nanos >> 32)) as u32
+}
+
+/// Frisch gespawnte Kamera an die gespeicherte Stelle setzen, bei neuen Welten an den Spawn.
+fn place_camera_from_meta(
+    mut meta: ResMut<WorldMeta>,
+    generator: Res<WorldGenerator>,
+    mut cams: Query<&mut Transform, Added<Camera3d>>,
+) {
+    for mut tf in &mut cams {
+        let spawn = *meta.spawn.get_or_insert_with(|| {
+            // etwas über dem Gelände am Ursprung
+            let height = generator.biomes().column(0, 0).height;
+            (0.5, height as f32 + 20.0, 0.5)
+        });
+
+        *tf = match meta.camera {
+            Some(state) => state.to_transform(),
+            None => CameraState { position: spawn, yaw: 0.0, pitch: -0.5 }.to_transform(),
+        };
+    }
+}
+
+fn advance_time_of_day(time: Res<Time>, mut tod: ResMut<TimeOfDay>) {
+    tod.0 = (tod.0 + time.delta_secs() * 24.0 / DAY_LENGTH_SECONDS).rem_euclid(24.0);
+}
+
+/// Aktuellen Spielzustand in die Metadaten übernehmen.
+fn capture_meta(meta: &mut WorldMeta, tod: &TimeOfDay, cam: Option<&Transform>) {
+    if let Some(tf) = cam {
+        meta.camera = Some(CameraState::from_transform(tf));
+    }
+    meta.time_of_day = tod.0;
+}
+
+fn write_meta(meta: &WorldMeta, dir: &WorldDir) {
+    match meta.save(dir) {
+        Ok(()) => debug!("{} gespeichert", dir.meta_path().display()),
+        Err(e) => error!("Welt speichern fehlgeschlagen: {e:#}"),
+    }
+}
+
+fn autosave_world_meta(
+    mut meta: ResMut<WorldMeta>,
+    dir: Res<WorldDir>,
+    tod: Res<TimeOfDay>,
+    cam: Query<&Transform, With<Camera3d>>,
+) {
+    capture_meta(&mut meta, &tod, cam.single().ok());
+    write_meta(&meta, &dir);
+}
+
+fn capture_meta_on_pause(
+    mut meta: ResMut<WorldMeta>,
+    tod: Res<TimeOfDay>,
+    cam: Query<&Transform, With<Camera3d>>,
+) {
+    capture_meta(&mut meta, &tod, cam.single().ok());
+}
+
+fn save_world_meta_on_exit(
+    mut exit: MessageReader<AppExit>,
+    mut meta: ResMut<WorldMeta>,
+    dir: Res<WorldDir>,
+    tod: Res<TimeOfDay>,
+    cam: Query<&Transform, With<Camera3d>>,
+) {
+    if exit.read().count() == 0 {
+        return;
+    }
+
+    capture_meta(&mut meta, &tod, cam.single().ok());
+    write_meta(&meta, &dir);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> WorldArgs {
+        WorldArgs::parse(list.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parses_world_and_seed() {
+        let a = args(&["--world", "test", "--seed", "99"]);
+        assert_eq!(a.world.as_deref(), Some("test"));
+        assert_eq!(a.seed, Some(99));
+        assert_eq!(a.bench, None);
+
+        let a = args(&["--bench", "benches/flythrough.ron"]);
+        assert_eq!(a.bench.as_deref(), Some(std::path::Path::new("benches/flythrough.ron")));
+
+        let a = args(&[]);
+        assert_eq!(a.world, None);
+        assert_eq!(a.seed, None);
+    }
+}
@@ -0,0 +1,246 @@
+//! Einstellungsmenü: eine Zeile pro Wert mit -/+ Knöpfen. Änderungen gelten
+//! sofort, geschrieben wird beim Schließen.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::menu::{panel, screen_root, spawn_button, title};
+use crate::voxel::MeshingMode;
+
+use super::plugin::{Settings, SettingsPath};
+
+#[derive(Resource, Default, Debug)]
+pub struct SettingsMenu {
+    pub open: bool,
+    /// seit dem letzten Speichern geändert
+    dirty: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingField {
+    ViewRadius,
+    UnloadRadius,
+    LoadBudget,
+    TickSeconds,
+    Meshing,
+    Fov,
+    MouseSensitivity,
+}
+
+impl SettingField {
+    pub const ALL: [SettingField; 7] = [
+        SettingField::ViewRadius,
+        SettingField::UnloadRadius,
+        SettingField::LoadBudget,
+        SettingField::TickSeconds,
+        SettingField::Meshing,
+        SettingField::Fov,
+        SettingField::MouseSensitivity,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingField::ViewRadius => "Sichtweite (Chunks)",
+            SettingField::UnloadRadius => "Entladen ab (Chunks)",
+            SettingField::LoadBudget => "Chunks pro Tick",
+            SettingField::TickSeconds => "Streaming-Takt (s)",
+            SettingField::Meshing => "Mesher",
+            SettingField::Fov => "Sichtfeld (Grad)",
+            SettingField::MouseSensitivity => "Mausempfindlichkeit",
+        }
+    }
+
+    pub fn value(self, s: &Settings) -> String {
+        match self {
+            SettingField::ViewRadius => s.view_radius.to_string(),
+            SettingField::UnloadRadius => s.unload_radius.to_string(),
+            SettingField::LoadBudget => s.load_budget.to_string(),
+            SettingField::TickSeconds => format!("{:.2}", s.tick_seconds),
+            SettingField::Meshing => format!("{:?}", s.meshing),
+            SettingField::Fov => format!("{:.0}", s.fov_degrees),
+            SettingField::MouseSensitivity => format!("{:.4}", s.mouse_sensitivity),
+        }
+    }
+
+    /// Einen Schritt in Richtung `dir` (+1/-1); danach wird geklemmt.
+    pub fn step(self, s: &mut Settings, dir: i32) {
+        let f = dir as f32;
+        match self {
+            SettingField::ViewRadius => s.view_radius += dir,
+            SettingField::UnloadRadius => s.unload_radius += dir,
+            SettingField::LoadBudget => s.load_budget = s.load_budget.saturating_add_signed(4 * dir as isize),
+            SettingField::TickSeconds => s.tick_seconds += 0.05 * f,
+            SettingField::Meshing => {
+                const MODES: [MeshingMode; 3] = [MeshingMode::Naive, MeshingMode::Greedy, MeshingMode::Binary];
+                let i = MODES.iter().position(|&m| m == s.meshing).unwrap_or_default() as i32;
+                s.meshing = MODES[(i + dir).rem_euclid(MODES.len() as i32) as usize];
+            }
+            SettingField::Fov => s.fov_degrees += 5.0 * f,
+            SettingField::MouseSensitivity => s.mouse_sensitivity += 0.0002 * f,
+        }
+        *s = s.clone().clamped();
+    }
+}
+
+#[derive(Component)]
+pub struct SettingsMenuRoot;
+
+#[derive(Component)]
+pub struct SettingValue(pub SettingField);
+
+#[derive(Component, Clone, Copy, Debug)]
+pub enum SettingButton {
+    Step(SettingField, i32),
+    Reset,
+    Close,
+}
+
+pub fn spawn_settings_menu(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .spawn((SettingsMenuRoot, screen_root(), GlobalZIndex(50), Visibility::Hidden))
+        .with_children(|root| {
+            root.spawn(panel()).with_children(|panel| {
+                panel.spawn(title("Einstellungen"));
+
+                for field in SettingField::ALL {
+                    panel
+                        .spawn(Node { column_gap: px(8), align_items: AlignItems::Center, ..default() })
+                        .with_children(|row| {
+                            row.spawn((
+                                Text::new(field.label()),
+                                TextFont { font_size: 14.0, ..default() },
+                                Node { width: px(200), ..default() },
+                            ));
+                            spawn_button(row, SettingButton::Step(field, -1), "-", 28.0);
+                            row.spawn((
+                                SettingValue(field),
+                                Text::new(field.value(&settings)),
+                                TextFont { font_size: 14.0, ..default() },
+                                TextLayout::new_with_justify(Justify::Center),
+                                Node { width: px(80), ..default() },
+                            ));
+                            spawn_button(row, SettingButton::Step(field, 1), "+", 28.0);
+                        });
+                }
+
+                panel
+                    .spawn(Node { column_gap: px(8), margin: UiRect::top(px(8)), ..default() })
+                    .with_children(|row| {
+                        spawn_button(row, SettingButton::Reset, "Standard", 120.0);
+                        spawn_button(row, SettingButton::Close, "Schließen", 120.0);
+                    });
+            });
+        });
+}
+
+/// Öffnen/Schließen, beim Schließen speichern.
+pub fn set_settings_menu_open(menu: &mut SettingsMenu, open: bool, settings: &Settings, path: &SettingsPath) {
+    if menu.open == open {
+        return;
+    }
+    menu.open = open;
+    if !open && menu.dirty {
+        match settings.save(&path.0) {
+            Ok(()) => info!("Einstellungen nach {} gespeichert", path.0.display()),
+            Err(e) => error!("Einstellungen speichern: {e:#}"),
+        }
+        menu.dirty = false;
+    }
+}
+
+/// Für andere Menüs (Hauptmenü, Pause): Einstellungen öffnen/schließen.
+#[derive(SystemParam)]
+pub struct SettingsMenuControl<'w> {
+    menu: ResMut<'w, SettingsMenu>,
+    settings: Res<'w, Settings>,
+    path: Res<'w, SettingsPath>,
+}
+
+impl SettingsMenuControl<'_> {
+    pub fn is_open(&self) -> bool {
+        self.menu.open
+    }
+
+    pub fn set_open(&mut self, open: bool) {
+        set_settings_menu_open(&mut self.menu, open, &self.settings, &self.path);
+    }
+}
+
+pub fn settings_menu_buttons(
+    buttons: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<Settings>,
+    path: Res<SettingsPath>,
+) {
+    for (interaction, &action) in &buttons {
+        if *interaction != Interaction::Pressed || !menu.open {
+            continue;
+        }
+
+        match action {
+            SettingButton::Step(field, dir) => {
+                field.step(&mut settings, dir);
+                menu.dirty = true;
+            }
+            SettingButton::Reset => {
+                *settings = Settings::default();
+                menu.dirty = true;
+            }
+            SettingButton::Close => set_settings_menu_open(&mut menu, false, &settings, &path),
+        }
+    }
+}
+
+pub fn update_settings_menu(
+    menu: Res<SettingsMenu>,
+    settings: Res<Settings>,
+    mut root: Query<&mut Visibility, With<SettingsMenuRoot>>,
+    mut values: Query<(&SettingValue, &mut Text)>,
+) {
+    if menu.is_changed() {
+        for mut vis in &mut root {
+            *vis = if menu.open { Visibility::Inherited } else { Visibility::Hidden };
+        }
+    }
+    if settings.is_changed() {
+        for (SettingValue(field), mut text) in &mut values {
+            text.0 = field.value(&settings);
+        }
+    }
+}
+
+/// Klicks ins Menü sollen keine Blöcke abbauen: nach der UI-Auswertung
+/// (PreUpdate) die Maustasten für den Rest des Frames leeren.
+pub fn swallow_clicks_while_open(menu: Res<SettingsMenu>, mut mouse: ResMut<ButtonInput<MouseButton>>) {
+    if menu.open {
+        mouse.reset_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_stay_in_range() {
+        let mut s = Settings { view_radius: 4, unload_radius: 5, ..default() };
+
+        // unload kann nicht unter view + 1
+        SettingField::UnloadRadius.step(&mut s, -1);
+        assert_eq!(s.unload_radius, 5);
+        // view zieht unload mit
+        SettingField::ViewRadius.step(&mut s, 1);
+        assert_eq!((s.view_radius, s.unload_radius), (5, 6));
+
+        s.meshing = MeshingMode::Binary;
+        SettingField::Meshing.step(&mut s, 1);
+        assert_eq!(s.meshing, MeshingMode::Naive);
+        SettingField::Meshing.step(&mut s, -1);
+        assert_eq!(s.meshing, MeshingMode::Binary);
+
+        for _ in 0..100 {
+            SettingField::Fov.step(&mut s, 1);
+        }
+        assert_eq!(s.fov_degrees, 120.0);
+    }
+}
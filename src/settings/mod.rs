@@ -0,0 +1,9 @@
+//! Spieler-Einstellungen in `settings.ron` (Arbeitsverzeichnis): Sichtweite,
+//! Streaming-Budget und -Takt, Mesher, FOV, Maus. Werden beim Start geladen,
+//! im Einstellungsmenü geändert, sofort angewendet und beim Schließen gespeichert.
+
+mod menu;
+mod plugin;
+
+pub use menu::{SettingField, SettingsMenu, SettingsMenuControl};
+pub use plugin::{MAX_VIEW_RADIUS, Settings, SettingsPath, SettingsPlugin};
@@ -0,0 +1,169 @@
+use anyhow::Context;
+use bevy::prelude::*;
+use bevy::ui::UiSystems;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::camera::FlyCam;
+use crate::input::ActionSystems;
+use crate::voxel::MeshingMode;
+use crate::voxel::chunk_stream::ChunkStreamConfig;
+
+use super::menu::{
+    SettingsMenu, settings_menu_buttons, spawn_settings_menu, swallow_clicks_while_open,
+    update_settings_menu,
+};
+
+const SETTINGS_FILE: &str = "settings.ron";
+/// größte Sichtweite in Chunks (Menü, Konsole, Datei)
+pub const MAX_VIEW_RADIUS: i32 = 48;
+
+/// Inhalt von `settings.ron`; fehlende Felder bekommen den Standardwert.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// in Chunks
+    pub view_radius: i32,
+    /// ab hier wird entladen, immer > `view_radius`
+    pub unload_radius: i32,
+    /// Chunk-Ladeaufträge pro Streaming-Tick
+    pub load_budget: usize,
+    pub tick_seconds: f32,
+    pub meshing: MeshingMode,
+    /// vertikal, in Grad
+    pub fov_degrees: f32,
+    pub mouse_sensitivity: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let stream = ChunkStreamConfig::default();
+        Self {
+            view_radius: stream.view_radius,
+            unload_radius: stream.unload_radius,
+            load_budget: stream.load_budget,
+            tick_seconds: stream.tick_seconds,
+            meshing: MeshingMode::default(),
+            fov_degrees: 45.0,
+            mouse_sensitivity: 0.002,
+        }
+    }
+}
+
+impl Settings {
+    /// Fehlt die Datei, gelten die Standardwerte.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let s = std::fs::read_to_string(path).with_context(|| format!("{} lesen", path.display()))?;
+        let settings: Settings = ron::from_str(&s).with_context(|| format!("{} parsen", path.display()))?;
+        Ok(settings.clamped())
+    }
+
+    /// Über Temp-Datei + rename, wie `world.ron`.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let s = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        let tmp = path.with_extension("ron.tmp");
+        std::fs::write(&tmp, s).with_context(|| format!("{} schreiben", tmp.display()))?;
+        std::fs::rename(&tmp, path).with_context(|| format!("{} schreiben", path.display()))?;
+        Ok(())
+    }
+
+    /// Werte in sinnvolle Grenzen bringen (Hand-editierte Datei, Menü-Schritte).
+    pub fn clamped(mut self) -> Self {
+        self.view_radius = self.view_radius.clamp(1, MAX_VIEW_RADIUS);
+        self.unload_radius = self.unload_radius.clamp(self.view_radius + 1, self.view_radius + 16);
+        self.load_budget = self.load_budget.clamp(1, 256);
+        self.tick_seconds = self.tick_seconds.clamp(0.02, 2.0);
+        self.fov_degrees = self.fov_degrees.clamp(30.0, 120.0);
+        self.mouse_sensitivity = self.mouse_sensitivity.clamp(0.0002, 0.02);
+        self
+    }
+}
+
+/// Wohin gespeichert wird.
+#[derive(Resource, Clone, Debug)]
+pub struct SettingsPath(pub PathBuf);
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let path = PathBuf::from(SETTINGS_FILE);
+        let settings = Settings::load(&path).unwrap_or_else(|e| {
+            error!("Einstellungen: {e:#}, nehme Standardwerte");
+            Settings::default()
+        });
+
+        app.insert_resource(settings)
+            .insert_resource(SettingsPath(path))
+            .init_resource::<SettingsMenu>()
+            .add_systems(Startup, spawn_settings_menu)
+            .add_systems(PreUpdate, swallow_clicks_while_open.after(UiSystems::Focus).before(ActionSystems))
+            .add_systems(Update, (settings_menu_buttons, update_settings_menu).chain())
+            .add_systems(Update, (apply_stream_settings, apply_camera_settings));
+    }
+}
+
+fn apply_stream_settings(
+    settings: Res<Settings>,
+    mut stream: ResMut<ChunkStreamConfig>,
+    mut mode: ResMut<MeshingMode>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    stream.view_radius = settings.view_radius;
+    stream.unload_radius = settings.unload_radius;
+    stream.load_budget = settings.load_budget;
+    stream.tick_seconds = settings.tick_seconds;
+    // nur bei echter Änderung, sonst wird alles neu gemesht
+    mode.set_if_neq(settings.meshing);
+}
+
+/// Auch für frisch gespawnte Kameras (Kamera entsteht erst beim Betreten der Welt).
+fn apply_camera_settings(
+    settings: Res<Settings>,
+    mut cams: Query<(Ref<Camera3d>, &mut Projection, Option<&mut FlyCam>)>,
+) {
+    for (cam, mut projection, fly) in &mut cams {
+        if !settings.is_changed() && !cam.is_added() {
+            continue;
+        }
+        if let Projection::Perspective(p) = projection.as_mut() {
+            p.fov = settings.fov_degrees.to_radians();
+        }
+        if let Some(mut fly) = fly {
+            fly.sensitivity = settings.mouse_sensitivity;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_and_partial_files() {
+        let dir = std::env::temp_dir().join(format!("bevy_experiments_settings_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.ron");
+
+        // fehlt -> Standard
+        assert_eq!(Settings::load(&path).unwrap(), Settings::default());
+
+        let s = Settings { view_radius: 8, unload_radius: 10, meshing: MeshingMode::Greedy, ..default() };
+        s.save(&path).unwrap();
+        assert_eq!(Settings::load(&path).unwrap(), s);
+
+        // nur ein Feld, und das zu groß; unload wird mitgezogen
+        std::fs::write(&path, "(view_radius: 500)").unwrap();
+        let loaded = Settings::load(&path).unwrap();
+        assert_eq!(loaded.view_radius, 48);
+        assert_eq!(loaded.unload_radius, 49);
+        assert_eq!(loaded.fov_degrees, Settings::default().fov_degrees);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+
+use crate::{
+    config::BlocksConfigRes,
+    voxel::{
+        chunk::{face_id, Block},
+        greedy_meshing::{axis_neg_dir, axis_pos_dir, axis_uvd_to_xyz, emit_greedy_quad, mask_axes, MESH_AXES},
+        mesh_buffers::ChunkMeshBuffers,
+        meshing::{effective_block_kind, FaceDir},
+        neighborhood::ChunkNeighborhood,
+    },
+};
+
+/// Solid-Bits der Nachbarschaft als Spalten, pro Achse eine je (U,V)-Zelle:
+/// Bit k = solide bei gepolsterter Koordinate k (also Zelle k-1) entlang der Achse.
+struct ColumnMasks {
+    /// gepolsterte Größe = dim + 2
+    pdim: IVec3,
+    cols: [Vec<u32>; 3],
+}
+
+impl ColumnMasks {
+    fn new(nb: &ChunkNeighborhood) -> Self {
+        let dim = nb.dim();
+        let pdim = dim + IVec3::splat(2);
+        debug_assert!(pdim.max_element() <= 32, "Spalten sind u32");
+
+        let p = [pdim.x, pdim.y, pdim.z];
+        let mut cols: [Vec<u32>; 3] = std::array::from_fn(|axis| {
+            let (u_axis, v_axis) = mask_axes(axis);
+            vec![0; (p[u_axis] * p[v_axis]) as usize]
+        });
+
+        for z in 0..pdim.z {
+            for y in 0..pdim.y {
+                for x in 0..pdim.x {
+                    if nb.get((x - 1, y - 1, z - 1)) == Block::Air {
+                        continue;
+                    }
+                    let xyz = [x, y, z];
+                    for (axis, col) in cols.iter_mut().enumerate() {
+                        let (u_axis, v_axis) = mask_axes(axis);
+                        col[(xyz[u_axis] + p[u_axis] * xyz[v_axis]) as usize] |= 1 << xyz[axis];
+                    }
+                }
+            }
+        }
+        Self { pdim, cols }
+    }
+
+    /// Oben drüber Luft? Über die Y-Spalte, damit auch der Rand stimmt.
+    #[inline]
+    fn above_is_air(&self, (x, y, z): (i32, i32, i32)) -> bool {
+        let ci = (x + 1 + self.pdim.x * (z + 1)) as usize;
+        self.cols[1][ci] & (1 << (y + 2)) == 0
+    }
+}
+
+/// Eine sichtbare Fläche in der Maske einer Grenzfläche.
+struct FaceCell {
+    u: i32,
+    v: i32,
+    id: u32,
+    block: Block,
+    dir: FaceDir,
+}
+
+/// Alle Zellen einer Grenzfläche mit derselben Face-ID als Bitzeilen (Bit = U).
+struct IdRows {
+    id: u32,
+    block: Block,
+    dir: FaceDir,
+    rows: Vec<u32>,
+}
+
+/// Binary Greedy Meshing.
+/// Gleiches Ergebnis wie `greedy_meshing::build_chunk_mesh_greedy_all_axes` (Buffer bitgenau gleich),
+/// aber Face-Culling und Rechteck-Suche laufen über Bitmasken statt Zelle für Zelle.
+pub fn binary_mesh(cfg: &BlocksConfigRes, nb: &ChunkNeighborhood) -> ChunkMeshBuffers {
+    let masks = ColumnMasks::new(nb);
+    let mut out = ChunkMeshBuffers::default();
+    let scale = nb.lod().scale() as f32;
+
+    for axis in MESH_AXES {
+        binary_axis(nb, &masks, axis, scale, cfg, &mut out);
+    }
+
+    out
+}
+
+fn binary_axis(
+    nb: &ChunkNeighborhood,
+    masks: &ColumnMasks,
+    axis: usize,
+    scale: f32,
+    cfg: &BlocksConfigRes,
+    out: &mut ChunkMeshBuffers,
+) {
+    let dim = nb.dim();
+    let size = [dim.x, dim.y, dim.z];
+    let psize = [masks.pdim.x, masks.pdim.y, masks.pdim.z];
+    let (u_axis, v_axis) = mask_axes(axis);
+
+    let su = size[u_axis];
+    let sv = size[v_axis];
+    let sd = size[axis];
+    let inner = (1u32 << sd) - 1;
+
+    // 1) Face-Culling pro Spalte, Ergebnis nach Grenzfläche einsortiert.
+    // Wie beim alten Mesher wird nur innerhalb des Chunks gecullt:
+    // außerhalb gilt für die Faces immer Luft, der Rand hat also immer Wände.
+    let mut slices: Vec<Vec<FaceCell>> = (0..=sd).map(|_| Vec::new()).collect();
+
+    for vv in 0..sv {
+        for uu in 0..su {
+            let ci = (uu + 1 + psize[u_axis] * (vv + 1)) as usize;
+            let col = (masks.cols[axis][ci] >> 1) & inner;
+            if col == 0 {
+                continue;
+            }
+
+            // solide, Nachbar in +axis Luft -> Face an Grenzfläche k+1
+            let mut pos = col & !(col >> 1);
+            // solide, Nachbar in -axis Luft -> Face an Grenzfläche k
+            let mut neg = col & !(col << 1);
+
+            while pos != 0 {
+                let k = pos.trailing_zeros() as i32;
+                pos &= pos - 1;
+                let p = axis_uvd_to_xyz(axis, u_axis, v_axis, uu, vv, k);
+                slices[(k + 1) as usize].push(face_cell(nb, masks, cfg, p, uu, vv, axis_pos_dir(axis)));
+            }
+            while neg != 0 {
+                let k = neg.trailing_zeros() as i32;
+                neg &= neg - 1;
+                let p = axis_uvd_to_xyz(axis, u_axis, v_axis, uu, vv, k);
+                slices[k as usize].push(face_cell(nb, masks, cfg, p, uu, vv, axis_neg_dir(axis)));
+            }
+        }
+    }
+
+    // 2) Pro Grenzfläche und Face-ID greedy über Bitzeilen
+    let mut groups: Vec<IdRows> = Vec::new();
+    let mut quads: Vec<(i32, i32, i32, i32, Block, FaceDir)> = Vec::new();
+
+    for (slice, cells) in slices.iter().enumerate() {
+        if cells.is_empty() {
+            continue;
+        }
+
+        groups.clear();
+        for c in cells {
+            let g = match groups.iter().position(|g| g.id == c.id) {
+                Some(i) => &mut groups[i],
+                None => {
+                    groups.push(IdRows { id: c.id, block: c.block, dir: c.dir, rows: vec![0; sv as usize] });
+                    groups.last_mut().unwrap()
+                }
+            };
+            g.rows[c.v as usize] |= 1 << c.u;
+        }
+
+        quads.clear();
+        for g in groups.iter_mut() {
+            for v in 0..sv as usize {
+                while g.rows[v] != 0 {
+                    let u = g.rows[v].trailing_zeros();
+                    let w = (g.rows[v] >> u).trailing_ones();
+                    let span = ((1u64 << w) - 1) as u32;
+                    let run = span << u;
+
+                    // Höhe: solange die nächste Zeile den ganzen Lauf enthält
+                    let mut h = 1;
+                    while v + h < sv as usize && g.rows[v + h] & run == run {
+                        g.rows[v + h] &= !run;
+                        h += 1;
+                    }
+                    g.rows[v] &= !run;
+
+                    quads.push((v as i32, u as i32, w as i32, h as i32, g.block, g.dir));
+                }
+            }
+        }
+
+        // alter Mesher emittiert in Rasterreihenfolge (v, dann u) über alle IDs
+        quads.sort_unstable_by_key(|&(v, u, ..)| (v, u));
+
+        for &(v, u, w, h, block, dir) in &quads {
+            emit_greedy_quad(cfg, block, dir, axis, u_axis, v_axis, u, v, slice as i32, w, h, scale, out);
+        }
+    }
+}
+
+fn face_cell(
+    nb: &ChunkNeighborhood,
+    masks: &ColumnMasks,
+    cfg: &BlocksConfigRes,
+    p: (i32, i32, i32),
+    u: i32,
+    v: i32,
+    dir: FaceDir,
+) -> FaceCell {
+    let eff = effective_block_kind(nb.get(p), masks.above_is_air(p));
+    FaceCell { u, v, id: face_id(cfg, eff, dir), block: eff, dir }
+}
+
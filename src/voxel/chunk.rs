@@ -1,15 +1,72 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{config::BlocksConfigRes, voxel::meshing::{FaceDir, face_kind, tile_for}};
 
 pub const CHUNK_SIZE: IVec3 = IVec3::new(16, 16, 16);
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Block {
     Air,
     Grass,
     Dirt,
-    Stone
+    Stone,
+    Sand,
+    Snow,
+    CoalOre,
+    IronOre,
+    GoldOre,
+    DiamondOre,
+    Log,
+    Leaves,
+    Planks,
+}
+
+impl Block {
+    /// Alle Blöcke in Reihenfolge ihrer ID.
+    /// Die ID landet in den Chunk-Dateien -> neue Blöcke nur hinten anhängen.
+    pub const ALL: [Block; 13] = [
+        Block::Air,
+        Block::Grass,
+        Block::Dirt,
+        Block::Stone,
+        Block::Sand,
+        Block::Snow,
+        Block::CoalOre,
+        Block::IronOre,
+        Block::GoldOre,
+        Block::DiamondOre,
+        Block::Log,
+        Block::Leaves,
+        Block::Planks,
+    ];
+
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_id(id: u8) -> Option<Block> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    /// Name in `blocks.ron`
+    pub fn key(self) -> &'static str {
+        match self {
+            Block::Air => "air",
+            Block::Grass => "grass",
+            Block::Dirt => "dirt",
+            Block::Stone => "stone",
+            Block::Sand => "sand",
+            Block::Snow => "snow",
+            Block::CoalOre => "coal_ore",
+            Block::IronOre => "iron_ore",
+            Block::GoldOre => "gold_ore",
+            Block::DiamondOre => "diamond_ore",
+            Block::Log => "log",
+            Block::Leaves => "leaves",
+            Block::Planks => "planks",
+        }
+    }
 }
 
 #[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -64,7 +121,7 @@ pub fn face_id(cfg: &BlocksConfigRes, block: Block, dir: FaceDir) -> u32 {
         FaceDir::NegZ => 6,
     };
 
-    (t as u32) ^ ((d as u32) << 24)
+    t ^ ((d as u32) << 24)
 }
 
 pub fn chunk_origin_world(pos: ChunkPos) -> Vec3 {
@@ -75,6 +132,11 @@ pub fn chunk_origin_world(pos: ChunkPos) -> Vec3 {
     )
 }
 
+/// Welt-Blockkoordinate -> (Chunk, lokale Koordinate im Chunk)
+pub fn block_to_chunk(pos: IVec3) -> (ChunkPos, IVec3) {
+    (ChunkPos(pos.div_euclid(CHUNK_SIZE)), pos.rem_euclid(CHUNK_SIZE))
+}
+
 pub fn world_to_chunk_pos(world: Vec3) -> ChunkPos {
     // Block-Koordinaten (floor für negatives)
     let bx = world.x.floor() as i32;
@@ -1,26 +1,69 @@
+use anyhow::{Context, bail};
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use crate::voxel::plugin::VoxelWorld;
 
-use super::chunk::{ChunkPos, ChunkData, Block};
+use super::chunk::{ChunkPos, ChunkData, Block, CHUNK_SIZE};
 
+/// Chunk weicht vom gespeicherten Stand ab; Autosave schreibt ihn und entfernt die Markierung.
 #[derive(Component)]
 pub struct ChunkModified;
 
+/// Geänderte Chunks, die noch nicht (oder nur im Speicher) gesichert sind.
+/// Mit Verzeichnis fliegt ein Eintrag nach dem Schreiben wieder raus, geladen
+/// wird dann aus der Datei; ohne Verzeichnis ist `saved` der einzige Speicher.
 #[derive(Resource, Default)]
 pub struct ChunkSaveStore {
     // kompletter Chunk-Blockbuffer
     pub saved: HashMap<ChunkPos, Vec<Block>>,
+    /// liegen in `saved`, sind aber noch nicht auf der Platte
+    pub unflushed: HashSet<ChunkPos>,
+    /// Verzeichnis für die Chunk-Dateien; `None` = nur im Speicher
+    dir: Option<PathBuf>,
 }
 
 impl ChunkSaveStore {
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: Some(dir.into()), ..default() }
+    }
+
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
     pub fn load_chunk(&self, pos: ChunkPos) -> Option<ChunkData> {
-        self.saved.get(&pos).map(|blocks| ChunkData { blocks: blocks.clone() })
+        if let Some(blocks) = self.saved.get(&pos) {
+            return Some(ChunkData { blocks: blocks.clone() });
+        }
+
+        let dir = self.dir.as_ref()?;
+        match read_chunk_file(dir, pos) {
+            Ok(blocks) => blocks.map(|blocks| ChunkData { blocks }),
+            Err(e) => {
+                // kaputte Datei: lieber neu generieren als gar nichts anzeigen
+                error!("Chunk {:?} laden fehlgeschlagen: {e:#}", pos.0);
+                None
+            }
+        }
     }
 
+    /// Merkt sich den Stand; auf die Platte kommt er beim nächsten Flush.
     pub fn save_chunk(&mut self, pos: ChunkPos, data: &ChunkData) {
         self.saved.insert(pos, data.blocks.clone());
+        if self.dir.is_some() {
+            self.unflushed.insert(pos);
+        }
+    }
+
+    /// `blocks` liegt jetzt so auf der Platte. Ist das noch der gemerkte Stand,
+    /// wird der Eintrag nicht mehr gebraucht.
+    pub fn mark_flushed(&mut self, pos: ChunkPos, blocks: &[Block]) {
+        if self.saved.get(&pos).is_some_and(|b| b == blocks) {
+            self.saved.remove(&pos);
+            self.unflushed.remove(&pos);
+        }
     }
 }
 
@@ -40,12 +83,123 @@ pub fn handle_chunk_unload_requests_system(
         world.chunks.remove(&pos);
 
         // nur speichern wenn modified
-        if q_modified.get(ent).is_ok() {
-            if let Ok(data) = q_data.get(ent) {
-                store.save_chunk(pos, data);
-            }
+        if q_modified.get(ent).is_ok()
+            && let Ok(data) = q_data.get(ent)
+        {
+            store.save_chunk(pos, data);
         }
 
         commands.entity(ent).despawn();
     }
-}
\ No newline at end of file
+}
+
+/// Dateiformat: Magic, dann Runs aus (Anzahl u16 LE, Block-ID u8).
+const CHUNK_MAGIC: &[u8; 4] = b"VXC1";
+
+pub fn chunk_file_path(dir: &Path, pos: ChunkPos) -> PathBuf {
+    dir.join(format!("{}_{}_{}.chunk", pos.0.x, pos.0.y, pos.0.z))
+}
+
+pub fn encode_chunk(blocks: &[Block]) -> Vec<u8> {
+    let mut out = CHUNK_MAGIC.to_vec();
+    let mut i = 0;
+    while i < blocks.len() {
+        let b = blocks[i];
+        let mut run = 1;
+        while i + run < blocks.len() && blocks[i + run] == b && run < u16::MAX as usize {
+            run += 1;
+        }
+        out.extend_from_slice(&(run as u16).to_le_bytes());
+        out.push(b.id());
+        i += run;
+    }
+    out
+}
+
+pub fn decode_chunk(bytes: &[u8]) -> anyhow::Result<Vec<Block>> {
+    let Some(runs) = bytes.strip_prefix(CHUNK_MAGIC) else {
+        bail!("keine Chunk-Datei (Magic fehlt)");
+    };
+    if runs.len() % 3 != 0 {
+        bail!("abgeschnittene Chunk-Datei");
+    }
+
+    let volume = (CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z) as usize;
+    let mut blocks = Vec::with_capacity(volume);
+    for run in runs.chunks_exact(3) {
+        let count = u16::from_le_bytes([run[0], run[1]]) as usize;
+        let block = Block::from_id(run[2]).with_context(|| format!("unbekannte Block-ID {}", run[2]))?;
+        blocks.extend(std::iter::repeat_n(block, count));
+    }
+
+    if blocks.len() != volume {
+        bail!("Chunk hat {} statt {} Blöcke", blocks.len(), volume);
+    }
+    Ok(blocks)
+}
+
+/// `Ok(None)`: für diesen Chunk gibt es (noch) keine Datei.
+pub fn read_chunk_file(dir: &Path, pos: ChunkPos) -> anyhow::Result<Option<Vec<Block>>> {
+    let path = chunk_file_path(dir, pos);
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("{} lesen", path.display())),
+    };
+    decode_chunk(&bytes)
+        .with_context(|| path.display().to_string())
+        .map(Some)
+}
+
+/// Temp-Datei + rename, damit ein Abbruch keinen halben Chunk hinterlässt.
+pub fn write_chunk_file(dir: &Path, pos: ChunkPos, blocks: &[Block]) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("{} anlegen", dir.display()))?;
+    let path = chunk_file_path(dir, pos);
+    let tmp = path.with_extension("chunk.tmp");
+    std::fs::write(&tmp, encode_chunk(blocks)).with_context(|| format!("{} schreiben", tmp.display()))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("{} schreiben", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_file_round_trip() {
+        let blocks = crate::voxel::fixtures::random(3).blocks;
+        let decoded = decode_chunk(&encode_chunk(&blocks)).unwrap();
+        assert_eq!(decoded, blocks);
+
+        // abgeschnitten oder fremd -> Fehler statt Müll
+        let bytes = encode_chunk(&blocks);
+        assert!(decode_chunk(&bytes[..bytes.len() - 3]).is_err());
+        assert!(decode_chunk(b"nope").is_err());
+    }
+
+    #[test]
+    fn flushed_chunks_leave_the_cache() {
+        let dir = std::env::temp_dir().join(format!("bevy_experiments_store_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut store = ChunkSaveStore::in_dir(&dir);
+        let (a, b) = (ChunkPos(IVec3::ZERO), ChunkPos(IVec3::X));
+        let data = crate::voxel::fixtures::random(5);
+
+        store.save_chunk(a, &data);
+        store.save_chunk(b, &data);
+        write_chunk_file(&dir, a, &data.blocks).unwrap();
+        store.mark_flushed(a, &data.blocks);
+        // b wurde inzwischen nochmal geändert -> der geschriebene Stand ist veraltet
+        let newer = crate::voxel::fixtures::random(6);
+        store.save_chunk(b, &newer);
+        store.mark_flushed(b, &data.blocks);
+
+        assert!(!store.saved.contains_key(&a) && !store.unflushed.contains(&a));
+        assert!(store.saved.contains_key(&b) && store.unflushed.contains(&b));
+        // a kommt jetzt von der Platte
+        assert_eq!(store.load_chunk(a).unwrap().blocks, data.blocks);
+        assert_eq!(store.load_chunk(b).unwrap().blocks, newer.blocks);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
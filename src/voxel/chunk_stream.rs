@@ -1,7 +1,7 @@
 use bevy::prelude::*;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashSet, VecDeque};
 
-use crate::voxel::{chunk::{Block, CHUNK_SIZE, ChunkData, chunk_origin_world}, chunk_store::ChunkSaveStore, plugin::VoxelWorld};
+use crate::voxel::{chunk::chunk_origin_world, chunk_store::{ChunkSaveStore, RequestChunkUnload}, lod::{ChunkLod, lod_for_distance}, plugin::{MeshingMode, VoxelWorld}, worldgen::WorldGenerator};
 
 use super::chunk::{ChunkDirty, world_to_chunk_pos, ChunkPos};
 
@@ -13,6 +13,21 @@ pub struct ChunkStreamConfig {
     pub y_min: i32,           // welche Chunk-Ebenen laden (z.B. 0..0)
     pub y_max: i32,
     pub load_budget: usize,   // wie viele Chunks pro Tick
+    pub lod_rings: [i32; 3],  // ab welchem Abstand (in Chunks) LOD 1/2/3 gilt
+}
+
+impl Default for ChunkStreamConfig {
+    fn default() -> Self {
+        Self {
+            view_radius: 24,
+            unload_radius: 26,
+            tick_seconds: 0.2,
+            y_min: -4,
+            y_max: 3,
+            load_budget: 16,
+            lod_rings: [4, 8, 12],
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -24,6 +39,12 @@ pub struct ChunkLoadQueue {
 #[derive(Resource)]
 pub struct StreamTimer(pub Timer);
 
+impl StreamTimer {
+    pub fn from_config(cfg: &ChunkStreamConfig) -> Self {
+        Self(Timer::from_seconds(cfg.tick_seconds.max(0.01), TimerMode::Repeating))
+    }
+}
+
 #[derive(Message, Clone, Copy)]
 pub struct RequestChunkLoad(pub ChunkPos);
 
@@ -55,8 +76,16 @@ pub fn chunk_stream_tick_system(
     mut world: ResMut<VoxelWorld>,
     mut queue: ResMut<ChunkLoadQueue>,
     mut ev_load: MessageWriter<RequestChunkLoad>,
+    mut ev_unload: MessageWriter<RequestChunkUnload>,
+    lods: Query<&ChunkLod>,
+    mode: Res<MeshingMode>,
     mut commands: Commands,
 ) {
+    // tick_seconds kann sich zur Laufzeit ändern (Einstellungen)
+    let interval = std::time::Duration::from_secs_f32(cfg.tick_seconds.max(0.01));
+    if timer.0.duration() != interval {
+        timer.0.set_duration(interval);
+    }
     timer.0.tick(time.delta());
     if !timer.0.just_finished() {
         return;
@@ -65,13 +94,17 @@ pub fn chunk_stream_tick_system(
     let Ok(cam_tf) = cam_q.single() else { return; };
     let center = world_to_chunk_pos(cam_tf.translation());
 
-    // 1) missing -> queue
+    // 1) missing -> queue (nahe Chunks zuerst, sonst dauert es bei großem Radius ewig)
     let wanted = wanted_chunks(center, cfg.view_radius, cfg.y_min, cfg.y_max);
-    for pos in wanted.iter().copied() {
-        if world.chunks.contains_key(&pos) { continue; }
-        if queue.queued.insert(pos) {
-            queue.fifo.push_back(pos);
-        }
+    let mut missing: Vec<ChunkPos> = wanted
+        .iter()
+        .copied()
+        .filter(|pos| !world.chunks.contains_key(pos) && !queue.queued.contains(pos))
+        .collect();
+    missing.sort_by_key(|&pos| chebyshev_dist(pos, center).max_element());
+    for pos in missing {
+        queue.queued.insert(pos);
+        queue.fifo.push_back(pos);
     }
 
     // 2) unload far (mit Hysterese)
@@ -84,14 +117,27 @@ pub fn chunk_stream_tick_system(
     }
 
     for (pos, ent) in to_unload {
+        // Despawn + Sichern von ChunkModified macht der Unload-Handler
         world.chunks.remove(&pos);
-        commands.entity(ent).despawn_children();
-        commands.entity(ent).despawn();
+        ev_unload.write(RequestChunkUnload(pos, ent));
 
         // wichtig: Nachbarn remeshen, weil Seiten wieder sichtbar werden können
         mark_neighbors_dirty(&mut commands, &world, pos);
     }
 
+    // 2b) LOD-Stufen nachziehen, wenn sich die Kamera bewegt hat
+    for (&pos, &ent) in world.chunks.iter() {
+        let Ok(&current) = lods.get(ent) else { continue; };
+        let wanted_lod = lod_for_distance(&cfg, chebyshev_dist(pos, center).max_element());
+        if wanted_lod != current {
+            commands.entity(ent).insert((wanted_lod, ChunkDirty));
+            // nur der naive Mesher cullt gegen Nachbarn, die anderen bleiben wie sie sind
+            if mode.culls_neighbors() {
+                mark_neighbors_dirty(&mut commands, &world, pos);
+            }
+        }
+    }
+
     // 3) budgeted load requests
     for _ in 0..cfg.load_budget {
         let Some(pos) = queue.fifo.pop_front() else { break; };
@@ -100,32 +146,17 @@ pub fn chunk_stream_tick_system(
     }
 }
 
-fn generate_chunk_data(pos: ChunkPos) -> ChunkData {
-    // Platzhalter: mach hier später Noise / Terrain rein
-    let mut blocks = vec![Block::Air; (CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z) as usize];
-
-    // Beispiel: simple Ebene bei world_y == 0
-    // Chunk y==0 komplett Dirt/Grass
-    if pos.0.y == 0 {
-        for z in 0..CHUNK_SIZE.z {
-            for y in 0..CHUNK_SIZE.y {
-                for x in 0..CHUNK_SIZE.x {
-                    let idx = ChunkData::idx(x, y, z);
-                    blocks[idx] = if y == CHUNK_SIZE.y - 1 { Block::Grass } else { Block::Dirt };
-                }
-            }
-        }
-    }
-
-    ChunkData { blocks }
-}
-
 pub fn handle_chunk_load_requests_system(
     mut commands: Commands,
     mut ev: MessageReader<RequestChunkLoad>,
     mut world: ResMut<VoxelWorld>,
     store: ResMut<ChunkSaveStore>,
+    cfg: Res<ChunkStreamConfig>,
+    generator: Res<WorldGenerator>,
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
 ) {
+    let center = cam_q.single().ok().map(|tf| world_to_chunk_pos(tf.translation()));
+
     for RequestChunkLoad(pos) in ev.read().copied() {
         if world.chunks.contains_key(&pos) {
             continue;
@@ -133,13 +164,17 @@ pub fn handle_chunk_load_requests_system(
 
         let data = store
             .load_chunk(pos)
-            .unwrap_or_else(|| generate_chunk_data(pos));
+            .unwrap_or_else(|| generator.generate_chunk(pos));
 
         let origin = chunk_origin_world(pos);
+        let lod = center
+            .map(|c| lod_for_distance(&cfg, chebyshev_dist(pos, c).max_element()))
+            .unwrap_or_default();
 
         let ent = commands.spawn((
             pos,
             data,
+            lod,
             ChunkDirty,
             Transform::from_translation(origin),
             GlobalTransform::default(),
@@ -166,7 +201,7 @@ fn neighbors_6(pos: ChunkPos) -> [ChunkPos; 6] {
     ]
 }
 
-fn mark_neighbors_dirty(commands: &mut Commands, world: &VoxelWorld, pos: ChunkPos) {
+pub(crate) fn mark_neighbors_dirty(commands: &mut Commands, world: &VoxelWorld, pos: ChunkPos) {
     for n in neighbors_6(pos) {
         if let Some(&e) = world.chunks.get(&n) {
             commands.entity(e).insert(ChunkDirty);
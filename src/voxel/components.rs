@@ -4,4 +4,4 @@ use bevy::prelude::*;
 pub struct ChunkMeshChild; // sitzt auf dem Kind-Entity
 
 #[derive(Component)]
-pub struct ChunkMeshTag;
\ No newline at end of file
+pub struct ChunkWireframeChild; // Drahtgitter, gleiches Mesh wie ChunkMeshChild
\ No newline at end of file
@@ -0,0 +1,123 @@
+//! Konsolenbefehle für die Welt: Sichtweite, Blöcke setzen/füllen, Chunk neu
+//! generieren, Configs neu laden.
+
+use bevy::prelude::*;
+use std::path::Path;
+
+use crate::config::{BlocksConfig, BlocksConfigRes, WorldGenConfig, WorldGenConfigRes};
+use crate::console::{ConsoleAppExt, ConsoleArgs, ConsoleResult};
+use crate::save::WorldMeta;
+use crate::settings::{MAX_VIEW_RADIUS, Settings};
+
+use super::chunk::{ChunkData, ChunkDirty, ChunkPos, world_to_chunk_pos};
+use super::chunk_store::ChunkModified;
+use super::chunk_stream::{ChunkStreamConfig, mark_neighbors_dirty};
+use super::edit::EditBlocks;
+use super::edit_ops::{MAX_EDIT_VOLUME, Region, RunVoxelEdit, VoxelEdit};
+use super::plugin::VoxelWorld;
+use super::worldgen::WorldGenerator;
+
+const ASSETS: &str = "assets";
+
+pub(super) fn register(app: &mut App) {
+    app.add_console_command("viewradius", "[<chunks>]", "Sichtweite in Chunks anzeigen/setzen", view_radius_command)
+        .add_console_command("setblock", "<x> <y> <z> <block>", "einen Block setzen", set_block_command)
+        .add_console_command("fill", "<x1> <y1> <z1> <x2> <y2> <z2> <block>", "Bereich füllen", fill_command)
+        .add_console_command(
+            "regen",
+            "[<cx> <cy> <cz>]",
+            "Chunk (sonst der unter der Kamera) neu generieren, Änderungen gehen verloren",
+            regen_command,
+        )
+        .add_console_command("reload", "", "blocks.ron und Generator-Config neu laden", reload_command);
+}
+
+/// Geht über `Settings`, damit Menü und Konsole dieselben Grenzen haben.
+fn view_radius_command(
+    In(args): In<ConsoleArgs>,
+    cfg: Res<ChunkStreamConfig>,
+    mut settings: ResMut<Settings>,
+) -> ConsoleResult {
+    if args.is_empty() {
+        return Ok(format!("Sichtweite {} (Entladen ab {})", cfg.view_radius, cfg.unload_radius));
+    }
+    let r: i32 = args.get(0, "Radius")?;
+    if !(1..=MAX_VIEW_RADIUS).contains(&r) {
+        return Err(format!("Radius muss zwischen 1 und {MAX_VIEW_RADIUS} liegen"));
+    }
+    // Hysterese beibehalten
+    let hysteresis = settings.unload_radius - settings.view_radius;
+    let mut s = settings.clone();
+    s.view_radius = r;
+    s.unload_radius = r + hysteresis.max(1);
+    *settings = s.clamped();
+    Ok(format!("Sichtweite {r}"))
+}
+
+fn set_block_command(In(args): In<ConsoleArgs>, mut ev: MessageWriter<EditBlocks>) -> ConsoleResult {
+    let pos = args.ivec3(0, "Position")?;
+    let block = args.block(3)?;
+    ev.write(EditBlocks(vec![(pos, block)]));
+    Ok(format!("{} bei {pos}", block.key()))
+}
+
+fn fill_command(In(args): In<ConsoleArgs>, mut ev: MessageWriter<RunVoxelEdit>) -> ConsoleResult {
+    let region = Region::new(args.ivec3(0, "Ecke 1")?, args.ivec3(3, "Ecke 2")?);
+    let block = args.block(6)?;
+    let count = region.volume();
+    if count > MAX_EDIT_VOLUME {
+        return Err(format!("{count} Blöcke, erlaubt sind höchstens {MAX_EDIT_VOLUME}"));
+    }
+    ev.write(RunVoxelEdit(VoxelEdit::Fill { region, block }));
+    Ok(format!("{count} Blöcke {}", block.key()))
+}
+
+fn regen_command(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    world: Res<VoxelWorld>,
+    generator: Res<WorldGenerator>,
+    mut chunks: Query<&mut ChunkData>,
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
+) -> ConsoleResult {
+    let pos = if args.is_empty() {
+        let cam = cam_q.single().map_err(|_| "keine Kamera".to_string())?;
+        world_to_chunk_pos(cam.translation())
+    } else {
+        ChunkPos(args.ivec3(0, "Chunk")?)
+    };
+
+    let &entity = world.chunks.get(&pos).ok_or_else(|| format!("Chunk {} ist nicht geladen", pos.0))?;
+    let mut data = chunks.get_mut(entity).map_err(|e| e.to_string())?;
+    *data = generator.generate_chunk(pos);
+
+    // ChunkModified, damit der gespeicherte (editierte) Stand überschrieben wird
+    commands.entity(entity).insert((ChunkModified, ChunkDirty));
+    mark_neighbors_dirty(&mut commands, &world, pos);
+    Ok(format!("Chunk {} neu generiert", pos.0))
+}
+
+fn reload_command(
+    _: In<ConsoleArgs>,
+    mut commands: Commands,
+    world: Res<VoxelWorld>,
+    generator: Res<WorldGenerator>,
+    meta: Option<Res<WorldMeta>>,
+) -> ConsoleResult {
+    let assets = Path::new(ASSETS);
+    // erst prüfen, dann ersetzen: ein halb editiertes blocks.ron darf das Meshen nicht abschießen
+    let blocks = BlocksConfig::from_file(&assets.join("blocks.ron"))
+        .and_then(|b| b.validate().map(|()| b))
+        .map_err(|e| format!("{e:#}"))?;
+    let path = meta.as_ref().map_or_else(|| "worldgen.ron".to_string(), |m| m.generator.config.clone());
+    let worldgen = WorldGenConfig::from_files(assets, &path).map_err(|e| format!("{e:#}"))?;
+    // nur für diese Sitzung: die in world.ron festgeschriebenen Parameter bleiben unangetastet.
+    // Neue Chunks kommen aus dem neuen Generator, geladene werden nur neu gemesht.
+    commands.insert_resource(WorldGenerator::new(generator.seed(), &worldgen));
+    commands.insert_resource(WorldGenConfigRes(worldgen));
+    commands.insert_resource(BlocksConfigRes(blocks));
+    for &entity in world.chunks.values() {
+        commands.entity(entity).insert(ChunkDirty);
+    }
+    Ok(format!("Configs neu geladen, {} Chunks werden neu gemesht", world.chunks.len()))
+}
@@ -0,0 +1,313 @@
+//! Blöcke in der Welt ändern, mit Undo/Redo.
+//!
+//! Alle Änderungen laufen über [`WorldBlocks::apply`]: pro Chunk gebündelt,
+//! geladene Chunks werden `ChunkModified` + `ChunkDirty`, entladene landen im
+//! `ChunkSaveStore`. Dadurch funktioniert Undo auch, wenn der Chunk zwischendurch
+//! entladen und wieder geladen wurde.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::input::{Action, ActionState};
+
+use super::chunk::{Block, CHUNK_SIZE, ChunkData, ChunkDirty, ChunkPos, block_to_chunk};
+use super::chunk_store::{ChunkModified, ChunkSaveStore};
+use super::plugin::VoxelWorld;
+use super::raycast::{RayHit, raycast_blocks};
+use super::worldgen::WorldGenerator;
+
+/// Eine tatsächlich durchgeführte Änderung (Weltkoordinate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockChange {
+    pub pos: IVec3,
+    pub old: Block,
+    pub new: Block,
+}
+
+/// Blöcke setzen; alles in einer Message ist ein Undo-Schritt.
+#[derive(Message, Clone, Debug)]
+pub struct EditBlocks(pub Vec<(IVec3, Block)>);
+
+/// Block, den `Place` setzt; `HotbarNext`/`HotbarPrev` blättern durch.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct PlaceBlock(pub Block);
+
+impl Default for PlaceBlock {
+    fn default() -> Self {
+        Self(Block::Stone)
+    }
+}
+
+/// Undo/Redo-Stapel aus Transaktionen.
+#[derive(Resource)]
+pub struct EditJournal {
+    undo: VecDeque<Vec<BlockChange>>,
+    redo: Vec<Vec<BlockChange>>,
+    /// so viele Schritte werden höchstens behalten
+    pub limit: usize,
+}
+
+impl Default for EditJournal {
+    fn default() -> Self {
+        Self { undo: VecDeque::new(), redo: Vec::new(), limit: 256 }
+    }
+}
+
+impl EditJournal {
+    /// Neue Transaktion; verwirft die Redo-Historie.
+    pub fn record(&mut self, tx: Vec<BlockChange>) {
+        if tx.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.undo.push_back(tx);
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Letzte Transaktion; wandert auf den Redo-Stapel.
+    /// Rückgängig machen heißt: `old` in umgekehrter Reihenfolge setzen.
+    pub fn undo(&mut self) -> Option<&[BlockChange]> {
+        let tx = self.undo.pop_back()?;
+        self.redo.push(tx);
+        self.redo.last().map(Vec::as_slice)
+    }
+
+    /// Zuletzt rückgängig gemachte Transaktion; wandert zurück auf den Undo-Stapel.
+    pub fn redo(&mut self) -> Option<&[BlockChange]> {
+        let tx = self.redo.pop()?;
+        self.undo.push_back(tx);
+        self.undo.back().map(Vec::as_slice)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+/// Lesen und Schreiben von Blöcken in Weltkoordinaten, egal ob der Chunk geladen ist.
+#[derive(SystemParam)]
+pub struct WorldBlocks<'w, 's> {
+    commands: Commands<'w, 's>,
+    world: Res<'w, VoxelWorld>,
+    chunks: Query<'w, 's, &'static mut ChunkData>,
+    store: ResMut<'w, ChunkSaveStore>,
+    generator: Res<'w, WorldGenerator>,
+}
+
+impl WorldBlocks<'_, '_> {
+    /// Block an `pos`; nicht geladene Chunks zählen als Luft.
+    pub fn get(&self, pos: IVec3) -> Block {
+        self.try_get(pos).unwrap_or(Block::Air)
+    }
+
+    /// `None`, wenn der Chunk nicht geladen ist.
+    pub fn try_get(&self, pos: IVec3) -> Option<Block> {
+        let (chunk, local) = block_to_chunk(pos);
+        self.world
+            .chunks
+            .get(&chunk)
+            .and_then(|&e| self.chunks.get(e).ok())
+            .map(|data| data.get_local(local.x, local.y, local.z))
+    }
+
+    /// Erster fester Block entlang des Strahls, höchstens `reach` Blöcke weit.
+    pub fn raycast(&self, origin: Vec3, dir: Vec3, reach: f32) -> Option<RayHit> {
+        raycast_blocks(origin, dir, reach, |p| self.get(p))
+    }
+
+    /// Setzt alle Blöcke, pro Chunk gebündelt, und liefert die echten Änderungen
+    /// (gleicher Block vorher/nachher fällt raus). Mehrfach genannte Positionen
+    /// werden in Reihenfolge angewendet.
+    pub fn apply(&mut self, edits: impl IntoIterator<Item = (IVec3, Block)>) -> Vec<BlockChange> {
+        let mut by_chunk: HashMap<ChunkPos, Vec<(IVec3, IVec3, Block)>> = HashMap::new();
+        for (pos, block) in edits {
+            let (chunk, local) = block_to_chunk(pos);
+            by_chunk.entry(chunk).or_default().push((pos, local, block));
+        }
+
+        let mut changes = Vec::new();
+        let mut border_neighbors = HashSet::new();
+
+        for (chunk, edits) in by_chunk {
+            let before = changes.len();
+
+            if let Some(&ent) = self.world.chunks.get(&chunk) {
+                let Ok(mut data) = self.chunks.get_mut(ent) else { continue; };
+                write_chunk(&mut data, &edits, &mut changes);
+                if changes.len() > before {
+                    self.commands.entity(ent).insert((ChunkModified, ChunkDirty));
+                }
+            } else {
+                let mut data = self
+                    .store
+                    .load_chunk(chunk)
+                    .unwrap_or_else(|| self.generator.generate_chunk(chunk));
+                write_chunk(&mut data, &edits, &mut changes);
+                if changes.len() > before {
+                    self.store.save_chunk(chunk, &data);
+                }
+            }
+
+            // Änderungen am Rand: Nachbar muss seine Seitenflächen neu bauen
+            for &(_, local, _) in &edits {
+                for axis in 0..3 {
+                    let mut offset = IVec3::ZERO;
+                    if local[axis] == 0 {
+                        offset[axis] = -1;
+                    } else if local[axis] == CHUNK_SIZE[axis] - 1 {
+                        offset[axis] = 1;
+                    } else {
+                        continue;
+                    }
+                    border_neighbors.insert(ChunkPos(chunk.0 + offset));
+                }
+            }
+        }
+
+        if !changes.is_empty() {
+            for n in border_neighbors {
+                if let Some(&e) = self.world.chunks.get(&n) {
+                    self.commands.entity(e).insert(ChunkDirty);
+                }
+            }
+        }
+
+        changes
+    }
+}
+
+fn write_chunk(data: &mut ChunkData, edits: &[(IVec3, IVec3, Block)], changes: &mut Vec<BlockChange>) {
+    for &(pos, local, new) in edits {
+        let i = ChunkData::idx(local.x, local.y, local.z);
+        let old = data.blocks[i];
+        if old != new {
+            data.blocks[i] = new;
+            changes.push(BlockChange { pos, old, new });
+        }
+    }
+}
+
+pub fn apply_block_edits_system(
+    mut ev: MessageReader<EditBlocks>,
+    mut blocks: WorldBlocks,
+    mut journal: ResMut<EditJournal>,
+) {
+    for EditBlocks(edits) in ev.read() {
+        let tx = blocks.apply(edits.iter().copied());
+        journal.record(tx);
+    }
+}
+
+/// Undo (Strg+Z) / Redo (Strg+Y oder Strg+Shift+Z).
+pub fn undo_redo_system(
+    actions: Res<ActionState>,
+    mut blocks: WorldBlocks,
+    mut journal: ResMut<EditJournal>,
+) {
+    if actions.just_pressed(Action::Undo) {
+        if let Some(tx) = journal.undo() {
+            blocks.apply(tx.iter().rev().map(|c| (c.pos, c.old)));
+        }
+    } else if actions.just_pressed(Action::Redo)
+        && let Some(tx) = journal.redo()
+    {
+        blocks.apply(tx.iter().map(|c| (c.pos, c.new)));
+    }
+}
+
+/// Reichweite der Werkzeuge in Blöcken.
+pub const EDIT_REACH: f32 = 8.0;
+
+/// `Break` (linke Maustaste) entfernt den anvisierten Block, `Place` (rechte) setzt `PlaceBlock` davor.
+pub fn block_click_tool_system(
+    actions: Res<ActionState>,
+    place: Res<PlaceBlock>,
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
+    blocks: WorldBlocks,
+    mut ev: MessageWriter<EditBlocks>,
+) {
+    let remove = actions.just_pressed(Action::Break);
+    let add = actions.just_pressed(Action::Place);
+    if !remove && !add {
+        return;
+    }
+    let Ok(cam) = cam_q.single() else { return; };
+
+    let Some(hit) = blocks.raycast(cam.translation(), cam.forward().as_vec3(), EDIT_REACH) else {
+        return;
+    };
+
+    if remove {
+        ev.write(EditBlocks(vec![(hit.block, Block::Air)]));
+    } else {
+        ev.write(EditBlocks(vec![(hit.block + hit.normal, place.0)]));
+    }
+}
+
+/// Alles außer Luft, in ID-Reihenfolge.
+fn hotbar_step(current: Block, dir: i32) -> Block {
+    let slots = &Block::ALL[1..];
+    let i = slots.iter().position(|&b| b == current).unwrap_or_default() as i32;
+    slots[(i + dir).rem_euclid(slots.len() as i32) as usize]
+}
+
+pub fn hotbar_system(actions: Res<ActionState>, mut place: ResMut<PlaceBlock>) {
+    let dir = actions.just_pressed(Action::HotbarNext) as i32 - actions.just_pressed(Action::HotbarPrev) as i32;
+    if dir != 0 {
+        place.0 = hotbar_step(place.0, dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(x: i32, old: Block, new: Block) -> BlockChange {
+        BlockChange { pos: IVec3::new(x, 0, 0), old, new }
+    }
+
+    #[test]
+    fn journal_undo_redo() {
+        let mut j = EditJournal::default();
+        j.record(vec![change(0, Block::Air, Block::Stone)]);
+        j.record(vec![change(1, Block::Air, Block::Dirt)]);
+        j.record(Vec::new()); // leer -> kein Schritt
+
+        assert_eq!(j.undo().unwrap()[0].pos.x, 1);
+        assert_eq!(j.undo().unwrap()[0].pos.x, 0);
+        assert!(j.undo().is_none());
+
+        assert_eq!(j.redo().unwrap()[0].pos.x, 0);
+        assert!(j.can_redo());
+
+        // neue Änderung verwirft Redo
+        j.record(vec![change(2, Block::Air, Block::Sand)]);
+        assert!(!j.can_redo());
+        assert_eq!(j.undo().unwrap()[0].pos.x, 2);
+    }
+
+    #[test]
+    fn journal_respects_limit() {
+        let mut j = EditJournal { limit: 2, ..default() };
+        for x in 0..5 {
+            j.record(vec![change(x, Block::Air, Block::Stone)]);
+        }
+        assert_eq!(j.undo().unwrap()[0].pos.x, 4);
+        assert_eq!(j.undo().unwrap()[0].pos.x, 3);
+        assert!(!j.can_undo());
+    }
+
+    #[test]
+    fn hotbar_wraps_and_skips_air() {
+        assert_eq!(hotbar_step(Block::Grass, -1), Block::Planks);
+        assert_eq!(hotbar_step(Block::Planks, 1), Block::Grass);
+        assert_eq!(hotbar_step(Block::Stone, 1), Block::Sand);
+    }
+}
@@ -0,0 +1,303 @@
+//! Bereichs-Operationen für den Welt-Editor: Box füllen, hohle Box, Kugel,
+//! Zylinder, Ersetzen und Kopieren/Einfügen mit Drehung.
+//!
+//! Eine Operation wird erst zu einer Liste `(Position, Block)` geplant und dann
+//! über [`WorldBlocks::apply`] geschrieben - dadurch pro Chunk gebündelt, nur
+//! berührte Chunks werden dirty, und jede Operation ist ein Undo-Schritt.
+
+use bevy::math::I64Vec3;
+use bevy::prelude::*;
+
+use super::chunk::Block;
+use super::edit::{EditJournal, WorldBlocks};
+
+/// Größere Operationen werden abgelehnt (128³ Blöcke); Kugel und Zylinder
+/// zählen mit ihrem umschließenden Quader.
+pub const MAX_EDIT_VOLUME: i64 = 128 * 128 * 128;
+
+/// Achsenparalleler Bereich in Blockkoordinaten, `min` und `max` inklusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub min: IVec3,
+    pub max: IVec3,
+}
+
+impl Region {
+    /// Aus zwei beliebigen Ecken.
+    pub fn new(a: IVec3, b: IVec3) -> Self {
+        Self { min: a.min(b), max: a.max(b) }
+    }
+
+    pub fn size(&self) -> IVec3 {
+        self.max - self.min + IVec3::ONE
+    }
+
+    /// Anzahl Blöcke, in i64 und sättigend - `size()` kann bei riesigen Bereichen überlaufen.
+    pub fn volume(&self) -> i64 {
+        let s = self.max.as_i64vec3() - self.min.as_i64vec3() + I64Vec3::ONE;
+        s.x.saturating_mul(s.y).saturating_mul(s.z)
+    }
+
+    pub fn contains(&self, p: IVec3) -> bool {
+        p.cmpge(self.min).all() && p.cmple(self.max).all()
+    }
+
+    /// Alle Positionen, x läuft innen (wie in `ChunkData`).
+    pub fn iter(&self) -> impl Iterator<Item = IVec3> + use<> {
+        let Region { min, max } = *self;
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec3::new(x, y, z)))
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum VoxelEdit {
+    Fill { region: Region, block: Block },
+    /// nur die Außenhülle (1 Block dick)
+    Hollow { region: Region, block: Block },
+    /// alle Blöcke, deren Mitte höchstens `radius` von der Mitte von `center` entfernt ist
+    Sphere { center: IVec3, radius: f32, block: Block },
+    /// senkrecht, `base` ist die Mitte der untersten Schicht
+    Cylinder { base: IVec3, radius: f32, height: i32, block: Block },
+    /// nur in geladenen Chunks, sonst weiß man nicht, was dort steht
+    Replace { region: Region, from: Block, to: Block },
+    /// Bereich in die Zwischenablage (ändert die Welt nicht)
+    Copy { region: Region },
+    /// Zwischenablage mit `min`-Ecke bei `origin` einfügen, vorher um Y drehen
+    Paste { origin: IVec3, quarter_turns: u8, skip_air: bool },
+}
+
+/// Operation aus Code oder Editor-Werkzeug ausführen.
+#[derive(Message, Clone, Debug)]
+pub struct RunVoxelEdit(pub VoxelEdit);
+
+/// Kopierter Bereich, relativ zur `min`-Ecke.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct Clipboard {
+    pub size: IVec3,
+    pub blocks: Vec<Block>,
+}
+
+impl Clipboard {
+    pub fn copy(region: Region, get: impl Fn(IVec3) -> Block) -> Self {
+        Self {
+            size: region.size(),
+            blocks: region.iter().map(get).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn index(&self, p: IVec3) -> usize {
+        (p.x + self.size.x * (p.y + self.size.y * p.z)) as usize
+    }
+
+    pub fn get(&self, p: IVec3) -> Block {
+        self.blocks[self.index(p)]
+    }
+
+    /// Um die Y-Achse gedreht, je 90° gegen den Uhrzeigersinn (von oben gesehen).
+    pub fn rotated(&self, quarter_turns: u8) -> Self {
+        let mut out = self.clone();
+        for _ in 0..quarter_turns % 4 {
+            out = out.rotated_once();
+        }
+        out
+    }
+
+    fn rotated_once(&self) -> Self {
+        let size = IVec3::new(self.size.z, self.size.y, self.size.x);
+        let mut out = Self { size, blocks: vec![Block::Air; self.blocks.len()] };
+        for p in Region::new(IVec3::ZERO, self.size - IVec3::ONE).iter() {
+            let q = IVec3::new(self.size.z - 1 - p.z, p.y, p.x);
+            let i = out.index(q);
+            out.blocks[i] = self.get(p);
+        }
+        out
+    }
+}
+
+impl VoxelEdit {
+    /// Blöcke, die `plan` höchstens anfasst (Bounding-Box), ohne zu planen.
+    pub fn volume(&self, clipboard: &Clipboard) -> i64 {
+        // Durchmesser des umschließenden Quaders
+        let diameter = |radius: f32| (radius.max(0.0).floor() as i64).saturating_mul(2).saturating_add(1);
+        match *self {
+            VoxelEdit::Fill { region, .. }
+            | VoxelEdit::Hollow { region, .. }
+            | VoxelEdit::Replace { region, .. }
+            | VoxelEdit::Copy { region } => region.volume(),
+            VoxelEdit::Sphere { radius, .. } => {
+                let d = diameter(radius);
+                d.saturating_mul(d).saturating_mul(d)
+            }
+            VoxelEdit::Cylinder { radius, height, .. } => {
+                let d = diameter(radius);
+                d.saturating_mul(d).saturating_mul(height.max(1) as i64)
+            }
+            VoxelEdit::Paste { .. } => clipboard.blocks.len() as i64,
+        }
+    }
+
+    /// Welche Blöcke geschrieben werden. `get` liefert `None` für nicht geladene Positionen.
+    /// `Copy` plant nichts, das erledigt [`run_voxel_edits_system`].
+    pub fn plan(&self, clipboard: &Clipboard, get: impl Fn(IVec3) -> Option<Block>) -> Vec<(IVec3, Block)> {
+        match *self {
+            VoxelEdit::Fill { region, block } => region.iter().map(|p| (p, block)).collect(),
+            VoxelEdit::Hollow { region, block } => region
+                .iter()
+                .filter(|p| p.cmpeq(region.min).any() || p.cmpeq(region.max).any())
+                .map(|p| (p, block))
+                .collect(),
+            VoxelEdit::Sphere { center, radius, block } => {
+                let r = radius.max(0.0);
+                let ri = r.floor() as i32;
+                Region::new(center - IVec3::splat(ri), center + IVec3::splat(ri))
+                    .iter()
+                    .filter(|&p| (p - center).as_vec3().length_squared() <= r * r)
+                    .map(|p| (p, block))
+                    .collect()
+            }
+            VoxelEdit::Cylinder { base, radius, height, block } => {
+                let r = radius.max(0.0);
+                let ri = r.floor() as i32;
+                let top = base.y + height.max(1) - 1;
+                Region::new(
+                    IVec3::new(base.x - ri, base.y, base.z - ri),
+                    IVec3::new(base.x + ri, top, base.z + ri),
+                )
+                .iter()
+                .filter(|&p| (p - base).xz().as_vec2().length_squared() <= r * r)
+                .map(|p| (p, block))
+                .collect()
+            }
+            VoxelEdit::Replace { region, from, to } => region
+                .iter()
+                .filter(|&p| get(p) == Some(from))
+                .map(|p| (p, to))
+                .collect(),
+            VoxelEdit::Copy { .. } => Vec::new(),
+            VoxelEdit::Paste { origin, quarter_turns, skip_air } => {
+                if clipboard.is_empty() {
+                    return Vec::new();
+                }
+                let clip = clipboard.rotated(quarter_turns);
+                Region::new(IVec3::ZERO, clip.size - IVec3::ONE)
+                    .iter()
+                    .map(|p| (origin + p, clip.get(p)))
+                    .filter(|&(_, b)| !(skip_air && b == Block::Air))
+                    .collect()
+            }
+        }
+    }
+}
+
+pub fn run_voxel_edits_system(
+    mut ev: MessageReader<RunVoxelEdit>,
+    mut blocks: WorldBlocks,
+    mut journal: ResMut<EditJournal>,
+    mut clipboard: ResMut<Clipboard>,
+) {
+    for RunVoxelEdit(edit) in ev.read() {
+        let volume = edit.volume(&clipboard);
+        if volume > MAX_EDIT_VOLUME {
+            warn!("{edit:?}: {volume} Blöcke, erlaubt sind höchstens {MAX_EDIT_VOLUME}");
+            continue;
+        }
+        if let VoxelEdit::Copy { region } = *edit {
+            *clipboard = Clipboard::copy(region, |p| blocks.get(p));
+            continue;
+        }
+
+        let planned = edit.plan(&clipboard, |p| blocks.try_get(p));
+        let tx = blocks.apply(planned);
+        journal.record(tx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(edit: VoxelEdit) -> Vec<(IVec3, Block)> {
+        edit.plan(&Clipboard::default(), |_| Some(Block::Air))
+    }
+
+    #[test]
+    fn shape_block_counts() {
+        let region = Region::new(IVec3::new(3, 3, 3), IVec3::new(-1, 0, 1));
+        assert_eq!(region.size(), IVec3::new(5, 4, 3));
+        assert_eq!(plan(VoxelEdit::Fill { region, block: Block::Stone }).len(), 60);
+        // 60 minus Inneres 3x2x1
+        assert_eq!(plan(VoxelEdit::Hollow { region, block: Block::Stone }).len(), 54);
+
+        // Radius 1: Mitte + 6 Nachbarn
+        let sphere = plan(VoxelEdit::Sphere { center: IVec3::ZERO, radius: 1.0, block: Block::Stone });
+        assert_eq!(sphere.len(), 7);
+        let sphere = plan(VoxelEdit::Sphere { center: IVec3::ZERO, radius: 4.0, block: Block::Stone });
+        assert!(sphere.iter().all(|(p, _)| p.as_vec3().length() <= 4.0));
+        assert!(sphere.iter().any(|(p, _)| *p == IVec3::new(0, -4, 0)));
+
+        // Radius 1, Höhe 3: Plus-Form (5) pro Schicht
+        let cyl = plan(VoxelEdit::Cylinder { base: IVec3::ZERO, radius: 1.0, height: 3, block: Block::Stone });
+        assert_eq!(cyl.len(), 15);
+        assert!(cyl.iter().all(|(p, _)| (0..3).contains(&p.y)));
+    }
+
+    #[test]
+    fn edit_volume_is_the_bounding_box() {
+        let clip = Clipboard::default();
+        let region = Region::new(IVec3::new(3, 3, 3), IVec3::new(-1, 0, 1));
+        assert_eq!(VoxelEdit::Fill { region, block: Block::Stone }.volume(&clip), 60);
+        assert_eq!(VoxelEdit::Copy { region }.volume(&clip), 60);
+        assert_eq!(VoxelEdit::Sphere { center: IVec3::ZERO, radius: 1.5, block: Block::Stone }.volume(&clip), 27);
+        let cyl = VoxelEdit::Cylinder { base: IVec3::ZERO, radius: 2.0, height: 10, block: Block::Stone };
+        assert_eq!(cyl.volume(&clip), 250);
+
+        // riesig: kein Überlauf, sondern klar zu groß
+        let huge = Region::new(IVec3::MIN, IVec3::MAX);
+        assert_eq!(huge.volume(), i64::MAX);
+        assert!(VoxelEdit::Fill { region: huge, block: Block::Stone }.volume(&clip) > MAX_EDIT_VOLUME);
+        let sphere = VoxelEdit::Sphere { center: IVec3::ZERO, radius: f32::INFINITY, block: Block::Stone };
+        assert!(sphere.volume(&clip) > MAX_EDIT_VOLUME);
+        let flat = Region::new(IVec3::ZERO, IVec3::new(999, 0, 999));
+        assert!(VoxelEdit::Hollow { region: flat, block: Block::Stone }.volume(&clip) <= MAX_EDIT_VOLUME);
+    }
+
+    #[test]
+    fn replace_only_matching_loaded_blocks() {
+        let region = Region::new(IVec3::ZERO, IVec3::new(3, 0, 0));
+        let get = |p: IVec3| match p.x {
+            0 | 2 => Some(Block::Dirt),
+            1 => Some(Block::Stone),
+            _ => None, // nicht geladen
+        };
+        let out = VoxelEdit::Replace { region, from: Block::Dirt, to: Block::Sand }.plan(&Clipboard::default(), get);
+        assert_eq!(out, vec![(IVec3::new(0, 0, 0), Block::Sand), (IVec3::new(2, 0, 0), Block::Sand)]);
+    }
+
+    #[test]
+    fn clipboard_rotation() {
+        // L-Form 3x1x2
+        let region = Region::new(IVec3::ZERO, IVec3::new(2, 0, 1));
+        let clip = Clipboard::copy(region, |p| if p == IVec3::ZERO { Block::Stone } else if p.z == 0 { Block::Dirt } else { Block::Air });
+
+        let once = clip.rotated(1);
+        assert_eq!(once.size, IVec3::new(2, 1, 3));
+        // (0,0,0) -> (size.z-1, 0, 0)
+        assert_eq!(once.get(IVec3::new(1, 0, 0)), Block::Stone);
+        assert_eq!(once.get(IVec3::new(1, 0, 2)), Block::Dirt);
+
+        assert_eq!(clip.rotated(4), clip);
+        assert_eq!(clip.rotated(1).rotated(3), clip);
+        assert_ne!(clip.rotated(2), clip);
+
+        let pasted = VoxelEdit::Paste { origin: IVec3::new(10, 0, 0), quarter_turns: 0, skip_air: true }
+            .plan(&clip, |_| None);
+        assert_eq!(pasted.len(), 3);
+        assert!(pasted.contains(&(IVec3::new(10, 0, 0), Block::Stone)));
+    }
+}
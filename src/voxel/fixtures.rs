@@ -0,0 +1,78 @@
+//! Feste Testchunks für Mesher-Tests und Benchmarks.
+
+use crate::config::{BlocksConfig, BlocksConfigRes, WorldGenConfig};
+
+use super::chunk::{Block, CHUNK_SIZE, ChunkData};
+use super::plugin::make_test_blocks;
+
+/// `assets/blocks.ron`, direkt eingebunden (ohne AssetServer).
+pub fn blocks_config() -> BlocksConfigRes {
+    let cfg: BlocksConfig = ron::from_str(include_str!("../../assets/blocks.ron")).expect("assets/blocks.ron");
+    BlocksConfigRes(cfg)
+}
+
+/// `assets/worldgen.ron` samt Vorlagen, direkt eingebunden.
+pub fn worldgen_config() -> WorldGenConfig {
+    let mut cfg: WorldGenConfig = ron::from_str(include_str!("../../assets/worldgen.ron")).expect("assets/worldgen.ron");
+    let templates = [
+        ("structures/tree.ron", include_str!("../../assets/structures/tree.ron")),
+        ("structures/boulder.ron", include_str!("../../assets/structures/boulder.ron")),
+        ("structures/hut.ron", include_str!("../../assets/structures/hut.ron")),
+    ];
+    for (path, src) in templates {
+        cfg.templates.insert(path.to_string(), ron::from_str(src).expect(path));
+    }
+    cfg
+}
+
+fn empty() -> Vec<Block> {
+    vec![Block::Air; (CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z) as usize]
+}
+
+/// Eine Lage Gras bei y == 0.
+pub fn flat_plane() -> ChunkData {
+    let mut blocks = empty();
+    for z in 0..CHUNK_SIZE.z {
+        for x in 0..CHUNK_SIZE.x {
+            blocks[ChunkData::idx(x, 0, z)] = Block::Grass;
+        }
+    }
+    ChunkData { blocks }
+}
+
+/// Boden mit Säule in der Mitte (wie beim Start im Spiel).
+pub fn pillar() -> ChunkData {
+    ChunkData { blocks: make_test_blocks() }
+}
+
+/// Jeder zweite Block solide - schlimmster Fall, nichts lässt sich zusammenfassen.
+pub fn checkerboard() -> ChunkData {
+    let mut blocks = empty();
+    for z in 0..CHUNK_SIZE.z {
+        for y in 0..CHUNK_SIZE.y {
+            for x in 0..CHUNK_SIZE.x {
+                if (x + y + z) % 2 == 0 {
+                    blocks[ChunkData::idx(x, y, z)] = Block::Stone;
+                }
+            }
+        }
+    }
+    ChunkData { blocks }
+}
+
+/// Zufällige Blöcke (etwa halb Luft), deterministisch pro `seed`.
+pub fn random(seed: u64) -> ChunkData {
+    let mut s = seed;
+    let blocks = (0..CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z)
+        .map(|_| {
+            // LCG, reicht für Testdaten
+            s = s.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            match (s >> 60) % 4 {
+                0 | 1 => Block::Air,
+                2 => Block::Dirt,
+                _ => Block::Stone,
+            }
+        })
+        .collect();
+    ChunkData { blocks }
+}
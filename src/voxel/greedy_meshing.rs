@@ -1,16 +1,14 @@
-use bevy::{
-    asset::RenderAssetUsages,
-    mesh::{Indices, PrimitiveTopology},
-    prelude::*,
-};
+use bevy::prelude::*;
 
 use crate::{
     config::BlocksConfigRes,
     voxel::{
-        chunk::{face_id, Block, CHUNK_SIZE, ChunkData, ChunkPos},
-        meshing::{face_kind, get_block_world, tile_for, effective_block_kind, FaceDir},
-        plugin::VoxelWorld,
-        tile::{tile_uv, push_uvs, UvRot},
+        chunk::{face_id, Block},
+        mesh_buffers::ChunkMeshBuffers,
+        meshing::{face_kind, tile_for, effective_block_kind, FaceDir},
+        neighborhood::ChunkNeighborhood,
+        packed_vertex::{PackedVertex, MAX_AO, MAX_LIGHT},
+        tile::{uv_corners, UvRot},
     },
 };
 
@@ -19,68 +17,40 @@ use crate::{
 /// - wir sweepen jede Achse separat
 /// - pro Grenzfläche bauen wir eine 2D-Maske
 /// - auf dieser Maske laufen wir greedy rectangles
-pub fn build_chunk_mesh_greedy_all_axes(
-    cfg: &BlocksConfigRes,
-    world: &VoxelWorld,
-    all_chunks: &Query<&ChunkData>,
-    chunk_pos: ChunkPos,
-    data: &ChunkData,
-) -> Mesh {
-    let mut positions: Vec<[f32; 3]> = vec![];
-    let mut normals: Vec<[f32; 3]> = vec![];
-    let mut uvs: Vec<[f32; 2]> = vec![];
-    let mut indices: Vec<u32> = vec![];
+///
+/// Bei `lod > 0` wird der Chunk vorher vergröbert und in LOD-Zellen gemesht.
+/// Gecullt wird nur innerhalb des Chunks, der Rand hat immer Wände - die
+/// schließen auch die Lücken an LOD-Nähten, eigene Skirts braucht es nicht.
+pub fn build_chunk_mesh_greedy_all_axes(cfg: &BlocksConfigRes, nb: &ChunkNeighborhood) -> ChunkMeshBuffers {
+    let mut out = ChunkMeshBuffers::default();
+    let scale = nb.lod().scale() as f32;
 
     // Greedy für Z, dann X, dann Y (Reihenfolge egal)
-    greedy_axis(
-        2, // Z
-        cfg, world, all_chunks, chunk_pos, data,
-        &mut positions, &mut normals, &mut uvs, &mut indices,
-    );
-    greedy_axis(
-        0, // X
-        cfg, world, all_chunks, chunk_pos, data,
-        &mut positions, &mut normals, &mut uvs, &mut indices,
-    );
-    greedy_axis(
-        1, // Y
-        cfg, world, all_chunks, chunk_pos, data,
-        &mut positions, &mut normals, &mut uvs, &mut indices,
-    );
-
-    let mut mesh = Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
-    );
-    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-    mesh.insert_indices(Indices::U32(indices));
-    mesh
+    for axis in MESH_AXES {
+        greedy_axis(axis, scale, cfg, nb, &mut out);
+    }
+
+    out
 }
 
+/// Reihenfolge der Achsen; der Binary-Mesher muss dieselbe benutzen,
+/// sonst sind die Buffer nicht identisch.
+pub const MESH_AXES: [usize; 3] = [2, 0, 1];
+
 /// Eine Achse greedy meshen.
 /// axis: 0=X, 1=Y, 2=Z
+/// `scale` ist die Zellgröße in Blöcken (bei LOD > 1).
 fn greedy_axis(
     axis: usize,
+    scale: f32,
     cfg: &BlocksConfigRes,
-    world: &VoxelWorld,
-    all_chunks: &Query<&ChunkData>,
-    chunk_pos: ChunkPos,
-    data: &ChunkData,
-    positions: &mut Vec<[f32; 3]>,
-    normals: &mut Vec<[f32; 3]>,
-    uvs: &mut Vec<[f32; 2]>,
-    indices: &mut Vec<u32>,
+    nb: &ChunkNeighborhood,
+    out: &mut ChunkMeshBuffers,
 ) {
-    let size = [CHUNK_SIZE.x, CHUNK_SIZE.y, CHUNK_SIZE.z];
+    let dim = nb.dim();
+    let size = [dim.x, dim.y, dim.z];
 
-    // Die zwei Achsen in der Maske (U,V) sind die "anderen beiden".
-    let (u_axis, v_axis) = match axis {
-        0 => (1, 2), // X sweep -> Maske ist YZ
-        1 => (0, 2), // Y sweep -> Maske ist XZ
-        _ => (0, 1), // Z sweep -> Maske ist XY
-    };
+    let (u_axis, v_axis) = mask_axes(axis);
 
     let su = size[u_axis];
     let sv = size[v_axis];
@@ -106,12 +76,12 @@ fn greedy_axis(
                 let a = if slice == 0 {
                     Block::Air
                 } else {
-                    get_block(world, all_chunks, chunk_pos, data, a_pos)
+                    nb.get(a_pos)
                 };
                 let b = if slice == sd {
                     Block::Air
                 } else {
-                    get_block(world, all_chunks, chunk_pos, data, b_pos)
+                    nb.get(b_pos)
                 };
 
                 let (id, blk, dir) = if a != Block::Air && b == Block::Air {
@@ -122,7 +92,7 @@ fn greedy_axis(
                     // Das bleibt hier identisch, nur mit Weltkoordinaten.
                     let is_surface = {
                         let (x, y, z) = a_pos;
-                        get_block_world(world, all_chunks, chunk_pos, x, y + 1, z) == Block::Air
+                        nb.get((x, y + 1, z)) == Block::Air
                     };
                     let eff = effective_block_kind(a, is_surface);
                     (face_id(cfg, eff, dir), eff, dir)
@@ -131,7 +101,7 @@ fn greedy_axis(
                     let dir = axis_neg_dir(axis);
                     let is_surface = {
                         let (x, y, z) = b_pos;
-                        get_block_world(world, all_chunks, chunk_pos, x, y + 1, z) == Block::Air
+                        nb.get((x, y + 1, z)) == Block::Air
                     };
                     let eff = effective_block_kind(b, is_surface);
                     (face_id(cfg, eff, dir), eff, dir)
@@ -204,10 +174,8 @@ fn greedy_axis(
                     slice,
                     w,
                     h,
-                    positions,
-                    normals,
-                    uvs,
-                    indices,
+                    scale,
+                    out,
                 );
 
                 u += w;
@@ -217,29 +185,19 @@ fn greedy_axis(
     }
 }
 
-/// Block holen.
-/// - lokal innerhalb des Chunks nehmen wir data.get_local()
-/// - außerhalb nutzen wir get_block_world(...).
-fn get_block(
-    world: &VoxelWorld,
-    all_chunks: &Query<&ChunkData>,
-    chunk_pos: ChunkPos,
-    data: &ChunkData,
-    (x, y, z): (i32, i32, i32),
-) -> Block {
-    // innerhalb des eigenen chunks:
-    if (0..CHUNK_SIZE.x).contains(&x) && (0..CHUNK_SIZE.y).contains(&y) && (0..CHUNK_SIZE.z).contains(&z) {
-        data.get_local(x, y, z)
-    } else {
-        // neighbor-chunks:
-        get_block_world(world, all_chunks, chunk_pos, x, y, z)
+/// Die zwei Achsen in der Maske (U,V) sind die "anderen beiden".
+pub fn mask_axes(axis: usize) -> (usize, usize) {
+    match axis {
+        0 => (1, 2), // X sweep -> Maske ist YZ
+        1 => (0, 2), // Y sweep -> Maske ist XZ
+        _ => (0, 1), // Z sweep -> Maske ist XY
     }
 }
 
 /// Mappt (U,V,D) auf (x,y,z), abhängig von axis.
 /// d = Position entlang axis.
 /// uu/vv sind die Maskenkoordinaten.
-fn axis_uvd_to_xyz(
+pub fn axis_uvd_to_xyz(
     axis: usize,
     u_axis: usize,
     v_axis: usize,
@@ -254,14 +212,14 @@ fn axis_uvd_to_xyz(
     (xyz[0], xyz[1], xyz[2])
 }
 
-fn axis_pos_dir(axis: usize) -> FaceDir {
+pub fn axis_pos_dir(axis: usize) -> FaceDir {
     match axis {
         0 => FaceDir::PosX,
         1 => FaceDir::PosY,
         _ => FaceDir::PosZ,
     }
 }
-fn axis_neg_dir(axis: usize) -> FaceDir {
+pub fn axis_neg_dir(axis: usize) -> FaceDir {
     match axis {
         0 => FaceDir::NegX,
         1 => FaceDir::NegY,
@@ -272,7 +230,7 @@ fn axis_neg_dir(axis: usize) -> FaceDir {
 /// Emit eines greedy-Quads.
 /// Es liegt auf der Grenzfläche bei `d = slice` entlang `axis`.
 /// In U/V spannt es `u..u+w` und `v..v+h`.
-fn emit_greedy_quad(
+pub fn emit_greedy_quad(
     cfg: &BlocksConfigRes,
     block: Block,
     dir: FaceDir,
@@ -284,12 +242,10 @@ fn emit_greedy_quad(
     d: i32, // slice (Grenzfläche)
     w: i32,
     h: i32,
-    positions: &mut Vec<[f32; 3]>,
-    normals: &mut Vec<[f32; 3]>,
-    uvs: &mut Vec<[f32; 2]>,
-    indices: &mut Vec<u32>,
+    scale: f32,
+    out: &mut ChunkMeshBuffers,
 ) {
-    let base = positions.len() as u32;
+    let base = out.positions.len() as u32;
 
     // Wir bauen 4 Ecken im (U,V) Rechteck und setzen axis-Koordinate auf d.
     // Danach ordnen wir die Punkte je nach dir so an, dass "außen" CCW ist.
@@ -304,20 +260,20 @@ fn emit_greedy_quad(
     let mut p_xyz = [[0.0f32; 3]; 4];
     for (i, (uu, vv, dd)) in p_uvd.iter().copied().enumerate() {
         let (x, y, z) = axis_uvd_to_xyz(axis, u_axis, v_axis, uu, vv, dd);
-        p_xyz[i] = [x as f32, y as f32, z as f32];
+        p_xyz[i] = [x as f32 * scale, y as f32 * scale, z as f32 * scale];
     }
 
     // Normal & Vertex-Reihenfolge abhängig von FaceDir.
     // Wichtig: je nach Richtung muss die Quad-Winding gedreht werden.
-    let (n, order) = match dir {
-        FaceDir::PosX => ([ 1.0,  0.0,  0.0], [0, 3, 2, 1]),
-        FaceDir::NegX => ([-1.0,  0.0,  0.0], [0, 1, 2, 3]),
+    let order = match dir {
+        FaceDir::PosX => [0, 3, 2, 1],
+        FaceDir::NegX => [0, 1, 2, 3],
 
-        FaceDir::PosY => ([ 0.0,  1.0,  0.0], [0, 1, 2, 3]),
-        FaceDir::NegY => ([ 0.0, -1.0,  0.0], [0, 3, 2, 1]),
+        FaceDir::PosY => [0, 1, 2, 3],
+        FaceDir::NegY => [0, 3, 2, 1],
 
-        FaceDir::PosZ => ([ 0.0,  0.0,  1.0], [0, 3, 2, 1]),
-        FaceDir::NegZ => ([ 0.0,  0.0, -1.0], [0, 1, 2, 3]),
+        FaceDir::PosZ => [0, 3, 2, 1],
+        FaceDir::NegZ => [0, 1, 2, 3],
     };
 
     let p0 = p_xyz[order[0]];
@@ -337,15 +293,24 @@ fn emit_greedy_quad(
         _ => UvRot::R90,
     };
 
-    // UVs: derzeit wie vorher (eine Textur über das ganze Quad gestreckt).
-    // Wenn du wirklich kacheln willst, musst du das bewusst lösen (siehe Hinweis unten).
-    let rect = (tile_uv(tile), rot);
-    push_uvs(rect.0, rect.1, uvs);
+    // UVs: eine Kachel über das ganze Quad gestreckt (Normale/UV baut erst der Shader,
+    // auf der CPU bei Bedarf `ChunkMeshBuffers::with_attributes`).
+    // AO/Licht gibt es noch nicht -> voll hell
+    for (p, uv) in [p0, p1, p2, p3].iter().zip(uv_corners(rot)) {
+        let v = PackedVertex {
+            pos: [p[0] as u32, p[1] as u32, p[2] as u32],
+            face: dir.index(),
+            uv,
+            ao: MAX_AO,
+            light: MAX_LIGHT,
+            tile,
+        };
+        out.voxel.push(v.pack());
+    }
 
-    positions.extend_from_slice(&[p0, p1, p2, p3]);
-    normals.extend_from_slice(&[n, n, n, n]);
+    out.positions.extend_from_slice(&[p0, p1, p2, p3]);
 
-    indices.extend_from_slice(&[
+    out.indices.extend_from_slice(&[
         base, base + 2, base + 1,
         base, base + 3, base + 2,
     ]);
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+
+use super::chunk::{Block, CHUNK_SIZE, ChunkData};
+use super::chunk_stream::ChunkStreamConfig;
+
+/// Detailstufe eines Chunks.
+/// 0 = volle Auflösung, 1 = 2x, 2 = 4x, 3 = 8x vergröbert.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ChunkLod(pub u8);
+
+pub const MAX_LOD: u8 = 3;
+
+impl ChunkLod {
+    /// Kantenlänge einer LOD-Zelle in Blöcken.
+    #[inline]
+    pub fn scale(self) -> i32 {
+        1 << self.0
+    }
+
+    /// Anzahl LOD-Zellen pro Chunk-Achse.
+    #[inline]
+    pub fn dim(self) -> IVec3 {
+        CHUNK_SIZE / self.scale()
+    }
+}
+
+/// Welche LOD-Stufe ein Chunk im Abstand `dist` (Chebyshev, in Chunks) bekommt.
+/// `lod_rings[i]` ist der Abstand, ab dem Stufe i+1 gilt.
+pub fn lod_for_distance(cfg: &ChunkStreamConfig, dist: i32) -> ChunkLod {
+    let mut lod = 0;
+    for (i, &ring) in cfg.lod_rings.iter().enumerate() {
+        if dist >= ring {
+            lod = i as u8 + 1;
+        }
+    }
+    ChunkLod(lod.min(MAX_LOD))
+}
+
+/// Eine LOD-Zelle aus den vollen Chunkdaten berechnen.
+/// Die Zelle ist solide, wenn mindestens die Hälfte ihrer Blöcke solide ist,
+/// und bekommt dann den häufigsten soliden Block.
+pub fn coarse_block(data: &ChunkData, cx: i32, cy: i32, cz: i32, scale: i32) -> Block {
+    if scale == 1 {
+        return data.get_local(cx, cy, cz);
+    }
+
+    let mut counts: Vec<(Block, u32)> = Vec::with_capacity(4);
+    let mut solid = 0;
+
+    for z in cz * scale..(cz + 1) * scale {
+        for y in cy * scale..(cy + 1) * scale {
+            for x in cx * scale..(cx + 1) * scale {
+                let b = data.get_local(x, y, z);
+                if b == Block::Air {
+                    continue;
+                }
+                solid += 1;
+                match counts.iter_mut().find(|(k, _)| *k == b) {
+                    Some((_, n)) => *n += 1,
+                    None => counts.push((b, 1)),
+                }
+            }
+        }
+    }
+
+    if solid * 2 < (scale * scale * scale) as u32 {
+        return Block::Air;
+    }
+
+    // bei Gleichstand gewinnt der zuerst gefundene (unterster) Block
+    let mut best = (Block::Air, 0);
+    for &(b, n) in &counts {
+        if n > best.1 {
+            best = (b, n);
+        }
+    }
+    best.0
+}
+
+/// Ganzen Chunk auf die LOD-Stufe herunterrechnen.
+/// Ergebnis ist `dim.x * dim.y * dim.z` groß, Index wie `ChunkData::idx`, nur mit `dim`.
+pub fn downsample(data: &ChunkData, lod: ChunkLod) -> Vec<Block> {
+    let dim = lod.dim();
+    let scale = lod.scale();
+    let mut out = Vec::with_capacity((dim.x * dim.y * dim.z) as usize);
+
+    for z in 0..dim.z {
+        for y in 0..dim.y {
+            for x in 0..dim.x {
+                out.push(coarse_block(data, x, y, z, scale));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voxel::fixtures;
+
+    #[test]
+    fn rings_pick_the_lod() {
+        let cfg = ChunkStreamConfig { lod_rings: [4, 8, 12], ..default() };
+        let lods: Vec<u8> = [0, 3, 4, 7, 8, 11, 12, 40].iter().map(|&d| lod_for_distance(&cfg, d).0).collect();
+        assert_eq!(lods, [0, 0, 1, 1, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    fn coarse_cell_needs_half_solid_and_takes_the_majority() {
+        let mut data = ChunkData { blocks: vec![Block::Air; (CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z) as usize] };
+        let mut set = |x, y, z, b| data.blocks[ChunkData::idx(x, y, z)] = b;
+
+        // Zelle (0,0,0) bei scale 2: 3 von 8 solide -> Luft
+        set(0, 0, 0, Block::Stone);
+        set(1, 0, 0, Block::Stone);
+        set(0, 1, 0, Block::Dirt);
+        // Zelle (1,0,0): 4 von 8 -> solide, Stone schlägt Dirt
+        set(2, 0, 0, Block::Dirt);
+        set(3, 0, 0, Block::Stone);
+        set(2, 1, 0, Block::Stone);
+        set(3, 1, 0, Block::Stone);
+
+        assert_eq!(coarse_block(&data, 0, 0, 0, 2), Block::Air);
+        assert_eq!(coarse_block(&data, 1, 0, 0, 2), Block::Stone);
+        // scale 1 ist der Block selbst
+        assert_eq!(coarse_block(&data, 0, 1, 0, 1), Block::Dirt);
+    }
+
+    #[test]
+    fn downsample_has_lod_dimensions() {
+        let data = fixtures::checkerboard();
+        for l in 0..=MAX_LOD {
+            let lod = ChunkLod(l);
+            let dim = lod.dim();
+            assert_eq!(downsample(&data, lod).len(), (dim.x * dim.y * dim.z) as usize, "LOD {l}");
+        }
+        assert_eq!(ChunkLod(3).dim(), IVec3::splat(2));
+
+        // Schachbrett ist genau halb solide -> jede Zelle wird Stein
+        assert!(downsample(&data, ChunkLod(1)).iter().all(|&b| b == Block::Stone));
+    }
+}
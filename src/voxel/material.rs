@@ -0,0 +1,101 @@
+use bevy::{
+    mesh::MeshVertexBufferLayoutRef,
+    pbr::{MaterialPipeline, MaterialPipelineKey},
+    prelude::*,
+    reflect::TypePath,
+    render::render_resource::{AsBindGroup, PolygonMode, RenderPipelineDescriptor, SpecializedMeshPipelineError},
+    shader::ShaderRef,
+};
+
+use super::packed_vertex::ATTRIBUTE_VOXEL;
+use super::tile::{ATLAS_H, ATLAS_W, PADDING, TILE_CONTENT};
+
+const SHADER_PATH: &str = "shaders/voxel.wgsl";
+const PREPASS_SHADER_PATH: &str = "shaders/voxel_prepass.wgsl";
+const WIREFRAME_SHADER_PATH: &str = "shaders/voxel_wireframe.wgsl";
+
+/// Material für Chunk-Meshes mit gepacktem Vertexformat (siehe `packed_vertex`).
+/// Position, Normale und UV werden erst im Shader aus `ATTRIBUTE_VOXEL` gebaut.
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+pub struct VoxelMaterial {
+    /// (atlas_w, atlas_h, tile_content, padding) - gleiche Werte wie in `tile.rs`
+    #[uniform(0)]
+    pub atlas: Vec4,
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Handle<Image>,
+}
+
+impl VoxelMaterial {
+    pub fn new(texture: Handle<Image>) -> Self {
+        Self {
+            atlas: Vec4::new(ATLAS_W, ATLAS_H, TILE_CONTENT, PADDING),
+            texture,
+        }
+    }
+}
+
+impl Material for VoxelMaterial {
+    fn vertex_shader() -> ShaderRef {
+        SHADER_PATH.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        SHADER_PATH.into()
+    }
+
+    // Kamera hat SSAO -> Depth/Normal-Prepass, der muss das Format auch kennen
+    fn prepass_vertex_shader() -> ShaderRef {
+        PREPASS_SHADER_PATH.into()
+    }
+
+    fn prepass_fragment_shader() -> ShaderRef {
+        PREPASS_SHADER_PATH.into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // gilt für Main- und Prepass-Pipeline: nur das gepackte Attribut
+        let vertex_layout = layout.0.get_layout(&[ATTRIBUTE_VOXEL.at_shader_location(0)])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        Ok(())
+    }
+}
+
+/// Drahtgitter über den Chunks (Z). Bevys `WireframePlugin` braucht POSITION,
+/// die hat das gepackte Format nicht -> eigene Line-Pipeline auf demselben Mesh.
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+pub struct VoxelWireframeMaterial {
+    #[uniform(0)]
+    pub color: LinearRgba,
+}
+
+impl Material for VoxelWireframeMaterial {
+    fn vertex_shader() -> ShaderRef {
+        WIREFRAME_SHADER_PATH.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        WIREFRAME_SHADER_PATH.into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let vertex_layout = layout.0.get_layout(&[ATTRIBUTE_VOXEL.at_shader_location(0)])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        descriptor.primitive.polygon_mode = PolygonMode::Line;
+        // wie bei Bevys Wireframe: Linien minimal nach vorne, sonst Z-Fighting mit den Flächen
+        if let Some(depth) = descriptor.depth_stencil.as_mut() {
+            depth.bias.slope_scale = 1.0;
+        }
+        Ok(())
+    }
+}
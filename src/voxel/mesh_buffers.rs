@@ -0,0 +1,77 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    camera::primitives::Aabb,
+    mesh::{Indices, PrimitiveTopology},
+    prelude::*,
+};
+
+use super::packed_vertex::{ATTRIBUTE_VOXEL, PackedVertex};
+use super::tile::{corner_uv, tile_uv};
+
+/// Normale je Face, Reihenfolge wie `FaceDir::index`.
+const FACE_NORMALS: [[f32; 3]; 6] = [
+    [1.0, 0.0, 0.0],
+    [-1.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, -1.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [0.0, 0.0, -1.0],
+];
+
+/// Vertexdaten eines Chunks, bevor daraus ein Bevy-Mesh wird.
+/// Der Mesher schreibt `voxel` für die GPU und Positionen für die Aabb.
+/// Normale/UV braucht nur, wer das Mesh auf der CPU benutzt (Export, Tests) -
+/// die holt `with_attributes` aus den gepackten Vertices.
+#[derive(Default)]
+pub struct ChunkMeshBuffers {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub uvs: Vec<[f32; 2]>,
+    pub voxel: Vec<[u32; 2]>,
+    pub indices: Vec<u32>,
+}
+
+impl ChunkMeshBuffers {
+    /// Mesh für `VoxelMaterial`, enthält nur das gepackte Attribut.
+    pub fn to_packed_mesh(&self) -> Mesh {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+        );
+        mesh.insert_attribute(ATTRIBUTE_VOXEL, self.voxel.clone());
+        mesh.insert_indices(Indices::U32(self.indices.clone()));
+        mesh
+    }
+
+    /// Normale und UV aus `voxel` auspacken.
+    pub fn with_attributes(mut self) -> Self {
+        self.normals.clear();
+        self.uvs.clear();
+        for &packed in &self.voxel {
+            let v = PackedVertex::unpack(packed);
+            self.normals.push(FACE_NORMALS[v.face as usize]);
+            self.uvs.push(corner_uv(tile_uv(v.tile), v.uv));
+        }
+        self
+    }
+
+    /// Anderes Mesh anhängen, Positionen um `offset` verschoben (z.B. Chunk-Ursprung).
+    /// Das gepackte Format kann keine Weltkoordinaten -> `voxel` bleibt hier leer.
+    pub fn append(&mut self, other: &ChunkMeshBuffers, offset: Vec3) {
+        let base = self.positions.len() as u32;
+        self.positions.extend(other.positions.iter().map(|&p| (Vec3::from(p) + offset).to_array()));
+        self.normals.extend_from_slice(&other.normals);
+        self.uvs.extend_from_slice(&other.uvs);
+        self.indices.extend(other.indices.iter().map(|&i| i + base));
+    }
+
+    /// Bounding Box über alle Positionen; leeres Mesh -> Null-Aabb.
+    pub fn aabb(&self) -> Aabb {
+        let mut it = self.positions.iter().map(|&p| Vec3::from(p));
+        let Some(first) = it.next() else {
+            return Aabb::default();
+        };
+        let (min, max) = it.fold((first, first), |(lo, hi), p| (lo.min(p), hi.max(p)));
+        Aabb::from_min_max(min, max)
+    }
+}
@@ -0,0 +1,253 @@
+//! Chunk-Meshes für externe Tools exportieren: glTF (`.gltf` + `.bin`) oder OBJ + MTL,
+//! jeweils mit dem Atlas als Textur (wird neben die Datei kopiert).
+//!
+//! Gemesht wird mit dem naiven Mesher in LOD 0: ein Quad pro Blockseite, jedes
+//! mit der ganzen Kachel. Zusammengefasste Quads (Greedy) würden eine Kachel über
+//! die ganze Fläche strecken - das Kacheln pro Block kann nur unser Shader.
+
+use anyhow::{Context, bail};
+use bevy::prelude::*;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::config::BlocksConfigRes;
+use crate::input::{Action, ActionState};
+
+use super::chunk::{ChunkData, ChunkPos, block_to_chunk, chunk_origin_world, world_to_chunk_pos};
+use super::mesh_buffers::ChunkMeshBuffers;
+use super::meshing::build_chunk_mesh_with_neighbors;
+use super::neighborhood::ChunkNeighborhood;
+use super::plugin::VoxelWorld;
+use super::selection::Selection;
+
+/// Alle `chunks` naiv meshen und zu einem Mesh in Weltkoordinaten zusammenfügen.
+/// Nachbarn, die `get` nicht kennt, gelten als Luft.
+pub fn mesh_chunks<'a>(
+    cfg: &BlocksConfigRes,
+    chunks: impl IntoIterator<Item = ChunkPos>,
+    get: impl Fn(ChunkPos) -> Option<&'a ChunkData>,
+) -> ChunkMeshBuffers {
+    let mut out = ChunkMeshBuffers::default();
+
+    for pos in chunks {
+        let Some(data) = get(pos) else { continue; };
+        let nb = ChunkNeighborhood::from_chunks(pos, data, &get);
+
+        out.append(&build_chunk_mesh_with_neighbors(cfg, &nb).with_attributes(), chunk_origin_world(pos));
+    }
+
+    out
+}
+
+/// Format nach Endung: `.gltf` oder `.obj`.
+pub fn export_mesh(mesh: &ChunkMeshBuffers, out: &Path, atlas: &Path) -> anyhow::Result<()> {
+    if mesh.indices.is_empty() {
+        bail!("Mesh ist leer, nichts zu exportieren");
+    }
+    if let Some(dir) = out.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("{} anlegen", dir.display()))?;
+    }
+
+    match out.extension().and_then(|e| e.to_str()) {
+        Some("gltf") => write_gltf(mesh, out, atlas),
+        Some("obj") => write_obj(mesh, out, atlas),
+        _ => bail!("{}: nur .gltf oder .obj", out.display()),
+    }
+}
+
+/// Atlas neben die Ausgabe kopieren; liefert den Dateinamen für die Referenz.
+fn copy_atlas(atlas: &Path, out: &Path) -> anyhow::Result<String> {
+    let name = atlas
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("Atlas-Pfad ohne Dateinamen")?
+        .to_string();
+    let target = out.with_file_name(&name);
+    if target != atlas {
+        std::fs::copy(atlas, &target).with_context(|| format!("{} kopieren", atlas.display()))?;
+    }
+    Ok(name)
+}
+
+fn file_name(p: &Path) -> String {
+    p.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string()
+}
+
+pub(crate) fn json_str(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// glTF 2.0 als JSON + externer `.bin`: Positionen, Normalen, UVs, u32-Indizes.
+pub fn write_gltf(mesh: &ChunkMeshBuffers, out: &Path, atlas: &Path) -> anyhow::Result<()> {
+    let image = copy_atlas(atlas, out)?;
+    let bin_path = out.with_extension("bin");
+
+    let mut bin: Vec<u8> = Vec::new();
+    let mut views = Vec::new();
+    let mut push_view = |bin: &mut Vec<u8>, bytes: &[u8], target: u32| {
+        views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{target}}}",
+            bin.len(),
+            bytes.len()
+        ));
+        bin.extend_from_slice(bytes);
+    };
+
+    let floats = |v: &mut Vec<u8>, xs: &[f32]| xs.iter().for_each(|x| v.extend_from_slice(&x.to_le_bytes()));
+    let mut tmp = Vec::new();
+    floats(&mut tmp, mesh.positions.as_flattened());
+    push_view(&mut bin, &tmp, 34962);
+    tmp.clear();
+    floats(&mut tmp, mesh.normals.as_flattened());
+    push_view(&mut bin, &tmp, 34962);
+    tmp.clear();
+    floats(&mut tmp, mesh.uvs.as_flattened());
+    push_view(&mut bin, &tmp, 34962);
+    tmp.clear();
+    mesh.indices.iter().for_each(|i| tmp.extend_from_slice(&i.to_le_bytes()));
+    push_view(&mut bin, &tmp, 34963);
+
+    let aabb = mesh.aabb();
+    let (min, max) = (aabb.min(), aabb.max());
+    let n = mesh.positions.len();
+
+    let json = format!(
+        r#"{{
+  "asset": {{"version": "2.0", "generator": "bevy_experiments"}},
+  "scene": 0,
+  "scenes": [{{"nodes": [0]}}],
+  "nodes": [{{"mesh": 0, "name": "terrain"}}],
+  "meshes": [{{"primitives": [{{"attributes": {{"POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2}}, "indices": 3, "material": 0}}]}}],
+  "materials": [{{"name": "atlas", "alphaMode": "MASK", "pbrMetallicRoughness": {{"baseColorTexture": {{"index": 0}}, "metallicFactor": 0.0, "roughnessFactor": 1.0}}}}],
+  "textures": [{{"source": 0, "sampler": 0}}],
+  "images": [{{"uri": {image}}}],
+  "samplers": [{{"magFilter": 9728, "minFilter": 9728}}],
+  "buffers": [{{"uri": {bin_uri}, "byteLength": {bin_len}}}],
+  "bufferViews": [{views}],
+  "accessors": [
+    {{"bufferView": 0, "componentType": 5126, "count": {n}, "type": "VEC3", "min": [{}, {}, {}], "max": [{}, {}, {}]}},
+    {{"bufferView": 1, "componentType": 5126, "count": {n}, "type": "VEC3"}},
+    {{"bufferView": 2, "componentType": 5126, "count": {n}, "type": "VEC2"}},
+    {{"bufferView": 3, "componentType": 5125, "count": {idx}, "type": "SCALAR"}}
+  ]
+}}
+"#,
+        min.x, min.y, min.z, max.x, max.y, max.z,
+        image = json_str(&image),
+        bin_uri = json_str(&file_name(&bin_path)),
+        bin_len = bin.len(),
+        views = views.join(", "),
+        idx = mesh.indices.len(),
+    );
+
+    std::fs::write(&bin_path, &bin).with_context(|| format!("{} schreiben", bin_path.display()))?;
+    std::fs::write(out, json).with_context(|| format!("{} schreiben", out.display()))?;
+    Ok(())
+}
+
+/// Wavefront OBJ + MTL. OBJ zählt V von unten, daher `1 - v`.
+pub fn write_obj(mesh: &ChunkMeshBuffers, out: &Path, atlas: &Path) -> anyhow::Result<()> {
+    let image = copy_atlas(atlas, out)?;
+    let mtl_path = out.with_extension("mtl");
+
+    let mut obj = String::new();
+    writeln!(obj, "mtllib {}", file_name(&mtl_path))?;
+    writeln!(obj, "o terrain")?;
+    for p in &mesh.positions {
+        writeln!(obj, "v {} {} {}", p[0], p[1], p[2])?;
+    }
+    for uv in &mesh.uvs {
+        writeln!(obj, "vt {} {}", uv[0], 1.0 - uv[1])?;
+    }
+    for n in &mesh.normals {
+        writeln!(obj, "vn {} {} {}", n[0], n[1], n[2])?;
+    }
+    writeln!(obj, "usemtl atlas")?;
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] + 1, tri[1] + 1, tri[2] + 1];
+        writeln!(obj, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}")?;
+    }
+
+    let mtl = format!("newmtl atlas\nKa 1 1 1\nKd 1 1 1\nKs 0 0 0\nd 1\nillum 1\nmap_Kd {image}\n");
+
+    std::fs::write(&mtl_path, mtl).with_context(|| format!("{} schreiben", mtl_path.display()))?;
+    std::fs::write(out, obj).with_context(|| format!("{} schreiben", out.display()))?;
+    Ok(())
+}
+
+/// Ohne Auswahl: so viele Chunks um die Kamera herum (horizontal).
+const EXPORT_RADIUS: i32 = 4;
+
+/// `ExportMesh` (F8): geladene Chunks (Auswahl oder Umgebung der Kamera) als glTF
+/// exportieren, `ExportMeshObj` (Shift+F8) als OBJ. Landet unter `exports/`.
+pub fn mesh_export_key_system(
+    actions: Res<ActionState>,
+    cfg: Res<BlocksConfigRes>,
+    world: Res<VoxelWorld>,
+    chunks: Query<&ChunkData>,
+    selection: Res<Selection>,
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
+) {
+    let obj = actions.just_pressed(Action::ExportMeshObj);
+    if !obj && !actions.just_pressed(Action::ExportMesh) {
+        return;
+    }
+
+    let (min, max) = match selection.region() {
+        Some(region) => (block_to_chunk(region.min).0 .0, block_to_chunk(region.max).0 .0),
+        None => {
+            let Ok(cam) = cam_q.single() else { return; };
+            let c = world_to_chunk_pos(cam.translation()).0;
+            let r = IVec3::new(EXPORT_RADIUS, i32::MAX / 2, EXPORT_RADIUS);
+            (c - r, c + r)
+        }
+    };
+
+    let wanted = world
+        .chunks
+        .keys()
+        .copied()
+        .filter(|p| p.0.cmpge(min).all() && p.0.cmple(max).all());
+    let mesh = mesh_chunks(&cfg, wanted, |p| world.chunks.get(&p).and_then(|&e| chunks.get(e).ok()));
+
+    let out = PathBuf::from(if obj { "exports/terrain.obj" } else { "exports/terrain.gltf" });
+    let atlas = Path::new("assets/textures").join(&cfg.0.atlas.texture);
+    match export_mesh(&mesh, &out, &atlas) {
+        Ok(()) => info!("{} exportiert ({} Dreiecke)", out.display(), mesh.indices.len() / 3),
+        Err(e) => error!("Mesh-Export: {e:#}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voxel::chunk::{Block, CHUNK_SIZE};
+    use crate::voxel::fixtures;
+    use std::collections::HashMap;
+
+    #[test]
+    fn chunks_end_up_in_world_space() {
+        let cfg = fixtures::blocks_config();
+        let solid = ChunkData { blocks: vec![Block::Stone; (CHUNK_SIZE.element_product()) as usize] };
+        let other = ChunkData { blocks: solid.blocks.clone() };
+        let map: HashMap<ChunkPos, ChunkData> =
+            [(ChunkPos(IVec3::ZERO), solid), (ChunkPos(IVec3::X), other)].into_iter().collect();
+
+        // zwei volle Chunks nebeneinander: die gemeinsame Seite fällt weg,
+        // außen je 16x16 Quads auf 5 Seiten pro Chunk
+        let mesh = mesh_chunks(&cfg, map.keys().copied(), |p| map.get(&p));
+        assert_eq!(mesh.indices.len() / 6, 2 * 5 * 256);
+
+        // jedes Quad ist genau eine Blockseite, die Kachel wird also nie gestreckt
+        for quad in mesh.positions.chunks(4) {
+            let (lo, hi) = quad.iter().fold((Vec3::MAX, Vec3::MIN), |(lo, hi), &p| (lo.min(p.into()), hi.max(p.into())));
+            let mut extent = (hi - lo).to_array();
+            extent.sort_by(f32::total_cmp);
+            assert_eq!(extent, [0.0, 1.0, 1.0]);
+        }
+
+        let aabb = mesh.aabb();
+        assert_eq!(Vec3::from(aabb.min()), Vec3::ZERO);
+        assert_eq!(Vec3::from(aabb.max()), Vec3::new(32.0, 16.0, 16.0));
+    }
+}
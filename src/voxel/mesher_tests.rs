@@ -0,0 +1,211 @@
+//! Golden-Tests für alle Mesher auf den Fixtures aus `fixtures`.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::binary_meshing::binary_mesh;
+use super::chunk::{Block, ChunkData};
+use super::fixtures;
+use super::greedy_meshing::build_chunk_mesh_greedy_all_axes;
+use super::lod::{ChunkLod, MAX_LOD, coarse_block};
+use super::mesh_buffers::ChunkMeshBuffers;
+use super::meshing::build_chunk_mesh_with_neighbors;
+use super::neighborhood::ChunkNeighborhood;
+
+type Mesher = fn(&crate::config::BlocksConfigRes, &ChunkNeighborhood) -> ChunkMeshBuffers;
+
+const MESHERS: [(&str, Mesher); 3] = [
+    ("naive", build_chunk_mesh_with_neighbors),
+    ("greedy", build_chunk_mesh_greedy_all_axes),
+    ("binary", binary_mesh),
+];
+
+fn mesh(mesher: Mesher, data: &ChunkData) -> ChunkMeshBuffers {
+    mesher(&fixtures::blocks_config(), &ChunkNeighborhood::isolated(data, ChunkLod(0))).with_attributes()
+}
+
+/// Anzahl Quads = Vertices / 4 = Indices / 6; vorher prüfen, dass alle Buffer zusammenpassen.
+fn quads(out: &ChunkMeshBuffers) -> usize {
+    let verts = out.positions.len();
+    assert_eq!(out.normals.len(), verts);
+    assert_eq!(out.uvs.len(), verts);
+    assert_eq!(out.voxel.len(), verts);
+    assert_eq!(verts % 4, 0);
+    assert_eq!(out.indices.len(), verts / 4 * 6);
+    assert!(out.indices.iter().all(|&i| (i as usize) < verts));
+    verts / 4
+}
+
+/// Geschlossene, einheitlich orientierte Oberfläche:
+/// jede gerichtete Kante a->b hat ein Gegenstück b->a.
+/// Achsparallele Kanten werden in Einheitsstücke zerlegt, damit
+/// T-Kreuzungen zwischen großen und kleinen Greedy-Quads kein Loch vortäuschen.
+/// Außerdem muss die Dreiecksorientierung zur Normale passen.
+fn assert_watertight(out: &ChunkMeshBuffers) {
+    type P = [i32; 3];
+    let mut edges: HashMap<(P, P), i32> = HashMap::new();
+    let to_i = |p: [f32; 3]| -> P { [p[0] as i32, p[1] as i32, p[2] as i32] };
+
+    for tri in out.indices.chunks_exact(3) {
+        let p = [0, 1, 2].map(|k| out.positions[tri[k] as usize]);
+        let n = out.normals[tri[0] as usize];
+
+        let e1 = [p[1][0] - p[0][0], p[1][1] - p[0][1], p[1][2] - p[0][2]];
+        let e2 = [p[2][0] - p[0][0], p[2][1] - p[0][1], p[2][2] - p[0][2]];
+        let cross = [
+            e1[1] * e2[2] - e1[2] * e2[1],
+            e1[2] * e2[0] - e1[0] * e2[2],
+            e1[0] * e2[1] - e1[1] * e2[0],
+        ];
+        let dot = cross[0] * n[0] + cross[1] * n[1] + cross[2] * n[2];
+        assert!(dot > 0.0, "triangle {p:?} winding does not match normal {n:?}");
+
+        for k in 0..3 {
+            let (a, b) = (to_i(p[k]), to_i(p[(k + 1) % 3]));
+            let d: Vec<usize> = (0..3).filter(|&i| a[i] != b[i]).collect();
+            if d.len() != 1 {
+                // Diagonale innerhalb eines Quads
+                *edges.entry((a, b)).or_default() += 1;
+                continue;
+            }
+            let axis = d[0];
+            let step = (b[axis] - a[axis]).signum();
+            let mut cur = a;
+            while cur != b {
+                let mut next = cur;
+                next[axis] += step;
+                *edges.entry((cur, next)).or_default() += 1;
+                cur = next;
+            }
+        }
+    }
+
+    for (&(a, b), &n) in &edges {
+        let back = edges.get(&(b, a)).copied().unwrap_or(0);
+        assert_eq!(n, back, "open edge {a:?} -> {b:?}");
+    }
+}
+
+fn check(data: ChunkData, golden: [(&str, usize); 3]) {
+    for ((name, mesher), (golden_name, golden_quads)) in MESHERS.into_iter().zip(golden) {
+        assert_eq!(name, golden_name);
+        let out = mesh(mesher, &data);
+        assert_eq!(quads(&out), golden_quads, "{name}");
+        assert_eq!(out.positions.len(), golden_quads * 4, "{name} vertices");
+        assert_eq!(out.indices.len(), golden_quads * 6, "{name} indices");
+        assert_watertight(&out);
+    }
+}
+
+#[test]
+fn golden_flat_plane() {
+    // oben + unten je 16x16, 4 Seiten je 16
+    check(fixtures::flat_plane(), [("naive", 576), ("greedy", 6), ("binary", 6)]);
+}
+
+#[test]
+fn golden_pillar() {
+    // Boden: 4 Quads oben (Loch unter der Säule), 1 unten, 4 Seiten;
+    // Säule: pro Seite Erde + Gras oben drauf = 2 Quads, plus Deckel
+    check(fixtures::pillar(), [("naive", 592), ("greedy", 18), ("binary", 18)]);
+}
+
+#[test]
+fn golden_checkerboard() {
+    // keine zwei sichtbaren Flächen gleicher Richtung liegen nebeneinander
+    check(fixtures::checkerboard(), [("naive", 12288), ("greedy", 12288), ("binary", 12288)]);
+}
+
+#[test]
+fn golden_random() {
+    // festgehaltene Werte; ändern sie sich, hat sich der Mesher geändert
+    check(fixtures::random(7), [("naive", 6480), ("greedy", 5311), ("binary", 5311)]);
+}
+
+#[test]
+fn empty_chunk_has_no_faces() {
+    let empty = ChunkData { blocks: vec![Block::Air; fixtures::flat_plane().blocks.len()] };
+    for (name, mesher) in MESHERS {
+        assert_eq!(quads(&mesh(mesher, &empty)), 0, "{name}");
+    }
+}
+
+#[test]
+fn lod_meshes_are_watertight() {
+    let cfg = fixtures::blocks_config();
+    for data in [fixtures::pillar(), fixtures::random(3)] {
+        for l in 1..=MAX_LOD {
+            let nb = ChunkNeighborhood::isolated(&data, ChunkLod(l));
+            for (name, mesher) in MESHERS {
+                let out = mesher(&cfg, &nb).with_attributes();
+                quads(&out);
+                assert_watertight(&out);
+                let max = out.positions.iter().flatten().fold(0.0f32, |m, &v| m.max(v));
+                assert!(max <= 16.0, "{name} lod {l} leaves the chunk");
+            }
+        }
+    }
+}
+
+/// Greedy und Binary müssen bitgenau dasselbe liefern, auch mit Nachbar oben drüber
+/// (Gras/Erde am oberen Rand) und auf allen LOD-Stufen.
+#[test]
+fn binary_matches_greedy() {
+    let cfg = fixtures::blocks_config();
+    let mut cases = vec![fixtures::flat_plane(), fixtures::pillar(), fixtures::checkerboard()];
+    cases.extend((0..8).map(fixtures::random));
+
+    for data in &cases {
+        for above in [None, Some(Block::Stone)] {
+            for l in 0..=MAX_LOD {
+                let lod = ChunkLod(l);
+                let dim = lod.dim();
+                let nb = ChunkNeighborhood::from_fn(lod, |(x, y, z)| {
+                    let inside = (0..dim.x).contains(&x) && (0..dim.y).contains(&y) && (0..dim.z).contains(&z);
+                    if inside {
+                        return coarse_block(data, x, y, z, lod.scale());
+                    }
+                    match above {
+                        Some(b) if y == dim.y => b,
+                        _ => Block::Air,
+                    }
+                });
+
+                let a = build_chunk_mesh_greedy_all_axes(&cfg, &nb).with_attributes();
+                let b = binary_mesh(&cfg, &nb).with_attributes();
+                assert_eq!(a.positions, b.positions, "positions (lod {l})");
+                assert_eq!(a.normals, b.normals, "normals (lod {l})");
+                assert_eq!(a.uvs, b.uvs, "uvs (lod {l})");
+                assert_eq!(a.voxel, b.voxel, "voxel (lod {l})");
+                assert_eq!(a.indices, b.indices, "indices (lod {l})");
+            }
+        }
+    }
+}
+
+/// Der naive Mesher cullt gegen Nachbarn: volle Nachbarn ringsum -> nur innere Flächen.
+#[test]
+fn naive_culls_against_neighbors() {
+    let cfg = fixtures::blocks_config();
+    let data = fixtures::flat_plane();
+    let nb = ChunkNeighborhood::from_fn(ChunkLod(0), |(x, y, z)| {
+        let inside = (0..16).contains(&x) && (0..16).contains(&z);
+        if y == 0 && !inside { Block::Grass } else if inside && (0..16).contains(&y) { data.get_local(x, y, z) } else { Block::Air }
+    });
+    assert_eq!(quads(&build_chunk_mesh_with_neighbors(&cfg, &nb).with_attributes()), 512);
+}
+
+/// Für die GPU reicht das gepackte Format, Normale/UV gibt es erst auf Anfrage.
+#[test]
+fn packed_path_has_no_normals_or_uvs() {
+    let cfg = fixtures::blocks_config();
+    let nb = ChunkNeighborhood::isolated(&fixtures::pillar(), ChunkLod(0));
+    for (name, mesher) in MESHERS {
+        let out = mesher(&cfg, &nb);
+        assert!(!out.voxel.is_empty(), "{name}");
+        assert!(out.normals.is_empty() && out.uvs.is_empty(), "{name}");
+
+        let mesh = out.to_packed_mesh();
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_none() && mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_none());
+    }
+}
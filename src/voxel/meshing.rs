@@ -1,14 +1,10 @@
-use bevy::mesh::Indices;
-use bevy::{asset::RenderAssetUsages, mesh::PrimitiveTopology};
-use bevy::prelude::*;
-
 use crate::config::BlocksConfigRes;
 
-use super::chunk::{Block, CHUNK_SIZE, ChunkPos};
-use super::plugin::VoxelWorld;
-
-use super::chunk::ChunkData;
-use super::tile::{UvRot, push_uvs, tile_uv};
+use super::chunk::Block;
+use super::mesh_buffers::ChunkMeshBuffers;
+use super::neighborhood::ChunkNeighborhood;
+use super::packed_vertex::{PackedVertex, MAX_AO, MAX_LIGHT};
+use super::tile::{UvRot, uv_corners};
 
 
 #[derive(Clone, Copy)]
@@ -21,72 +17,75 @@ pub enum FaceDir {
     NegZ, // unten
 }
 
+impl FaceDir {
+    /// Index für Shader/Packing: +X, -X, +Y, -Y, +Z, -Z
+    pub fn index(self) -> u32 {
+        match self {
+            FaceDir::PosX => 0,
+            FaceDir::NegX => 1,
+            FaceDir::PosY => 2,
+            FaceDir::NegY => 3,
+            FaceDir::PosZ => 4,
+            FaceDir::NegZ => 5,
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum BlockFace { Top, Bottom, Side }
 
-pub fn build_chunk_mesh_with_neighbors(
-    cfg: &BlocksConfigRes,
-    world: &VoxelWorld,
-    all_chunks: &Query<&ChunkData>,
-    chunk_pos: ChunkPos,
-    data: &ChunkData,
-) -> Mesh {
-    let mut positions: Vec<[f32; 3]> = Vec::new();
-    let mut normals: Vec<[f32; 3]> = Vec::new();
-    let mut uvs: Vec<[f32; 2]> = Vec::new();
-    let mut indices: Vec<u32> = Vec::new();
-
-    for z in 0..CHUNK_SIZE.z {
-        for y in 0..CHUNK_SIZE.y {
-            for x in 0..CHUNK_SIZE.x {
-                let raw = data.get_local(x, y, z);
+/// Naiver Mesher: ein Quad pro sichtbarer Blockseite.
+/// Langsam und viele Vertices, aber einfach - Referenz für die anderen Mesher.
+/// Anders als die Greedy-Mesher cullt er auch gegen die Nachbarchunks.
+pub fn build_chunk_mesh_with_neighbors(cfg: &BlocksConfigRes, nb: &ChunkNeighborhood) -> ChunkMeshBuffers {
+    let mut out = ChunkMeshBuffers::default();
+    let dim = nb.dim();
+    let scale = nb.lod().scale() as f32;
+
+    for z in 0..dim.z {
+        for y in 0..dim.y {
+            for x in 0..dim.x {
+                let raw = nb.get((x, y, z));
                 if raw == Block::Air { continue; }
 
-                let is_surface = get_block_world(world, all_chunks, chunk_pos, x, y + 1, z) == Block::Air;
+                let is_surface = nb.get((x, y + 1, z)) == Block::Air;
                 let block = effective_block_kind(raw, is_surface);
 
                 // X+ (rechts ist luft, also sichtbare seite)
-                if get_block_world(&world, &all_chunks, chunk_pos, x+1, y, z) == Block::Air {
-                    push_face(&cfg, block, FaceDir::PosX, x, y, z, &mut positions, &mut normals, &mut uvs, &mut indices);
+                if nb.get((x + 1, y, z)) == Block::Air {
+                    push_face(cfg, block, FaceDir::PosX, x, y, z, scale, &mut out);
                 }
 
                 // X- (links ist luft, also sichtbare seite)
-                if get_block_world(&world, &all_chunks, chunk_pos, x - 1, y, z) == Block::Air {
-                    push_face(&cfg, block, FaceDir::NegX, x, y, z, &mut positions, &mut normals, &mut uvs, &mut indices);
+                if nb.get((x - 1, y, z)) == Block::Air {
+                    push_face(cfg, block, FaceDir::NegX, x, y, z, scale, &mut out);
                 }
 
                 // Y+ (oben ist luft, also sichtbare seite)
-                if get_block_world(&world, &all_chunks, chunk_pos, x, y + 1, z) == Block::Air {
-                    push_face(&cfg, block, FaceDir::PosY, x, y, z, &mut positions, &mut normals, &mut uvs, &mut indices);
+                if is_surface {
+                    push_face(cfg, block, FaceDir::PosY, x, y, z, scale, &mut out);
                 }
 
                 // Y- (vorne ist luft, also sichtbare seite)
-                if get_block_world(&world, &all_chunks, chunk_pos, x, y - 1, z) == Block::Air {
-                    push_face(&cfg, block, FaceDir::NegY, x, y, z, &mut positions, &mut normals, &mut uvs, &mut indices);
+                if nb.get((x, y - 1, z)) == Block::Air {
+                    push_face(cfg, block, FaceDir::NegY, x, y, z, scale, &mut out);
                 }
 
                 // Z+ (hinten ist luft, also sichtbare seite)
-                if get_block_world(&world, &all_chunks, chunk_pos, x, y, z + 1) == Block::Air {
-                    push_face(&cfg, block, FaceDir::PosZ, x, y, z, &mut positions, &mut normals, &mut uvs, &mut indices);
+                if nb.get((x, y, z + 1)) == Block::Air {
+                    push_face(cfg, block, FaceDir::PosZ, x, y, z, scale, &mut out);
                 }
 
                 // Z- (vorne ist luft, also sichtbare seite)
-                if get_block_world(&world, &all_chunks, chunk_pos, x, y, z - 1) == Block::Air {
-                    push_face(&cfg, block, FaceDir::NegZ, x, y, z, &mut positions, &mut normals, &mut uvs, &mut indices);
+                if nb.get((x, y, z - 1)) == Block::Air {
+                    push_face(cfg, block, FaceDir::NegZ, x, y, z, scale, &mut out);
                 }
 
             }
         }
     }
 
-    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-    mesh.insert_indices(Indices::U32(indices));
-    
-
-    mesh
+    out
 }
 
 fn push_face(
@@ -96,64 +95,56 @@ fn push_face(
     x: i32,
     y: i32,
     z: i32,
-    positions: &mut Vec<[f32; 3]>,
-    normals: &mut Vec<[f32; 3]>,
-    uvs: &mut Vec<[f32; 2]>,
-    indices: &mut Vec<u32>,
+    scale: f32,
+    out: &mut ChunkMeshBuffers,
 ) {
     
-    let base = positions.len() as u32;
+    let base = out.positions.len() as u32;
 
-    let x0 = x as f32;
-    let x1 = x as f32 + 1.0;
-    let y0 = y as f32;
-    let y1 = y as f32 + 1.0;
-    let z0 = z as f32;
-    let z1 = z as f32 + 1.0;
+    let x0 = x as f32 * scale;
+    let x1 = (x + 1) as f32 * scale;
+    let y0 = y as f32 * scale;
+    let y1 = (y + 1) as f32 * scale;
+    let z0 = z as f32 * scale;
+    let z1 = (z + 1) as f32 * scale;
 
     // 4 Vertices pro Face, außen gesehen CCW
-    let (p0, p1, p2, p3, n) = match dir {
+    let (p0, p1, p2, p3) = match dir {
         FaceDir::PosX => (
             [x1, y0, z0],
             [x1, y0, z1],
             [x1, y1, z1],
             [x1, y1, z0],
-            [1.0, 0.0, 0.0],
         ),
         FaceDir::NegX => (
             [x0, y0, z1],
             [x0, y0, z0],
             [x0, y1, z0],
             [x0, y1, z1],
-            [-1.0, 0.0, 0.0],
         ),
         FaceDir::PosY => (
             [x0, y1, z0],
             [x1, y1, z0],
             [x1, y1, z1],
             [x0, y1, z1],
-            [0.0, 1.0, 0.0],
         ),
         FaceDir::NegY => (
             [x0, y0, z1],
             [x1, y0, z1],
             [x1, y0, z0],
             [x0, y0, z0],
-            [0.0, -1.0, 0.0],
         ),
         FaceDir::PosZ => (
             [x1, y0, z1],
             [x0, y0, z1],
             [x0, y1, z1],
             [x1, y1, z1],
-            [0.0, 0.0, 1.0],
         ),
         FaceDir::NegZ => (
             [x0, y0, z0],
             [x1, y0, z0],
             [x1, y1, z0],
             [x0, y1, z0],
-            [0.0, 0.0, -1.0],
         ),
     };
 
@@ -165,56 +156,27 @@ fn push_face(
         _ => UvRot::R0,
     };
 
-    let rect = (tile_uv(tile), rot);
-    push_uvs(rect.0, rect.1, uvs);
+    for (p, uv) in [p0, p1, p2, p3].iter().zip(uv_corners(rot)) {
+        let v = PackedVertex {
+            pos: [p[0] as u32, p[1] as u32, p[2] as u32],
+            face: dir.index(),
+            uv,
+            ao: MAX_AO,
+            light: MAX_LIGHT,
+            tile,
+        };
+        out.voxel.push(v.pack());
+    }
 
-    positions.extend_from_slice(&[p0, p1, p2, p3]);
-    normals.extend_from_slice(&[n, n, n, n]);
+    out.positions.extend_from_slice(&[p0, p1, p2, p3]);
 
     // Triangles (CCW)
-    indices.extend_from_slice(&[
+    out.indices.extend_from_slice(&[
         base, base + 2, base + 1,
         base, base + 3, base + 2,
     ]);
 }
 
-fn neighbor_coord(base: ChunkPos, x: i32, y: i32, z: i32) -> (ChunkPos, IVec3) {
-    let sx = CHUNK_SIZE.x;
-    let sy = CHUNK_SIZE.y;
-    let sz = CHUNK_SIZE.z;
-
-    let ox = x.div_euclid(sx);
-    let oy = y.div_euclid(sy);
-    let oz = z.div_euclid(sz);
-
-    let lx = x.rem_euclid(sx);
-    let ly = y.rem_euclid(sy);
-    let lz = z.rem_euclid(sz);
-
-    (ChunkPos(base.0 + IVec3::new(ox, oy, oz)), IVec3::new(lx, ly, lz))
-}
-
-pub fn get_block_world(
-    world: &VoxelWorld,
-    all_chunks: &Query<&ChunkData>,
-    base_chunk: ChunkPos,
-    x: i32,
-    y: i32,
-    z: i32,
-) -> Block {
-    let (cp, local) = neighbor_coord(base_chunk, x, y, z);
-
-    let Some(&e) = world.chunks.get(&cp) else {
-        return Block::Air; // außerhalb geladener Welt = Luft (oder später: "Unknown")
-    };
-
-    let Ok(data) = all_chunks.get(e) else {
-        return Block::Air;
-    };
-
-    data.get_local(local.x, local.y, local.z) // deine lokale get()-Methode, ohne "out of bounds = Air"
-}
-
 pub fn effective_block_kind(
     block: Block,
     above_is_air: bool,
@@ -235,14 +197,7 @@ pub fn face_kind(dir: FaceDir) -> BlockFace {
 }
 
 pub fn tile_for(cfg: &BlocksConfigRes, block: Block, face: BlockFace) -> (u32, u32) {
-    let key = match block {
-        Block::Grass => "grass",
-        Block::Dirt => "dirt",
-        Block::Stone => "stone",
-        Block::Air => "air",
-    };
-
-    let def = cfg.0.blocks.get(key).expect("block missing in config");
+    let def = cfg.0.blocks.get(block.key()).expect("block missing in config");
 
     // Fallback: all -> specific
     if let Some(all) = def.all { return all; }
@@ -1,10 +1,28 @@
 mod plugin;
-mod chunk_store;
-mod chunk_stream;
-mod chunk;
-mod meshing;
+pub mod chunk_store;
+pub mod chunk_stream;
+mod console_commands;
+pub mod chunk;
+pub mod meshing;
 mod components;
 mod tile;
-mod greedy_meshing;
+pub mod greedy_meshing;
+pub mod binary_meshing;
+pub mod neighborhood;
+pub mod fixtures;
+pub mod lod;
+pub mod worldgen;
+pub mod edit;
+pub mod edit_ops;
+pub mod selection;
+pub mod vox;
+pub mod mesh_export;
+pub mod raycast;
+mod visibility;
+mod material;
+pub mod mesh_buffers;
+mod packed_vertex;
+#[cfg(test)]
+mod mesher_tests;
 
-pub use plugin::VoxelPlugin;
+pub use plugin::{ChunkMeshStats, MeshingMode, MeshingStats, VoxelPlugin, VoxelWorld, make_test_blocks};
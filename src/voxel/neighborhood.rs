@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+use crate::voxel::{
+    chunk::{Block, CHUNK_SIZE, ChunkData, ChunkPos, block_to_chunk},
+    lod::{coarse_block, downsample, ChunkLod},
+    plugin::VoxelWorld,
+};
+
+/// Blöcke eines Chunks plus 1 Zelle Rand aus den Nachbarchunks, in LOD-Zellen.
+/// Mehr brauchen die Mesher nicht - so laufen sie ohne ECS (Tests, Benchmarks).
+pub struct ChunkNeighborhood {
+    lod: ChunkLod,
+    /// innere Größe (LOD-Zellen)
+    dim: IVec3,
+    /// (dim + 2)^3, Index über `pidx`
+    blocks: Vec<Block>,
+}
+
+impl ChunkNeighborhood {
+    /// Aus beliebiger Funktion (Koordinaten -1..=dim) füllen.
+    pub fn from_fn(lod: ChunkLod, sample: impl Fn((i32, i32, i32)) -> Block) -> Self {
+        let dim = lod.dim();
+        let pdim = dim + IVec3::splat(2);
+
+        let mut blocks = Vec::with_capacity((pdim.x * pdim.y * pdim.z) as usize);
+        for z in -1..=dim.z {
+            for y in -1..=dim.y {
+                for x in -1..=dim.x {
+                    blocks.push(sample((x, y, z)));
+                }
+            }
+        }
+
+        Self { lod, dim, blocks }
+    }
+
+    /// Chunk ohne Nachbarn, außen ist alles Luft.
+    pub fn isolated(data: &ChunkData, lod: ChunkLod) -> Self {
+        let dim = lod.dim();
+        let scale = lod.scale();
+        Self::from_fn(lod, |(x, y, z)| {
+            let inside = (0..dim.x).contains(&x) && (0..dim.y).contains(&y) && (0..dim.z).contains(&z);
+            if inside { coarse_block(data, x, y, z, scale) } else { Block::Air }
+        })
+    }
+
+    /// Rand aus den geladenen Nachbarchunks holen.
+    /// Nicht geladene Nachbarn und Nachbarn mit anderer LOD-Stufe gelten als Luft.
+    /// Das betrifft nur den naiven Mesher, der gegen Nachbarn cullt: an einer
+    /// LOD-Naht bekommt er so seine Randflächen zurück.
+    pub fn from_world(
+        world: &VoxelWorld,
+        all_chunks: &Query<&ChunkData>,
+        lods: &Query<&ChunkLod>,
+        chunk_pos: ChunkPos,
+        data: &ChunkData,
+        lod: ChunkLod,
+    ) -> Self {
+        let scale = lod.scale();
+        let dim = lod.dim();
+        let coarse = (scale > 1).then(|| downsample(data, lod));
+
+        Self::from_fn(lod, |(x, y, z)| {
+            let inside = (0..dim.x).contains(&x) && (0..dim.y).contains(&y) && (0..dim.z).contains(&z);
+            if inside {
+                return match &coarse {
+                    Some(c) => c[(x + dim.x * (y + dim.y * z)) as usize],
+                    None => data.get_local(x, y, z),
+                };
+            }
+
+            let offset = IVec3::new(x.div_euclid(dim.x), y.div_euclid(dim.y), z.div_euclid(dim.z));
+            let Some(&e) = world.chunks.get(&ChunkPos(chunk_pos.0 + offset)) else {
+                return Block::Air;
+            };
+            if lods.get(e).copied().unwrap_or_default() != lod {
+                return Block::Air;
+            }
+            let Ok(n) = all_chunks.get(e) else {
+                return Block::Air;
+            };
+            coarse_block(n, x.rem_euclid(dim.x), y.rem_euclid(dim.y), z.rem_euclid(dim.z), scale)
+        })
+    }
+
+    /// LOD 0 mit Rand aus beliebiger Chunk-Quelle (Tools ohne ECS);
+    /// was `get` nicht kennt, ist Luft.
+    pub fn from_chunks<'a>(
+        chunk_pos: ChunkPos,
+        data: &ChunkData,
+        get: impl Fn(ChunkPos) -> Option<&'a ChunkData>,
+    ) -> Self {
+        let dim = CHUNK_SIZE;
+        Self::from_fn(ChunkLod::default(), |(x, y, z)| {
+            let local = IVec3::new(x, y, z);
+            if local.cmpge(IVec3::ZERO).all() && local.cmplt(dim).all() {
+                return data.get_local(x, y, z);
+            }
+            let (n, l) = block_to_chunk(chunk_pos.0 * dim + local);
+            get(n).map_or(Block::Air, |d| d.get_local(l.x, l.y, l.z))
+        })
+    }
+
+    pub fn lod(&self) -> ChunkLod {
+        self.lod
+    }
+
+    pub fn dim(&self) -> IVec3 {
+        self.dim
+    }
+
+    /// Block in LOD-Zellen-Koordinaten, -1..=dim erlaubt.
+    #[inline]
+    pub fn get(&self, (x, y, z): (i32, i32, i32)) -> Block {
+        debug_assert!(
+            (-1..=self.dim.x).contains(&x) && (-1..=self.dim.y).contains(&y) && (-1..=self.dim.z).contains(&z),
+            "outside neighborhood: {:?}",
+            (x, y, z)
+        );
+        let px = self.dim.x + 2;
+        let py = self.dim.y + 2;
+        self.blocks[(x + 1 + px * (y + 1 + py * (z + 1))) as usize]
+    }
+}
@@ -0,0 +1,153 @@
+use bevy::mesh::{MeshVertexAttribute, VertexFormat};
+
+/// Gepacktes Chunk-Vertex-Attribut (2x u32 = 8 Byte statt 32 Byte für Pos/Normal/UV).
+/// Wird in `assets/shaders/voxel_packed.wgsl` wieder ausgepackt.
+pub const ATTRIBUTE_VOXEL: MeshVertexAttribute =
+    MeshVertexAttribute::new("Voxel", 0x5643_0001, VertexFormat::Uint32x2);
+
+// Layout Wort 0:
+//  0..5   x        (0..=16 in Blöcken, Chunk-lokal)
+//  5..10  y
+// 10..15  z
+// 15..18  face     (FaceDir::index)
+// 18      uv_u     (0 = u0, 1 = u1 der Kachel)
+// 19      uv_v
+// 20..22  ao       (0 = dunkel, 3 = keine Verdeckung)
+// 22..26  light    (0..15)
+// Wort 1:
+//  0..16  tile.x
+// 16..32  tile.y
+const POS_BITS: u32 = 5;
+const POS_MASK: u32 = (1 << POS_BITS) - 1;
+const FACE_SHIFT: u32 = 15;
+const UV_U_SHIFT: u32 = 18;
+const UV_V_SHIFT: u32 = 19;
+const AO_SHIFT: u32 = 20;
+const LIGHT_SHIFT: u32 = 22;
+
+pub const MAX_AO: u32 = 3;
+pub const MAX_LIGHT: u32 = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PackedVertex {
+    pub pos: [u32; 3],
+    pub face: u32,
+    pub uv: [u32; 2],
+    pub ao: u32,
+    pub light: u32,
+    pub tile: (u32, u32),
+}
+
+impl PackedVertex {
+    pub fn pack(&self) -> [u32; 2] {
+        debug_assert!(self.pos.iter().all(|&p| p <= POS_MASK), "position out of range: {:?}", self.pos);
+        debug_assert!(self.face < 6);
+        debug_assert!(self.ao <= MAX_AO && self.light <= MAX_LIGHT);
+
+        let w0 = (self.pos[0] & POS_MASK)
+            | (self.pos[1] & POS_MASK) << POS_BITS
+            | (self.pos[2] & POS_MASK) << (2 * POS_BITS)
+            | (self.face & 0b111) << FACE_SHIFT
+            | (self.uv[0] & 1) << UV_U_SHIFT
+            | (self.uv[1] & 1) << UV_V_SHIFT
+            | (self.ao & MAX_AO) << AO_SHIFT
+            | (self.light & MAX_LIGHT) << LIGHT_SHIFT;
+        let w1 = (self.tile.0 & 0xFFFF) | (self.tile.1 & 0xFFFF) << 16;
+
+        [w0, w1]
+    }
+
+    /// CPU-Gegenstück zu `unpack_voxel` im Shader.
+    pub fn unpack([w0, w1]: [u32; 2]) -> Self {
+        Self {
+            pos: [
+                w0 & POS_MASK,
+                (w0 >> POS_BITS) & POS_MASK,
+                (w0 >> (2 * POS_BITS)) & POS_MASK,
+            ],
+            face: (w0 >> FACE_SHIFT) & 0b111,
+            uv: [(w0 >> UV_U_SHIFT) & 1, (w0 >> UV_V_SHIFT) & 1],
+            ao: (w0 >> AO_SHIFT) & MAX_AO,
+            light: (w0 >> LIGHT_SHIFT) & MAX_LIGHT,
+            tile: (w1 & 0xFFFF, w1 >> 16),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex() -> PackedVertex {
+        PackedVertex {
+            pos: [3, 16, 0],
+            face: 4,
+            uv: [1, 0],
+            ao: 2,
+            light: 9,
+            tile: (21, 5),
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let v = vertex();
+        assert_eq!(PackedVertex::unpack(v.pack()), v);
+    }
+
+    #[test]
+    fn roundtrip_extremes() {
+        for pos in [[0, 0, 0], [16, 16, 16], [31, 0, 31]] {
+            for face in 0..6 {
+                let v = PackedVertex {
+                    pos,
+                    face,
+                    uv: [1, 1],
+                    ao: MAX_AO,
+                    light: MAX_LIGHT,
+                    tile: (0xFFFF, 0xFFFF),
+                };
+                assert_eq!(PackedVertex::unpack(v.pack()), v);
+            }
+        }
+    }
+
+    #[test]
+    fn fields_do_not_overlap() {
+        let zero = PackedVertex {
+            pos: [0, 0, 0],
+            face: 0,
+            uv: [0, 0],
+            ao: 0,
+            light: 0,
+            tile: (0, 0),
+        };
+        assert_eq!(zero.pack(), [0, 0]);
+
+        // jedes Feld einzeln voll setzen darf nur seine eigenen Bits treffen
+        let singles = [
+            PackedVertex { pos: [POS_MASK, 0, 0], ..zero },
+            PackedVertex { pos: [0, POS_MASK, 0], ..zero },
+            PackedVertex { pos: [0, 0, POS_MASK], ..zero },
+            PackedVertex { face: 5, ..zero },
+            PackedVertex { face: 2, ..zero },
+            PackedVertex { uv: [1, 0], ..zero },
+            PackedVertex { uv: [0, 1], ..zero },
+            PackedVertex { ao: MAX_AO, ..zero },
+            PackedVertex { light: MAX_LIGHT, ..zero },
+        ];
+        let mut seen = 0u32;
+        for v in singles {
+            let [w0, w1] = v.pack();
+            assert_eq!(w1, 0);
+            assert_eq!(seen & w0, 0, "overlap in {v:?}");
+            seen |= w0;
+        }
+    }
+
+    #[test]
+    fn tile_in_second_word() {
+        let [_, w1] = vertex().pack();
+        assert_eq!(w1, 21 | (5 << 16));
+    }
+}
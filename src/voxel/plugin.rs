@@ -1,82 +1,136 @@
 use bevy::image::ImageSampler;
+use bevy::pbr::wireframe::{NoWireframe, WireframeConfig};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::app_state::{AppState, LoadingProgress};
+use crate::app_state::{AppState, InWorld, LoadingProgress};
 use crate::config::BlocksConfigRes;
-use crate::voxel::{chunk, greedy_meshing};
-use crate::voxel::chunk_store::{ChunkSaveStore, RequestChunkUnload};
+use crate::voxel::{binary_meshing, greedy_meshing, meshing, mesh_buffers::ChunkMeshBuffers, neighborhood::ChunkNeighborhood};
+use crate::voxel::edit::{EditBlocks, EditJournal, PlaceBlock, apply_block_edits_system, block_click_tool_system, hotbar_system, undo_redo_system};
+use crate::voxel::edit_ops::{Clipboard, RunVoxelEdit, run_voxel_edits_system};
+use crate::voxel::selection::{Selection, draw_selection_gizmos, selection_tool_system};
+use crate::voxel::vox::{ExportVox, ImportVox, vox_io_system, vox_keys_system};
+use crate::voxel::mesh_export::mesh_export_key_system;
+use crate::voxel::chunk_store::{ChunkSaveStore, RequestChunkUnload, handle_chunk_unload_requests_system};
 use crate::voxel::chunk_stream::{ChunkLoadQueue, ChunkStreamConfig, RequestChunkLoad, StreamTimer, chunk_stream_tick_system, handle_chunk_load_requests_system};
 
-use super::meshing::build_chunk_mesh_with_neighbors;
-use super::chunk::{Block, CHUNK_SIZE, ChunkData, ChunkDirty, ChunkPos, chunk_origin_world};
-use super::components::ChunkMeshChild;
+use super::chunk::{Block, CHUNK_SIZE, ChunkData, ChunkDirty, ChunkPos};
+use super::lod::ChunkLod;
+use super::worldgen::{WorldGenerator, WorldSeed, setup_world_generator};
+use super::material::{VoxelMaterial, VoxelWireframeMaterial};
+use super::visibility::{chunk_occlusion_culling_system, compute_visibility_graph};
+use super::components::{ChunkMeshChild, ChunkWireframeChild};
+use super::console_commands;
 
 
 #[derive(Resource)]
 pub struct VoxelMaterials {
-    pub blocks: Handle<StandardMaterial>,
+    pub blocks: Handle<VoxelMaterial>,
+    pub wireframe: Handle<VoxelWireframeMaterial>,
 }
 
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Welcher Mesher für Chunks benutzt wird. Greedy und Binary liefern dasselbe Mesh,
+/// Binary ist nur schneller; Naive (ein Quad pro Blockseite) ist zum Vergleichen da.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MeshingMode {
+    Naive,
+    Greedy,
+    #[default]
+    Binary,
+}
+
+impl MeshingMode {
+    pub fn mesh(self, cfg: &BlocksConfigRes, nb: &ChunkNeighborhood) -> ChunkMeshBuffers {
+        match self {
+            MeshingMode::Naive => meshing::build_chunk_mesh_with_neighbors(cfg, nb),
+            MeshingMode::Greedy => greedy_meshing::build_chunk_mesh_greedy_all_axes(cfg, nb),
+            MeshingMode::Binary => binary_meshing::binary_mesh(cfg, nb),
+        }
+    }
+
+    /// Hängt das Mesh von den Nachbarchunks ab (Culling über die Chunkgrenze)?
+    /// Greedy und Binary schauen nur für Gras/Erde nach oben.
+    pub fn culls_neighbors(self) -> bool {
+        self == MeshingMode::Naive
+    }
+}
 
 #[derive(Resource, Default)]
 pub struct VoxelWorld {
     pub chunks: HashMap<ChunkPos, Entity>,
 }
 
+/// Was `remesh_dirty_chunks` im letzten Frame getan hat (für das Debug-Overlay).
+#[derive(Resource, Default, Debug)]
+pub struct MeshingStats {
+    pub last_frame: Duration,
+    pub chunks_last_frame: usize,
+}
+
+/// Größe des aktuellen Meshes, sitzt auf dem Chunk-Entity.
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct ChunkMeshStats {
+    pub vertices: u32,
+    pub triangles: u32,
+}
+
 pub struct VoxelPlugin;
 
 impl Plugin for VoxelPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<VoxelWorld>()
+        app.add_plugins(MaterialPlugin::<VoxelMaterial>::default())
+        .add_plugins(MaterialPlugin::<VoxelWireframeMaterial> { prepass_enabled: false, shadows_enabled: false, ..default() })
+        .init_resource::<VoxelWorld>()
+        .init_resource::<MeshingMode>()
+        .init_resource::<MeshingStats>()
         .init_resource::<ChunkSaveStore>()
-        .insert_resource(ChunkStreamConfig {
-            view_radius: 4,
-            unload_radius: 6,
-            tick_seconds: 0.2,
-            y_min: 0,
-            y_max: 0,
-            load_budget: 2,
-        })
+        .init_resource::<WorldSeed>()
+        .init_resource::<EditJournal>()
+        .init_resource::<PlaceBlock>()
+        .init_resource::<Clipboard>()
+        .init_resource::<Selection>()
+        .init_resource::<ChunkStreamConfig>()
         .insert_resource(ChunkLoadQueue::default())
-        .insert_resource(StreamTimer(Timer::from_seconds(0.2, TimerMode::Repeating)))
+        // Dauer zieht chunk_stream_tick_system aus `tick_seconds` nach
+        .insert_resource(StreamTimer::from_config(&ChunkStreamConfig::default()))
         .add_message::<RequestChunkLoad>()
         .add_message::<RequestChunkUnload>()
+        .add_message::<EditBlocks>()
+        .add_message::<RunVoxelEdit>()
+        .add_message::<ImportVox>()
+        .add_message::<ExportVox>()
         .add_systems(Update, (setup_voxel_materials, poll_voxel_loaded).run_if(in_state(AppState::Loading)))
-        .add_systems(OnEnter(AppState::InGame), spawn_chunks)
+        .add_systems(OnEnter(InWorld), setup_world_generator)
+        .add_systems(OnExit(InWorld), unload_world)
         .add_systems(
             Update,
-            (chunk_stream_tick_system,handle_chunk_load_requests_system,remesh_dirty_chunks, handle_chunk_load_requests_system).run_if(in_state(AppState::InGame)),
-        );
-    }
-}
+            (
+                chunk_stream_tick_system,
+                handle_chunk_unload_requests_system,
+                handle_chunk_load_requests_system,
+                // Edits nach dem Laden, damit sie geladene Chunks direkt treffen
+                (
+                    hotbar_system,
+                    block_click_tool_system,
+                    selection_tool_system,
+                    vox_keys_system,
+                    vox_io_system,
+                    apply_block_edits_system,
+                    run_voxel_edits_system,
+                    undo_redo_system,
+                ).chain(),
+                remesh_dirty_chunks,
+                chunk_occlusion_culling_system,
+            ).chain().run_if(in_state(AppState::InGame)),
+        )
+        .add_systems(Update, (draw_selection_gizmos, mesh_export_key_system).run_if(in_state(AppState::InGame)))
+        .add_systems(Update, remesh_all_chunks.run_if(resource_changed::<MeshingMode>).run_if(in_state(AppState::InGame)))
+        .add_systems(Update, sync_wireframe.run_if(resource_exists_and_changed::<WireframeConfig>));
 
-fn spawn_chunks(
-    mut commands: Commands,
-    mut world: ResMut<VoxelWorld>,
-    mut chunk_store: ResMut<ChunkSaveStore>,
-) {
-    for cz in -1..=1 {
-        for cx in -1..=1 {
-            let pos = ChunkPos(IVec3::new(cx, 0, cz));
-            let data = ChunkData { blocks: make_test_blocks() };
-
-            chunk_store.save_chunk(pos, &data); // zum Testen speichern
-
-            let e = commands.spawn((
-                pos,
-                data,
-                ChunkDirty, // beim Start direkt meshen
-                Transform::from_translation(chunk_origin_world(pos)),
-                GlobalTransform::default(),
-                Visibility::default(),
-                InheritedVisibility::default(),
-                ViewVisibility::default(),
-            )).id();
-
-
-            world.chunks.insert(pos, e);
-        }
+        console_commands::register(app);
     }
 }
 
@@ -84,7 +138,9 @@ fn setup_voxel_materials(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut images: ResMut<Assets<Image>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut materials: ResMut<Assets<VoxelMaterial>>,
+    mut wire_materials: ResMut<Assets<VoxelWireframeMaterial>>,
+    wire_config: Option<Res<WireframeConfig>>,
     loaded: Option<Res<BlocksConfigRes>>,
 ) {
 
@@ -98,15 +154,25 @@ fn setup_voxel_materials(
         img.sampler = ImageSampler::nearest();
     }
 
-    let mat = materials.add(StandardMaterial {
-        base_color_texture: Some(tex),
-        perceptual_roughness: 1.0,
-        //cull_mode: None,
-        unlit: true,
-        ..default()
-    });
+    let mat = materials.add(VoxelMaterial::new(tex));
+    let color = wire_config.map_or(Color::WHITE, |c| c.default_color);
+    let wireframe = wire_materials.add(VoxelWireframeMaterial { color: color.into() });
 
-    commands.insert_resource(VoxelMaterials { blocks: mat });
+    commands.insert_resource(VoxelMaterials { blocks: mat, wireframe });
+}
+
+fn wireframe_visibility(config: Option<&WireframeConfig>) -> Visibility {
+    if config.is_some_and(|c| c.global) { Visibility::Inherited } else { Visibility::Hidden }
+}
+
+/// Globales Wireframe (Z) gilt auch für die Chunks, die haben dafür ein eigenes Kind.
+fn sync_wireframe(config: Res<WireframeConfig>, mut wires: Query<&mut Visibility, With<ChunkWireframeChild>>) {
+    let wanted = wireframe_visibility(Some(&config));
+    for mut vis in &mut wires {
+        if *vis != wanted {
+            *vis = wanted;
+        }
+    }
 }
 
 fn poll_voxel_loaded(
@@ -122,56 +188,110 @@ fn poll_voxel_loaded(
     }
 }
 
+/// Zurück ins Menü: alle Chunks weg, nichts von dieser Welt bleibt übrig.
+/// Gespeichert hat vorher das Save-Plugin (seine `OnExit`-Systeme lesen die Daten,
+/// bevor diese Despawns angewendet werden).
+fn unload_world(
+    mut commands: Commands,
+    mut world: ResMut<VoxelWorld>,
+    mut queue: ResMut<ChunkLoadQueue>,
+    mut journal: ResMut<EditJournal>,
+    mut selection: ResMut<Selection>,
+) {
+    for (_, e) in world.chunks.drain() {
+        commands.entity(e).despawn();
+    }
+    *queue = ChunkLoadQueue::default();
+    *journal = EditJournal::default();
+    *selection = Selection::default();
+    commands.remove_resource::<WorldGenerator>();
+}
+
+/// Anderer Mesher -> alles neu meshen.
+fn remesh_all_chunks(mut commands: Commands, world: Res<VoxelWorld>) {
+    for &e in world.chunks.values() {
+        commands.entity(e).insert(ChunkDirty);
+    }
+}
+
 fn remesh_dirty_chunks(
     mut commands: Commands,
     world: Res<VoxelWorld>,
     all_chunks: Query<&ChunkData>,
+    lods: Query<&ChunkLod>,
     voxel_mats: Res<VoxelMaterials>,
     mut meshes: ResMut<Assets<Mesh>>,
-    dirty: Query<(Entity, &ChunkPos, &ChunkData, Option<&Children>), With<ChunkDirty>>,
+    dirty: Query<(Entity, &ChunkPos, &ChunkData, Option<&ChunkLod>, Option<&Children>), With<ChunkDirty>>,
     //chunks: Query<(Entity, &ChunkPos, &ChunkData, Option<&Children>), With<ChunkDirty>>,
-    mesh_children: Query<Entity, With<ChunkMeshChild>>,
+    mesh_children: Query<Entity, Or<(With<ChunkMeshChild>, With<ChunkWireframeChild>)>>,
+    wire_config: Option<Res<WireframeConfig>>,
     cfg: Res<BlocksConfigRes>,
+    mode: Res<MeshingMode>,
+    mut stats: ResMut<MeshingStats>,
 ) {
-    for (chunk_e, &chunk_pos, data, children_opt) in &dirty {
-
-        let mesh = greedy_meshing::build_chunk_mesh_greedy_all_axes(&cfg, &world, &all_chunks, chunk_pos, data);
-        //let mesh = build_chunk_mesh_with_neighbors(&cfg, &world, &all_chunks, chunk_pos, data);
-        let mesh_handle = meshes.add(mesh);
-
-        // vorhandenes Mesh-Kind suchen
-        let mut existing_child: Option<Entity> = None;
-        if let Some(children) = children_opt {
-            for &c in children {
-                if mesh_children.get(c).is_ok() {
-                    existing_child = Some(c);
-                    break;
-                }
-            }
-        }
+    let started = Instant::now();
+    let mut meshed = 0;
 
-        match existing_child {
-            Some(child) => {
-                commands.entity(child).insert(Mesh3d(mesh_handle));
-            }
-            None => {
-                commands.entity(chunk_e).with_children(|p| {
-                    p.spawn((
-                        ChunkMeshChild,
-                        Mesh3d(mesh_handle),
-                        MeshMaterial3d(voxel_mats.blocks.clone()),
-                        Transform::IDENTITY,
-                        GlobalTransform::default(),
-                        Visibility::default(),
-                        InheritedVisibility::default(),
-                        ViewVisibility::default(),
-                    ));
-                });
+    for (chunk_e, &chunk_pos, data, lod, children_opt) in &dirty {
+
+        let lod = lod.copied().unwrap_or_default();
+        let nb = ChunkNeighborhood::from_world(&world, &all_chunks, &lods, chunk_pos, data, lod);
+        let buffers = mode.mesh(&cfg, &nb);
+
+        // Bevy berechnet die Aabb nur einmal (Without<Aabb>), nach einem Remesh
+        // wäre sie veraltet und das Frustum-Culling falsch -> selbst setzen.
+        // Leeres Mesh: Null-Aabb, wird dann einfach immer weggecullt.
+        // (Das gepackte Mesh hat keine POSITION, Bevy könnte sie auch gar nicht berechnen.)
+        let aabb = buffers.aabb();
+        let mesh_stats = ChunkMeshStats {
+            vertices: buffers.voxel.len() as u32,
+            triangles: (buffers.indices.len() / 3) as u32,
+        };
+        let mesh_handle = meshes.add(buffers.to_packed_mesh());
+
+        // vorhandene Mesh-Kinder (Flächen + Drahtgitter) suchen
+        let existing: Vec<Entity> = children_opt
+            .map(|children| children.iter().filter(|&c| mesh_children.contains(c)).collect())
+            .unwrap_or_default();
+
+        if existing.is_empty() {
+            commands.entity(chunk_e).with_children(|p| {
+                p.spawn((
+                    ChunkMeshChild,
+                    Mesh3d(mesh_handle.clone()),
+                    aabb,
+                    MeshMaterial3d(voxel_mats.blocks.clone()),
+                    // Bevys Wireframe-Pipeline braucht POSITION, dafür gibt es ChunkWireframeChild
+                    NoWireframe,
+                    Transform::IDENTITY,
+                    GlobalTransform::default(),
+                    Visibility::default(),
+                    InheritedVisibility::default(),
+                    ViewVisibility::default(),
+                ));
+                p.spawn((
+                    ChunkWireframeChild,
+                    Mesh3d(mesh_handle),
+                    aabb,
+                    MeshMaterial3d(voxel_mats.wireframe.clone()),
+                    NoWireframe,
+                    Transform::IDENTITY,
+                    wireframe_visibility(wire_config.as_deref()),
+                ));
+            });
+        } else {
+            for child in existing {
+                commands.entity(child).insert((Mesh3d(mesh_handle.clone()), aabb));
             }
         }
 
+        // Blockdaten können sich geändert haben -> Cave-Culling-Graph neu
+        commands.entity(chunk_e).insert((compute_visibility_graph(data), mesh_stats));
         commands.entity(chunk_e).remove::<ChunkDirty>();
+        meshed += 1;
     }
+
+    *stats = MeshingStats { last_frame: started.elapsed(), chunks_last_frame: meshed };
 }
 
 #[inline]
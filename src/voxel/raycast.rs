@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use super::chunk::Block;
+
+/// Getroffener Block und die Seite, durch die der Strahl eingetreten ist.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RayHit {
+    pub block: IVec3,
+    /// zeigt aus dem Block heraus; `block + normal` ist der Platz davor
+    pub normal: IVec3,
+}
+
+/// Voxel-Traversal (Amanatides & Woo): läuft Block für Block am Strahl entlang,
+/// bis `get` etwas anderes als Luft liefert oder `max_dist` erreicht ist.
+pub fn raycast_blocks(
+    origin: Vec3,
+    dir: Vec3,
+    max_dist: f32,
+    get: impl Fn(IVec3) -> Block,
+) -> Option<RayHit> {
+    let dir = dir.try_normalize()?;
+    let mut block = origin.floor().as_ivec3();
+    let step = dir.signum().as_ivec3();
+
+    // Strahlparameter t bis zur nächsten Blockgrenze, pro Achse
+    let next_boundary = |o: f32, b: i32, d: f32| {
+        if d > 0.0 { (b as f32 + 1.0 - o) / d } else if d < 0.0 { (b as f32 - o) / d } else { f32::INFINITY }
+    };
+    let mut t_max = Vec3::new(
+        next_boundary(origin.x, block.x, dir.x),
+        next_boundary(origin.y, block.y, dir.y),
+        next_boundary(origin.z, block.z, dir.z),
+    );
+    let t_delta = dir.abs().recip();
+
+    let mut normal = IVec3::ZERO;
+    loop {
+        if get(block) != Block::Air {
+            return Some(RayHit { block, normal });
+        }
+
+        // kleinste Grenze zuerst überschreiten
+        let axis = if t_max.x < t_max.y && t_max.x < t_max.z {
+            0
+        } else if t_max.y < t_max.z {
+            1
+        } else {
+            2
+        };
+        if t_max[axis] > max_dist {
+            return None;
+        }
+
+        block[axis] += step[axis];
+        normal = IVec3::ZERO;
+        normal[axis] = -step[axis];
+        t_max[axis] += t_delta[axis];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_floor_from_above() {
+        let get = |p: IVec3| if p.y < 0 { Block::Stone } else { Block::Air };
+        let hit = raycast_blocks(Vec3::new(0.5, 5.5, 0.5), Vec3::NEG_Y, 20.0, get).unwrap();
+        assert_eq!(hit.block, IVec3::new(0, -1, 0));
+        assert_eq!(hit.normal, IVec3::Y);
+
+        // negative Koordinaten, schräger Strahl
+        let hit = raycast_blocks(Vec3::new(-3.2, 2.0, -7.9), Vec3::new(-1.0, -1.0, 0.3), 20.0, get).unwrap();
+        assert_eq!(hit.block.y, -1);
+        assert_eq!(hit.normal, IVec3::Y);
+
+        assert!(raycast_blocks(Vec3::new(0.5, 5.5, 0.5), Vec3::Y, 20.0, get).is_none());
+    }
+}
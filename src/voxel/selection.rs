@@ -0,0 +1,108 @@
+//! Editor-Werkzeug im Spiel: Bereich mit zwei Ecken auswählen und
+//! `VoxelEdit`-Operationen darauf anwenden. Standardbelegung:
+//!
+//! 1 / 2      Ecke A / B auf den anvisierten Block
+//! E          Auswahl aufheben
+//! F / H      Auswahl füllen / hohle Box (mit `PlaceBlock`)
+//! R          in der Auswahl den anvisierten Blocktyp durch `PlaceBlock` ersetzen
+//! Strg+C     Auswahl kopieren
+//! Strg+V     Zwischenablage vor dem anvisierten Block einfügen
+//! Strg+E/I   Auswahl als .vox exportieren / wieder importieren (siehe `vox`)
+//! T          Einfügen um 90° weiterdrehen
+
+use bevy::prelude::*;
+
+use crate::input::{Action, ActionState};
+
+use super::chunk::Block;
+use super::edit::{EDIT_REACH, PlaceBlock, WorldBlocks};
+use super::edit_ops::{Region, RunVoxelEdit, VoxelEdit};
+
+#[derive(Resource, Default, Debug)]
+pub struct Selection {
+    pub a: Option<IVec3>,
+    pub b: Option<IVec3>,
+    /// Drehung beim Einfügen, in 90°-Schritten um Y
+    pub paste_turns: u8,
+}
+
+impl Selection {
+    pub fn region(&self) -> Option<Region> {
+        Some(Region::new(self.a?, self.b?))
+    }
+}
+
+pub fn selection_tool_system(
+    actions: Res<ActionState>,
+    place: Res<PlaceBlock>,
+    mut selection: ResMut<Selection>,
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
+    blocks: WorldBlocks,
+    mut ev: MessageWriter<RunVoxelEdit>,
+) {
+    if actions.just_pressed(Action::ClearSelection) {
+        *selection = Selection { paste_turns: selection.paste_turns, ..default() };
+    }
+    if actions.just_pressed(Action::RotatePaste) {
+        selection.paste_turns = (selection.paste_turns + 1) % 4;
+        info!("Einfügen gedreht: {}°", selection.paste_turns as u32 * 90);
+    }
+
+    let hit = cam_q
+        .single()
+        .ok()
+        .and_then(|cam| blocks.raycast(cam.translation(), cam.forward().as_vec3(), EDIT_REACH));
+
+    if let Some(hit) = hit {
+        if actions.just_pressed(Action::SelectCornerA) {
+            selection.a = Some(hit.block);
+        }
+        if actions.just_pressed(Action::SelectCornerB) {
+            selection.b = Some(hit.block);
+        }
+        if actions.just_pressed(Action::Paste) {
+            ev.write(RunVoxelEdit(VoxelEdit::Paste {
+                origin: hit.block + hit.normal,
+                quarter_turns: selection.paste_turns,
+                skip_air: true,
+            }));
+        }
+    }
+
+    let Some(region) = selection.region() else { return; };
+
+    if actions.just_pressed(Action::Fill) {
+        ev.write(RunVoxelEdit(VoxelEdit::Fill { region, block: place.0 }));
+    }
+    if actions.just_pressed(Action::Hollow) {
+        ev.write(RunVoxelEdit(VoxelEdit::Hollow { region, block: place.0 }));
+    }
+    if actions.just_pressed(Action::Replace)
+        && let Some(hit) = hit
+    {
+        let from = blocks.get(hit.block);
+        if from != Block::Air {
+            ev.write(RunVoxelEdit(VoxelEdit::Replace { region, from, to: place.0 }));
+        }
+    }
+    if actions.just_pressed(Action::Copy) {
+        ev.write(RunVoxelEdit(VoxelEdit::Copy { region }));
+    }
+}
+
+/// Auswahl als Drahtbox, einzelne Ecken als kleine Würfel.
+pub fn draw_selection_gizmos(selection: Res<Selection>, mut gizmos: Gizmos) {
+    if let Some(region) = selection.region() {
+        let size = region.size().as_vec3();
+        let center = region.min.as_vec3() + size * 0.5;
+        gizmos.cuboid(Transform::from_translation(center).with_scale(size + 0.02), Color::srgb(1.0, 0.8, 0.1));
+        return;
+    }
+
+    for corner in [selection.a, selection.b].into_iter().flatten() {
+        gizmos.cuboid(
+            Transform::from_translation(corner.as_vec3() + Vec3::splat(0.5)).with_scale(Vec3::splat(1.02)),
+            Color::srgb(1.0, 0.8, 0.1),
+        );
+    }
+}
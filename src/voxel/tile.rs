@@ -5,6 +5,8 @@ pub const TILE_CONTENT: f32 = 16.0;
 pub const PADDING: f32 = 0.001;
 pub const CELL: f32 = TILE_CONTENT + 2.0 * PADDING; // 32.0
 
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
 pub enum UvRot {
     R0,
     R90,
@@ -41,26 +43,27 @@ pub fn tile_uv((tx, ty): (u32, u32)) -> UvRect {
     UvRect { u0, v0, u1, v1 }
 }
 
-pub fn push_uvs(rect: UvRect, rot: UvRot, uvs: &mut Vec<[f32; 2]>) {
-    let u0 = rect.u0;
-    let u1 = rect.u1;
-    let v0 = rect.v0;
-    let v1 = rect.v1;
-
-    let base = match rot {
+/// Welche Kachel-Ecke (0 = u0/v0, 1 = u1/v1) bekommen die 4 Quad-Vertices.
+/// Der Shader baut daraus mit `tile_uv` die gleichen UVs wie `corner_uv`.
+pub fn uv_corners(rot: UvRot) -> [[u32; 2]; 4] {
+    match rot {
         UvRot::R0 => [
-            [u0,v0], [u1,v0], [u1,v1], [u0,v1],
+            [0,0], [1,0], [1,1], [0,1],
         ],
         UvRot::R90 => [
-            [u0,v1], [u0,v0], [u1,v0], [u1,v1],
+            [0,1], [0,0], [1,0], [1,1],
         ],
         UvRot::R180 => [
-            [u1,v1], [u0,v1], [u0,v0], [u1,v0],
+            [1,1], [0,1], [0,0], [1,0],
         ],
         UvRot::R270 => [
-            [u1,v0], [u1,v1], [u0,v1], [u0,v0],
+            [1,0], [1,1], [0,1], [0,0],
         ],
-    };
+    }
+}
 
-    uvs.extend_from_slice(&base);
+pub fn corner_uv(rect: UvRect, [cu, cv]: [u32; 2]) -> [f32; 2] {
+    let u = if cu == 0 { rect.u0 } else { rect.u1 };
+    let v = if cv == 0 { rect.v0 } else { rect.v1 };
+    [u, v]
 }
\ No newline at end of file
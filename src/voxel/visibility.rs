@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use std::collections::{HashSet, VecDeque};
+
+use super::chunk::{Block, CHUNK_SIZE, ChunkData, ChunkPos, world_to_chunk_pos};
+use super::chunk_stream::ChunkStreamConfig;
+use super::plugin::VoxelWorld;
+
+/// Die 6 Chunk-Seiten, Index = Face.
+/// Gegenüberliegende Seite ist immer `i ^ 1`.
+const FACE_DIRS: [IVec3; 6] = [
+    IVec3::X,
+    IVec3::NEG_X,
+    IVec3::Y,
+    IVec3::NEG_Y,
+    IVec3::Z,
+    IVec3::NEG_Z,
+];
+
+/// Cave-Culling: welche Seiten des Chunks sind über Luft miteinander verbunden.
+/// Bit `a * 6 + b` gesetzt -> man kommt durch Seite a rein und durch Seite b wieder raus.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChunkVisibilityGraph(pub u64);
+
+impl ChunkVisibilityGraph {
+    pub const ALL: Self = Self((1 << 36) - 1);
+
+    #[inline]
+    pub fn connected(self, a: usize, b: usize) -> bool {
+        self.0 & (1 << (a * 6 + b)) != 0
+    }
+
+    fn connect_all(&mut self, faces: u8) {
+        for a in 0..6 {
+            if faces & (1 << a) == 0 { continue; }
+            for b in 0..6 {
+                if faces & (1 << b) != 0 {
+                    self.0 |= 1 << (a * 6 + b);
+                }
+            }
+        }
+    }
+}
+
+/// Flood-Fill über alle Luftzellen des Chunks.
+/// Jede zusammenhängende Luftregion verbindet alle Seiten, die sie berührt.
+pub fn compute_visibility_graph(data: &ChunkData) -> ChunkVisibilityGraph {
+    let size = (CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z) as usize;
+    let mut visited = vec![false; size];
+    let mut graph = ChunkVisibilityGraph::default();
+    let mut stack: Vec<IVec3> = Vec::new();
+
+    for z in 0..CHUNK_SIZE.z {
+        for y in 0..CHUNK_SIZE.y {
+            for x in 0..CHUNK_SIZE.x {
+                let i = ChunkData::idx(x, y, z);
+                if visited[i] || data.blocks[i] != Block::Air {
+                    continue;
+                }
+
+                visited[i] = true;
+                stack.push(IVec3::new(x, y, z));
+                let mut faces = 0u8;
+
+                while let Some(p) = stack.pop() {
+                    faces |= touched_faces(p);
+
+                    for d in FACE_DIRS {
+                        let n = p + d;
+                        if n.cmplt(IVec3::ZERO).any() || n.cmpge(CHUNK_SIZE).any() {
+                            continue;
+                        }
+                        let ni = ChunkData::idx(n.x, n.y, n.z);
+                        if !visited[ni] && data.blocks[ni] == Block::Air {
+                            visited[ni] = true;
+                            stack.push(n);
+                        }
+                    }
+                }
+
+                graph.connect_all(faces);
+                if graph == ChunkVisibilityGraph::ALL {
+                    return graph;
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+/// Welche Chunk-Seiten berührt die Zelle `p` (als Bitmaske über Face-Indizes).
+fn touched_faces(p: IVec3) -> u8 {
+    let max = CHUNK_SIZE - IVec3::ONE;
+    let mut faces = 0;
+    if p.x == max.x { faces |= 1 << 0; }
+    if p.x == 0 { faces |= 1 << 1; }
+    if p.y == max.y { faces |= 1 << 2; }
+    if p.y == 0 { faces |= 1 << 3; }
+    if p.z == max.z { faces |= 1 << 4; }
+    if p.z == 0 { faces |= 1 << 5; }
+    faces
+}
+
+/// BFS vom Kamera-Chunk aus über die Sichtbarkeitsgraphen.
+/// Ein Chunk ist nur sichtbar, wenn man ihn durch Luft erreicht, ohne dabei
+/// jemals zurück Richtung Kamera zu laufen. Alles andere wird versteckt.
+/// Nicht geladene (oder noch nicht gemeshte) Chunks gelten als komplett offen.
+pub fn chunk_occlusion_culling_system(
+    cam_q: Query<&GlobalTransform, With<Camera3d>>,
+    world: Res<VoxelWorld>,
+    cfg: Res<ChunkStreamConfig>,
+    graphs: Query<&ChunkVisibilityGraph>,
+    mut visibilities: Query<&mut Visibility, With<ChunkPos>>,
+) {
+    let Ok(cam_tf) = cam_q.single() else { return; };
+    let center = world_to_chunk_pos(cam_tf.translation());
+
+    // Suchraum begrenzen: horizontal Sichtweite, vertikal bis zur Kamera
+    let y_lo = (cfg.y_min - 1).min(center.0.y);
+    let y_hi = (cfg.y_max + 1).max(center.0.y);
+    let in_bounds = |p: IVec3| {
+        (p.x - center.0.x).abs() <= cfg.view_radius
+            && (p.z - center.0.z).abs() <= cfg.view_radius
+            && (y_lo..=y_hi).contains(&p.y)
+    };
+
+    let reached = reachable_chunks(center, in_bounds, |pos| {
+        world
+            .chunks
+            .get(&pos)
+            .and_then(|&e| graphs.get(e).ok().copied())
+            .unwrap_or(ChunkVisibilityGraph::ALL)
+    });
+
+    for (pos, &e) in world.chunks.iter() {
+        let Ok(mut vis) = visibilities.get_mut(e) else { continue; };
+        let wanted = if reached.contains(pos) { Visibility::Inherited } else { Visibility::Hidden };
+        // nur schreiben wenn nötig, sonst propagiert Bevy jedes Frame
+        if *vis != wanted {
+            *vis = wanted;
+        }
+    }
+}
+
+/// Die eigentliche BFS: alle Chunks, die von `center` aus sichtbar sein können.
+fn reachable_chunks(
+    center: ChunkPos,
+    in_bounds: impl Fn(IVec3) -> bool,
+    graph_of: impl Fn(ChunkPos) -> ChunkVisibilityGraph,
+) -> HashSet<ChunkPos> {
+    let mut reached: HashSet<ChunkPos> = HashSet::new();
+    // (Chunk, Seite durch die wir reingekommen sind, bisher gelaufene Richtungen)
+    let mut queue: VecDeque<(ChunkPos, Option<usize>, u8)> = VecDeque::new();
+    reached.insert(center);
+    queue.push_back((center, None, 0));
+
+    while let Some((pos, entered, walked)) = queue.pop_front() {
+        let graph = graph_of(pos);
+
+        for (d, dir) in FACE_DIRS.iter().enumerate() {
+            // nie zurück Richtung Kamera
+            if walked & (1 << (d ^ 1)) != 0 {
+                continue;
+            }
+            if let Some(f) = entered && !graph.connected(f, d) {
+                continue;
+            }
+
+            let next = pos.0 + *dir;
+            if !in_bounds(next) {
+                continue;
+            }
+            if reached.insert(ChunkPos(next)) {
+                queue.push_back((ChunkPos(next), Some(d ^ 1), walked | (1 << d)));
+            }
+        }
+    }
+
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voxel::fixtures;
+
+    fn filled(block: impl Fn(i32, i32, i32) -> Block) -> ChunkData {
+        let mut blocks = Vec::with_capacity((CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z) as usize);
+        for z in 0..CHUNK_SIZE.z {
+            for y in 0..CHUNK_SIZE.y {
+                for x in 0..CHUNK_SIZE.x {
+                    blocks.push(block(x, y, z));
+                }
+            }
+        }
+        ChunkData { blocks }
+    }
+
+    #[test]
+    fn solid_and_empty_chunks() {
+        assert_eq!(compute_visibility_graph(&filled(|_, _, _| Block::Stone)), ChunkVisibilityGraph::default());
+        assert_eq!(compute_visibility_graph(&filled(|_, _, _| Block::Air)), ChunkVisibilityGraph::ALL);
+        // eine Lage Gras bei y = 0 schließt nur die Unterseite (3)
+        let g = compute_visibility_graph(&fixtures::flat_plane());
+        assert!((0..6).all(|s| !g.connected(3, s) && !g.connected(s, 3)));
+        assert!(g.connected(2, 0) && g.connected(1, 4));
+    }
+
+    #[test]
+    fn wall_splits_the_chunk() {
+        let g = compute_visibility_graph(&filled(|x, _, _| if x == 8 { Block::Stone } else { Block::Air }));
+        // +X (0) und -X (1) sind getrennt, beide Hälften berühren aber oben/unten/vorne/hinten
+        assert!(!g.connected(0, 1));
+        assert!(!g.connected(1, 0));
+        for side in 2..6 {
+            assert!(g.connected(0, side) && g.connected(1, side), "Seite {side}");
+        }
+        assert!(g.connected(2, 3));
+    }
+
+    #[test]
+    fn bfs_hides_chunks_behind_solid_ones() {
+        // eine Reihe Chunks entlang X, Kamera in 0, Chunk 1 ist massiv
+        let in_row = |p: IVec3| p.y == 0 && p.z == 0 && (-3..=3).contains(&p.x);
+        let solid_at_1 = |pos: ChunkPos| {
+            if pos.0.x == 1 { ChunkVisibilityGraph::default() } else { ChunkVisibilityGraph::ALL }
+        };
+
+        let reached = reachable_chunks(ChunkPos(IVec3::ZERO), in_row, solid_at_1);
+        // der massive Chunk selbst ist sichtbar, alles dahinter nicht
+        assert!(reached.contains(&ChunkPos(IVec3::X)));
+        assert!(!reached.contains(&ChunkPos(IVec3::new(2, 0, 0))));
+        assert!(!reached.contains(&ChunkPos(IVec3::new(3, 0, 0))));
+        assert!(reached.contains(&ChunkPos(IVec3::new(-3, 0, 0))));
+
+        // auch nicht über einen Umweg, der zurück Richtung Kamera laufen müsste
+        let in_slab = |p: IVec3| p.y == 0 && (-3..=3).contains(&p.x) && (-1..=1).contains(&p.z);
+        let reached = reachable_chunks(ChunkPos(IVec3::ZERO), in_slab, solid_at_1);
+        assert!(!reached.contains(&ChunkPos(IVec3::new(2, 0, 0))));
+
+        // offen -> alles erreichbar
+        let reached = reachable_chunks(ChunkPos(IVec3::ZERO), in_row, |_| ChunkVisibilityGraph::ALL);
+        assert_eq!(reached.len(), 7);
+    }
+}
@@ -0,0 +1,384 @@
+//! MagicaVoxel `.vox`: Import in die Zwischenablage (zum Einfügen) und Export
+//! eines Weltbereichs. Farben <-> Blöcke über `assets/vox_palette.ron`.
+//!
+//! MagicaVoxel ist Z-oben, wir Y-oben: vox (x, y, z) = welt (x, size.z-1-z, y).
+//! Das ist eine Drehung (keine Spiegelung), Modelle sehen also gleich aus.
+//!
+//! Unterstützt wird nur das erste Modell einer Datei (SIZE/XYZI) plus RGBA-Palette;
+//! Szenengraph, Materialien usw. werden übersprungen.
+
+use anyhow::{Context, bail};
+use bevy::math::I64Vec3;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+use crate::config::VoxPalette;
+use crate::input::{Action, ActionState};
+
+use super::chunk::Block;
+use super::edit::WorldBlocks;
+use super::edit_ops::{Clipboard, Region};
+use super::selection::Selection;
+
+const VOX_VERSION: u32 = 150;
+/// Größe pro Achse im Format (Koordinaten sind u8)
+const VOX_MAX_SIZE: i32 = 256;
+
+/// `.vox` lesen; das Ergebnis kann direkt eingefügt werden.
+pub fn read_vox(bytes: &[u8], palette: &VoxPalette) -> anyhow::Result<Clipboard> {
+    let mut r = Reader { bytes, pos: 0 };
+    if r.take(4)? != b"VOX " {
+        bail!("keine .vox-Datei");
+    }
+    let _version = r.u32()?;
+
+    let (id, content, children) = r.chunk_header()?;
+    if id != *b"MAIN" {
+        bail!("MAIN-Chunk fehlt");
+    }
+    r.take(content)?;
+    let end = r.pos + children;
+
+    let mut size: Option<[i32; 3]> = None;
+    let mut voxels: Option<Vec<[u8; 4]>> = None;
+    let mut rgba: Option<Vec<[u8; 4]>> = None;
+
+    while r.pos < end {
+        let (id, content, children) = r.chunk_header()?;
+        let body = r.take(content)?;
+        r.take(children)?;
+        let mut c = Reader { bytes: body, pos: 0 };
+
+        match &id {
+            b"SIZE" if size.is_none() => {
+                let s = [c.i32()?, c.i32()?, c.i32()?];
+                if s.iter().any(|v| !(1..=VOX_MAX_SIZE).contains(v)) {
+                    bail!("ungültige Modellgröße {s:?} (1..={VOX_MAX_SIZE} pro Achse)");
+                }
+                size = Some(s);
+            }
+            b"XYZI" if voxels.is_none() => {
+                let n = c.u32()? as usize;
+                // Anzahl kommt aus der Datei: erst prüfen, dann reservieren
+                if n.checked_mul(4).is_none_or(|len| len > c.remaining()) {
+                    bail!("XYZI: {n} Voxel passen nicht in {} Bytes", c.remaining());
+                }
+                let mut v = Vec::with_capacity(n);
+                for _ in 0..n {
+                    let b = c.take(4)?;
+                    v.push([b[0], b[1], b[2], b[3]]);
+                }
+                voxels = Some(v);
+            }
+            b"RGBA" => {
+                let mut p = Vec::with_capacity(256);
+                for _ in 0..256 {
+                    let b = c.take(4)?;
+                    p.push([b[0], b[1], b[2], b[3]]);
+                }
+                rgba = Some(p);
+            }
+            _ => {}
+        }
+    }
+
+    let [sx, sy, sz] = size.context("SIZE-Chunk fehlt")?;
+    let voxels = voxels.context("XYZI-Chunk fehlt")?;
+    // ohne RGBA gilt die eingebaute MagicaVoxel-Standardpalette - die haben wir nicht
+    let rgba = rgba.context("RGBA-Palette fehlt (Standardpalette wird nicht unterstützt)")?;
+
+    // Farbindex -> Block einmal vorab
+    let mut by_index = [Block::Air; 256];
+    for (i, slot) in by_index.iter_mut().enumerate().skip(1) {
+        let [r, g, b, _] = rgba[i - 1];
+        *slot = palette.nearest((r, g, b)).context("Farbtabelle ist leer")?;
+    }
+
+    let size = IVec3::new(sx, sz, sy);
+    let mut clip = Clipboard { size, blocks: vec![Block::Air; size.element_product() as usize] };
+    for [x, y, z, ci] in voxels {
+        let p = IVec3::new(x as i32, z as i32, sy - 1 - y as i32);
+        if !Region::new(IVec3::ZERO, size - IVec3::ONE).contains(p) {
+            bail!("Voxel ({x}, {y}, {z}) liegt außerhalb des Modells");
+        }
+        let i = clip.index(p);
+        clip.blocks[i] = by_index[ci as usize];
+    }
+
+    Ok(clip)
+}
+
+/// Weltbereich lesen und als `.vox` kodieren. Die Auswahl kann beliebig groß
+/// sein, zu große Bereiche werden daher abgelehnt, bevor ein Block gelesen wird.
+pub fn export_vox(region: Region, get: impl Fn(IVec3) -> Block, palette: &VoxPalette) -> anyhow::Result<Vec<u8>> {
+    // in i64: bei riesigen Auswahlen läuft `size()` über
+    let size = region.max.as_i64vec3() - region.min.as_i64vec3() + I64Vec3::ONE;
+    if size.max_element() > VOX_MAX_SIZE as i64 {
+        bail!("Bereich {size} ist größer als {VOX_MAX_SIZE} pro Achse");
+    }
+    write_vox(&Clipboard::copy(region, get), palette)
+}
+
+/// Als `.vox` schreiben; Luft bleibt leer.
+pub fn write_vox(clip: &Clipboard, palette: &VoxPalette) -> anyhow::Result<Vec<u8>> {
+    if clip.size.max_element() > VOX_MAX_SIZE {
+        bail!("Bereich {} ist größer als {VOX_MAX_SIZE} pro Achse", clip.size);
+    }
+    if palette.colors.len() > 255 {
+        bail!("Farbtabelle hat mehr als 255 Einträge");
+    }
+
+    let mut voxels = Vec::new();
+    for p in Region::new(IVec3::ZERO, clip.size - IVec3::ONE).iter() {
+        let block = clip.get(p);
+        if block == Block::Air {
+            continue;
+        }
+        let slot = palette
+            .slot_of(block)
+            .with_context(|| format!("{block:?} fehlt in der Farbtabelle"))?;
+        let (x, y, z) = (p.x, clip.size.z - 1 - p.z, p.y);
+        voxels.extend_from_slice(&[x as u8, y as u8, z as u8, (slot + 1) as u8]);
+    }
+
+    let mut size = Vec::new();
+    for v in [clip.size.x, clip.size.z, clip.size.y] {
+        size.extend_from_slice(&v.to_le_bytes());
+    }
+
+    let mut xyzi = ((voxels.len() / 4) as u32).to_le_bytes().to_vec();
+    xyzi.extend_from_slice(&voxels);
+
+    let mut rgba = vec![0u8; 256 * 4];
+    for (i, c) in palette.colors.iter().enumerate() {
+        rgba[i * 4..i * 4 + 4].copy_from_slice(&[c.rgb.0, c.rgb.1, c.rgb.2, 255]);
+    }
+
+    let mut children = Vec::new();
+    write_chunk(&mut children, b"SIZE", &size, &[]);
+    write_chunk(&mut children, b"XYZI", &xyzi, &[]);
+    write_chunk(&mut children, b"RGBA", &rgba, &[]);
+
+    let mut out = b"VOX ".to_vec();
+    out.extend_from_slice(&VOX_VERSION.to_le_bytes());
+    write_chunk(&mut out, b"MAIN", &[], &children);
+    Ok(out)
+}
+
+fn write_chunk(out: &mut Vec<u8>, id: &[u8; 4], content: &[u8], children: &[u8]) {
+    out.extend_from_slice(id);
+    out.extend_from_slice(&(content.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(children.len() as u32).to_le_bytes());
+    out.extend_from_slice(content);
+    out.extend_from_slice(children);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|&e| e <= self.bytes.len());
+        let Some(end) = end else { bail!("Datei zu kurz") };
+        let s = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(s)
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn i32(&mut self) -> anyhow::Result<i32> {
+        Ok(self.u32()? as i32)
+    }
+
+    fn chunk_header(&mut self) -> anyhow::Result<([u8; 4], usize, usize)> {
+        let id = self.take(4)?;
+        let content = self.u32()? as usize;
+        let children = self.u32()? as usize;
+        Ok(([id[0], id[1], id[2], id[3]], content, children))
+    }
+}
+
+/// `.vox` in die Zwischenablage laden; danach mit Strg+V einfügen.
+#[derive(Message, Clone, Debug)]
+pub struct ImportVox(pub PathBuf);
+
+/// Weltbereich als `.vox` speichern (nicht geladene Chunks sind Luft).
+#[derive(Message, Clone, Debug)]
+pub struct ExportVox {
+    pub path: PathBuf,
+    pub region: Region,
+}
+
+/// Strg+E / Strg+I: Auswahl exportieren / wieder importieren.
+const VOX_QUICK_PATH: &str = "exports/selection.vox";
+
+pub fn vox_keys_system(
+    actions: Res<ActionState>,
+    selection: Res<Selection>,
+    mut import: MessageWriter<ImportVox>,
+    mut export: MessageWriter<ExportVox>,
+) {
+    if actions.just_pressed(Action::ExportVox)
+        && let Some(region) = selection.region()
+    {
+        export.write(ExportVox { path: VOX_QUICK_PATH.into(), region });
+    }
+    if actions.just_pressed(Action::ImportVox) {
+        import.write(ImportVox(VOX_QUICK_PATH.into()));
+    }
+}
+
+pub fn vox_io_system(
+    mut imports: MessageReader<ImportVox>,
+    mut exports: MessageReader<ExportVox>,
+    blocks: WorldBlocks,
+    mut clipboard: ResMut<Clipboard>,
+) {
+    if imports.is_empty() && exports.is_empty() {
+        return;
+    }
+    let palette = match VoxPalette::load(VoxPalette::PATH) {
+        Ok(p) => p,
+        Err(e) => {
+            error!("{}: {e:#}", VoxPalette::PATH);
+            imports.clear();
+            exports.clear();
+            return;
+        }
+    };
+
+    for ImportVox(path) in imports.read() {
+        let result = std::fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| read_vox(&bytes, &palette));
+        match result {
+            Ok(clip) => {
+                info!("{} importiert ({}), mit Strg+V einfügen", path.display(), clip.size);
+                *clipboard = clip;
+            }
+            Err(e) => error!("{} importieren: {e:#}", path.display()),
+        }
+    }
+
+    for ExportVox { path, region } in exports.read() {
+        let result = export_vox(*region, |p| blocks.get(p), &palette).and_then(|bytes| {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            Ok(std::fs::write(path, bytes)?)
+        });
+        match result {
+            Ok(()) => info!("{} exportiert ({})", path.display(), region.size()),
+            Err(e) => error!("{} exportieren: {e:#}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voxel::chunk::CHUNK_SIZE;
+    use crate::voxel::fixtures;
+
+    fn palette() -> VoxPalette {
+        ron::from_str(include_str!("../../assets/vox_palette.ron")).expect("assets/vox_palette.ron")
+    }
+
+    fn chunk_clip(blocks: Vec<Block>) -> Clipboard {
+        Clipboard { size: CHUNK_SIZE, blocks }
+    }
+
+    #[test]
+    fn vox_round_trip_chunks() {
+        let palette = palette();
+        for data in [fixtures::flat_plane(), fixtures::pillar(), fixtures::checkerboard(), fixtures::random(11)] {
+            let clip = chunk_clip(data.blocks);
+            let back = read_vox(&write_vox(&clip, &palette).unwrap(), &palette).unwrap();
+            assert_eq!(back, clip);
+        }
+
+        // alle Blocktypen, nicht-würfelförmig
+        let size = IVec3::new(3, 5, 2);
+        let blocks = (0..size.element_product() as usize).map(|i| Block::ALL[i % Block::ALL.len()]).collect();
+        let clip = Clipboard { size, blocks };
+        let back = read_vox(&write_vox(&clip, &palette).unwrap(), &palette).unwrap();
+        assert_eq!(back, clip);
+    }
+
+    #[test]
+    fn vox_is_z_up() {
+        let palette = palette();
+        // eine Säule nach oben (Y) muss in MagicaVoxel entlang Z laufen
+        let clip = Clipboard { size: IVec3::new(1, 3, 1), blocks: vec![Block::Stone; 3] };
+        let bytes = write_vox(&clip, &palette).unwrap();
+        // Header (8) + MAIN-Header (12) + SIZE-Header (12) -> x, y, z
+        let size: Vec<i32> = bytes[32..44].chunks(4).map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+        assert_eq!(size, vec![1, 1, 3]);
+    }
+
+    #[test]
+    fn vox_rejects_garbage() {
+        let palette = palette();
+        assert!(read_vox(b"nope", &palette).is_err());
+        let bytes = write_vox(&chunk_clip(fixtures::pillar().blocks), &palette).unwrap();
+        assert!(read_vox(&bytes[..bytes.len() - 100], &palette).is_err());
+    }
+
+    #[test]
+    fn oversized_export_is_refused_before_reading() {
+        let palette = palette();
+        let reads = std::cell::Cell::new(0);
+        let get = |_| {
+            reads.set(reads.get() + 1);
+            Block::Stone
+        };
+
+        // Ecke A gesetzt, 1000 Blöcke weiter Ecke B
+        let far = Region::new(IVec3::ZERO, IVec3::new(1000, 3, 3));
+        assert!(export_vox(far, get, &palette).is_err());
+        let huge = Region::new(IVec3::MIN, IVec3::MAX);
+        assert!(export_vox(huge, get, &palette).is_err());
+        assert_eq!(reads.get(), 0);
+
+        let ok = Region::new(IVec3::ZERO, IVec3::new(VOX_MAX_SIZE - 1, 0, 0));
+        assert!(export_vox(ok, get, &palette).is_ok());
+        assert_eq!(reads.get(), VOX_MAX_SIZE);
+    }
+
+    /// Datei aus SIZE-Werten und rohem XYZI-Inhalt, wie `write_vox` sie baut.
+    fn vox_file(size: [i32; 3], xyzi: &[u8]) -> Vec<u8> {
+        let size: Vec<u8> = size.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let mut children = Vec::new();
+        write_chunk(&mut children, b"SIZE", &size, &[]);
+        write_chunk(&mut children, b"XYZI", xyzi, &[]);
+        write_chunk(&mut children, b"RGBA", &[0; 256 * 4], &[]);
+        let mut out = b"VOX ".to_vec();
+        out.extend_from_slice(&VOX_VERSION.to_le_bytes());
+        write_chunk(&mut out, b"MAIN", &[], &children);
+        out
+    }
+
+    #[test]
+    fn vox_rejects_untrusted_sizes() {
+        let palette = palette();
+        let one_voxel = [1u32.to_le_bytes().as_slice(), &[0, 0, 0, 1]].concat();
+        assert!(read_vox(&vox_file([1, 1, 1], &one_voxel), &palette).is_ok());
+
+        for size in [[0, 1, 1], [1, -4, 1], [257, 1, 1], [i32::MAX, i32::MAX, i32::MAX]] {
+            assert!(read_vox(&vox_file(size, &one_voxel), &palette).is_err(), "{size:?}");
+        }
+        // behauptet 4 Milliarden Voxel, hat aber einen
+        let lying = [u32::MAX.to_le_bytes().as_slice(), &[0, 0, 0, 1]].concat();
+        assert!(read_vox(&vox_file([1, 1, 1], &lying), &palette).is_err());
+    }
+}
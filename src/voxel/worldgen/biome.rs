@@ -0,0 +1,156 @@
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+
+use crate::config::{BiomeDef, WorldGenConfig};
+
+/// Ergebnis pro Säule (x,z).
+#[derive(Clone, Copy, Debug)]
+pub struct Column {
+    /// Index in `BiomeMap::biomes`, das Biom mit dem nächsten Klimapunkt
+    pub biome: usize,
+    /// Welt-Y des obersten soliden Blocks
+    pub height: i32,
+}
+
+/// Temperatur, Feuchte und Höhe aus 2D-Noise.
+/// Die Höhe wird über alle Biome in der Nähe (im Klimaraum) gemischt,
+/// damit es an Biomgrenzen keine Stufen gibt; der Oberflächenblock kommt
+/// dagegen immer vom nächsten Biom.
+pub struct BiomeMap {
+    temperature: Fbm<Perlin>,
+    humidity: Fbm<Perlin>,
+    height: Fbm<Perlin>,
+    blend: f32,
+    biomes: Vec<BiomeDef>,
+}
+
+impl BiomeMap {
+    pub fn new(seed: u32, cfg: &WorldGenConfig) -> Self {
+        assert!(!cfg.biomes.is_empty(), "worldgen.ron: keine Biome");
+
+        let climate = |s: u32| {
+            Fbm::<Perlin>::new(s)
+                .set_octaves(3)
+                .set_frequency(1.0 / cfg.climate.scale)
+        };
+
+        Self {
+            temperature: climate(seed),
+            humidity: climate(seed.wrapping_add(1)),
+            height: Fbm::<Perlin>::new(seed.wrapping_add(2))
+                .set_octaves(4)
+                .set_frequency(1.0 / cfg.climate.height_scale),
+            blend: cfg.climate.blend,
+            biomes: cfg.biomes.clone(),
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.biomes.iter().position(|b| b.name == name)
+    }
+
+    pub fn biome(&self, i: usize) -> &BiomeDef {
+        &self.biomes[i]
+    }
+
+    /// (Temperatur, Feuchte), jeweils 0..1
+    pub fn climate(&self, x: i32, z: i32) -> (f32, f32) {
+        let p = [x as f64, z as f64];
+        (to_unit(self.temperature.get(p)), to_unit(self.humidity.get(p)))
+    }
+
+    pub fn column(&self, x: i32, z: i32) -> Column {
+        let (t, h) = self.climate(x, z);
+        let relief = to_unit(self.height.get([x as f64, z as f64]));
+
+        let dist: Vec<f32> = self
+            .biomes
+            .iter()
+            .map(|b| ((b.temperature - t).powi(2) + (b.humidity - h).powi(2)).sqrt())
+            .collect();
+
+        let (nearest, &d0) = dist
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+
+        // Gewicht fällt mit dem Abstand zum nächsten Biom auf 0 ab (nach `blend`).
+        // d0 ändert sich stetig, also auch die Gewichte -> keine Kanten in der Höhe.
+        let mut sum_w = 0.0;
+        let mut sum_h = 0.0;
+        for (b, &d) in self.biomes.iter().zip(&dist) {
+            let w = if self.blend > 0.0 {
+                (1.0 - (d - d0) / self.blend).max(0.0).powi(2)
+            } else if d == d0 {
+                1.0
+            } else {
+                0.0
+            };
+            if w == 0.0 {
+                continue;
+            }
+            let (lo, hi) = b.height;
+            sum_w += w;
+            sum_h += w * (lo as f32 + (hi - lo) as f32 * relief);
+        }
+
+        Column { biome: nearest, height: (sum_h / sum_w).round() as i32 }
+    }
+}
+
+/// Fbm liefert grob -1..1, meistens aber nur ±0.5 -> etwas strecken.
+fn to_unit(n: f64) -> f32 {
+    (n as f32 * 0.9 + 0.5).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voxel::chunk::CHUNK_SIZE;
+    use crate::voxel::fixtures;
+
+    #[test]
+    fn columns_are_stable_per_seed() {
+        let cfg = fixtures::worldgen_config();
+        let (a, b) = (BiomeMap::new(7, &cfg), BiomeMap::new(7, &cfg));
+        let other = BiomeMap::new(8, &cfg);
+
+        let mut differs = false;
+        for z in (-300..300).step_by(37) {
+            for x in (-300..300).step_by(41) {
+                let (ca, cb) = (a.column(x, z), b.column(x, z));
+                assert_eq!((ca.biome, ca.height), (cb.biome, cb.height), "({x}, {z})");
+                // nochmal fragen ändert nichts
+                assert_eq!(a.column(x, z).height, ca.height);
+                differs |= other.column(x, z).height != ca.height;
+            }
+        }
+        assert!(differs, "anderer Seed, gleiche Welt");
+    }
+
+    /// Über Chunkgrenzen springt die Oberfläche nicht stärker als innerhalb
+    /// eines Chunks, auch dort, wo Biome ineinander übergehen.
+    #[test]
+    fn surface_is_continuous_across_chunk_borders() {
+        let cfg = fixtures::worldgen_config();
+        let map = BiomeMap::new(3, &cfg);
+        let size = CHUNK_SIZE.x;
+
+        let mut border_step = 0;
+        let mut inner_step = 0;
+        let mut biome_changes = 0;
+        for z in -1024..1024 {
+            for x in (-1024..1024).step_by(size as usize) {
+                // x - 1 | x liegt auf einer Chunkgrenze, x | x + 1 im Chunk
+                let (l, r, n) = (map.column(x - 1, z), map.column(x, z), map.column(x + 1, z));
+                border_step = border_step.max((l.height - r.height).abs());
+                inner_step = inner_step.max((r.height - n.height).abs());
+                biome_changes += (l.biome != r.biome) as u32;
+            }
+        }
+
+        assert!(biome_changes > 0, "kein Biomwechsel im Testgebiet");
+        assert!(border_step <= 2, "Stufe an der Chunkgrenze: {border_step}");
+        assert!(border_step <= inner_step.max(1), "Grenze {border_step}, innen {inner_step}");
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+
+use crate::config::CaveSettings;
+use crate::voxel::chunk::{Block, CHUNK_SIZE, ChunkData};
+
+use super::Column;
+
+/// Höhlen-Pass: höhlt festes Gelände über 3D-Noise aus.
+/// - Käsehöhlen: große Kammern, wo ein Fbm-Noise über der Schwelle liegt
+/// - Tunnel: wo zwei unabhängige Noise-Felder beide nahe 0 sind, entstehen
+///   lange, gewundene Röhren (Schnittlinie zweier Flächen)
+///
+/// Hängt nur von Weltkoordinaten ab, dadurch passen Höhlen über Chunkgrenzen.
+pub struct CaveCarver {
+    cheese: Fbm<Perlin>,
+    tunnel_a: Perlin,
+    tunnel_b: Perlin,
+    settings: CaveSettings,
+}
+
+impl CaveCarver {
+    pub fn new(seed: u32, settings: &CaveSettings) -> Self {
+        Self {
+            cheese: Fbm::<Perlin>::new(seed.wrapping_add(10))
+                .set_octaves(2)
+                .set_frequency(1.0 / settings.cheese_scale),
+            tunnel_a: Perlin::new(seed.wrapping_add(11)),
+            tunnel_b: Perlin::new(seed.wrapping_add(12)),
+            settings: settings.clone(),
+        }
+    }
+
+    pub fn is_cave(&self, x: i32, y: i32, z: i32) -> bool {
+        let (fx, fy, fz) = (x as f64, y as f64, z as f64);
+
+        // Kammern flacher als hoch: Y doppelt so schnell
+        let c = self.cheese.get([fx, fy * 2.0, fz]) * 0.5 + 0.5;
+        if c > self.settings.cheese_threshold {
+            return true;
+        }
+
+        let f = 1.0 / self.settings.tunnel_scale;
+        let p = [fx * f, fy * f * 1.5, fz * f];
+        let a = self.tunnel_a.get(p);
+        let b = self.tunnel_b.get(p);
+        a * a + b * b < self.settings.tunnel_width * self.settings.tunnel_width
+    }
+
+    pub fn carve(&self, data: &mut ChunkData, origin: IVec3, columns: &[Column]) {
+        for z in 0..CHUNK_SIZE.z {
+            for x in 0..CHUNK_SIZE.x {
+                let top = columns[(x + CHUNK_SIZE.x * z) as usize].height - self.settings.surface_margin;
+                for y in 0..CHUNK_SIZE.y {
+                    let wy = origin.y + y;
+                    if wy > top {
+                        break;
+                    }
+                    let i = ChunkData::idx(x, y, z);
+                    if data.blocks[i] != Block::Air && self.is_cave(origin.x + x, wy, origin.z + z) {
+                        data.blocks[i] = Block::Air;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voxel::chunk::{Block, CHUNK_SIZE, ChunkPos, chunk_origin_world};
+    use crate::voxel::fixtures;
+    use crate::voxel::worldgen::WorldGenerator;
+    use bevy::prelude::*;
+
+    #[test]
+    fn same_seed_same_chunk() {
+        let cfg = fixtures::worldgen_config();
+        let (a, b) = (WorldGenerator::new(42, &cfg), WorldGenerator::new(42, &cfg));
+        // Oberfläche, Höhlen und Erze (tiefe Chunks)
+        for pos in [IVec3::new(0, 0, 0), IVec3::new(-3, 1, 5), IVec3::new(2, -2, -1), IVec3::new(7, -4, 3)] {
+            let (da, db) = (a.generate_chunk(ChunkPos(pos)), b.generate_chunk(ChunkPos(pos)));
+            assert!(da.blocks == db.blocks, "Chunk {pos}");
+            // zweiter Aufruf am selben Generator (Struktur-Cache ist jetzt warm)
+            assert!(a.generate_chunk(ChunkPos(pos)).blocks == da.blocks, "Chunk {pos}, 2. Mal");
+        }
+    }
+
+    /// Beide Chunks an einer gemeinsamen Seite höhlen genau dort aus, wo
+    /// `is_cave` in Weltkoordinaten es sagt, und Tunnel laufen durch die Seite durch.
+    #[test]
+    fn caves_match_across_chunk_face() {
+        let cfg = fixtures::worldgen_config();
+        let generator = WorldGenerator::new(5, &cfg);
+        let carver = &generator.caves;
+        let last = CHUNK_SIZE.x - 1;
+
+        let mut crossings = 0;
+        // tief genug, dass alles fester Stein wäre (Oberfläche >= 6)
+        for y in -4..=-2 {
+            for z in -4..4 {
+                let a = ChunkPos(IVec3::new(0, y, z));
+                let b = ChunkPos(IVec3::new(1, y, z));
+                let (da, db) = (generator.generate_chunk(a), generator.generate_chunk(b));
+                let origin = chunk_origin_world(a).as_ivec3();
+
+                for ly in 0..CHUNK_SIZE.y {
+                    for lz in 0..CHUNK_SIZE.z {
+                        let (wy, wz) = (origin.y + ly, origin.z + lz);
+                        let air_a = da.get_local(last, ly, lz) == Block::Air;
+                        let air_b = db.get_local(0, ly, lz) == Block::Air;
+                        assert_eq!(air_a, carver.is_cave(last, wy, wz), "A ({last}, {wy}, {wz})");
+                        assert_eq!(air_b, carver.is_cave(last + 1, wy, wz), "B ({}, {wy}, {wz})", last + 1);
+                        crossings += (air_a && air_b) as u32;
+                    }
+                }
+            }
+        }
+        assert!(crossings > 0, "keine Höhle geht durch die Seite");
+    }
+}
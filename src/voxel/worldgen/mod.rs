@@ -0,0 +1,118 @@
+//! Terrain-Generierung: aus Seed + Chunkposition deterministisch die Blöcke.
+//! Gleicher Seed -> gleiche Welt, und benachbarte Chunks passen ohne Naht zusammen,
+//! weil jeder Pass nur von Weltkoordinaten bzw. (Seed, Chunk) abhängt.
+//!
+//! Passes, in dieser Reihenfolge:
+//! 1. Grundgelände: Biom-Höhe -> Oberfläche, Füllung, Stein
+//! 2. Höhlen (`caves`)
+//! 3. Erzadern (`ores`)
+//! 4. Strukturen wie Bäume und Hütten (`structures`)
+
+mod biome;
+mod caves;
+mod ores;
+mod rng;
+mod structures;
+
+use bevy::prelude::*;
+
+use crate::config::{OreRule, WorldGenConfig, WorldGenConfigRes};
+
+use super::chunk::{Block, CHUNK_SIZE, ChunkData, ChunkPos, chunk_origin_world};
+
+pub use biome::{BiomeMap, Column};
+pub use caves::CaveCarver;
+pub use rng::{Rng, hash_seed};
+pub use structures::StructurePlacer;
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct WorldSeed(pub u32);
+
+impl Default for WorldSeed {
+    fn default() -> Self {
+        Self(1337)
+    }
+}
+
+#[derive(Resource)]
+pub struct WorldGenerator {
+    seed: u32,
+    biomes: BiomeMap,
+    caves: CaveCarver,
+    ores: Vec<OreRule>,
+    structures: StructurePlacer,
+}
+
+impl WorldGenerator {
+    pub fn new(seed: u32, cfg: &WorldGenConfig) -> Self {
+        let biomes = BiomeMap::new(seed, cfg);
+        Self {
+            seed,
+            caves: CaveCarver::new(seed, &cfg.caves),
+            ores: cfg.ores.clone(),
+            structures: StructurePlacer::new(seed, cfg, &biomes),
+            biomes,
+        }
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn biomes(&self) -> &BiomeMap {
+        &self.biomes
+    }
+
+    pub fn generate_chunk(&self, pos: ChunkPos) -> ChunkData {
+        let origin = chunk_origin_world(pos).as_ivec3();
+
+        // Säulen einmal pro Chunk, werden von mehreren Passes gebraucht
+        let mut columns = Vec::with_capacity((CHUNK_SIZE.x * CHUNK_SIZE.z) as usize);
+        for z in 0..CHUNK_SIZE.z {
+            for x in 0..CHUNK_SIZE.x {
+                columns.push(self.biomes.column(origin.x + x, origin.z + z));
+            }
+        }
+
+        let mut data = self.base_pass(origin, &columns);
+        self.caves.carve(&mut data, origin, &columns);
+        ores::place_ores(self.seed, &self.ores, pos, &mut data);
+        self.structures.place(pos, &mut data, &self.biomes);
+        data
+    }
+
+    /// Grundgelände: alles unter der Biom-Höhe ist fest.
+    fn base_pass(&self, origin: IVec3, columns: &[Column]) -> ChunkData {
+        let mut blocks = vec![Block::Air; (CHUNK_SIZE.x * CHUNK_SIZE.y * CHUNK_SIZE.z) as usize];
+
+        for z in 0..CHUNK_SIZE.z {
+            for x in 0..CHUNK_SIZE.x {
+                let col = columns[(x + CHUNK_SIZE.x * z) as usize];
+                let biome = self.biomes.biome(col.biome);
+
+                for y in 0..CHUNK_SIZE.y {
+                    let depth = col.height - (origin.y + y);
+                    blocks[ChunkData::idx(x, y, z)] = if depth < 0 {
+                        Block::Air
+                    } else if depth == 0 {
+                        biome.surface
+                    } else if depth <= biome.filler_depth {
+                        biome.filler
+                    } else {
+                        Block::Stone
+                    };
+                }
+            }
+        }
+
+        ChunkData { blocks }
+    }
+}
+
+pub fn setup_world_generator(
+    mut commands: Commands,
+    seed: Res<WorldSeed>,
+    cfg: Res<WorldGenConfigRes>,
+) {
+    commands.insert_resource(WorldGenerator::new(seed.0, &cfg.0));
+}
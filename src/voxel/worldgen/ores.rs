@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::config::OreRule;
+use crate::voxel::chunk::{Block, CHUNK_SIZE, ChunkData, ChunkPos, chunk_origin_world};
+
+use super::rng::{Rng, hash_seed};
+
+/// Erz-Pass: Adern als kurze Random Walks durch Stein.
+///
+/// Jede Ader gehört zu dem Chunk, in dem sie startet, und wird nur aus
+/// (Seed, Chunk, Regel) erzeugt. Weil Adern über die Grenze wachsen können,
+/// spielt jeder Chunk auch die Adern seiner 26 Nachbarn ab und übernimmt
+/// die Blöcke, die bei ihm landen - so passen die Teile immer zusammen.
+pub fn place_ores(seed: u32, rules: &[OreRule], pos: ChunkPos, data: &mut ChunkData) {
+    let origin = chunk_origin_world(pos).as_ivec3();
+
+    for dz in -1..=1 {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let src = ChunkPos(pos.0 + IVec3::new(dx, dy, dz));
+                for (i, rule) in rules.iter().enumerate() {
+                    veins_from(seed, i as u32, rule, src, |p| {
+                        let local = p - origin;
+                        if local.cmplt(IVec3::ZERO).any() || local.cmpge(CHUNK_SIZE).any() {
+                            return;
+                        }
+                        let idx = ChunkData::idx(local.x, local.y, local.z);
+                        if data.blocks[idx] == Block::Stone {
+                            data.blocks[idx] = rule.block;
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Alle Blockpositionen (Welt) der Adern einer Regel, die in `src` starten.
+fn veins_from(seed: u32, rule_idx: u32, rule: &OreRule, src: ChunkPos, mut put: impl FnMut(IVec3)) {
+    let origin = chunk_origin_world(src).as_ivec3();
+    // Chunk liegt komplett außerhalb der Tiefe -> nichts
+    if origin.y > rule.max_y || origin.y + CHUNK_SIZE.y <= rule.min_y {
+        return;
+    }
+
+    let mut rng = Rng::new(hash_seed(seed, src.0, 100 + rule_idx));
+    let mut count = rule.veins_per_chunk.floor() as u32;
+    if rng.next_f32() < rule.veins_per_chunk.fract() {
+        count += 1;
+    }
+
+    for _ in 0..count {
+        let mut p = origin
+            + IVec3::new(
+                rng.range(0, CHUNK_SIZE.x),
+                rng.range(0, CHUNK_SIZE.y),
+                rng.range(0, CHUNK_SIZE.z),
+            );
+        // Zufall trotzdem verbrauchen, damit die Folge nicht von der Tiefe abhängt
+        let skip = p.y < rule.min_y || p.y > rule.max_y;
+
+        for _ in 0..rule.vein_size {
+            if !skip {
+                put(p);
+            }
+            let step = if rng.next_u64() & 1 == 0 { 1 } else { -1 };
+            match rng.range(0, 3) {
+                0 => p.x += step,
+                1 => p.y += step,
+                _ => p.z += step,
+            }
+        }
+    }
+}
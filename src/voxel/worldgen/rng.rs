@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+/// Deterministischer Seed aus Welt-Seed, Position und Zweck (`salt`).
+/// Gleiche Eingabe -> gleiche Zahlen, egal in welcher Reihenfolge Chunks laden.
+pub fn hash_seed(seed: u32, pos: IVec3, salt: u32) -> u64 {
+    let mut h = splitmix(seed as u64 ^ (salt as u64) << 32);
+    h = splitmix(h ^ pos.x as u32 as u64);
+    h = splitmix(h ^ (pos.y as u32 as u64) << 16);
+    splitmix(h ^ (pos.z as u32 as u64) << 32)
+}
+
+fn splitmix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Kleiner SplitMix64-Generator für Platzierungen (kein `rand` nötig).
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        splitmix(self.0)
+    }
+
+    /// 0..1
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// lo..hi (hi exklusiv)
+    pub fn range(&mut self, lo: i32, hi: i32) -> i32 {
+        debug_assert!(hi > lo);
+        lo + (self.next_u64() % (hi - lo) as u64) as i32
+    }
+}
@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::config::{StructureSettings, StructureTemplate, WorldGenConfig};
+use crate::voxel::chunk::{Block, CHUNK_SIZE, ChunkData, ChunkPos, chunk_origin_world};
+
+use super::biome::BiomeMap;
+use super::rng::{Rng, hash_seed};
+
+/// Vorlage aufbereitet: Blöcke relativ zum Anker.
+struct Structure {
+    blocks: Vec<(IVec3, Block)>,
+    min: IVec3,
+    max: IVec3,
+    replace: bool,
+}
+
+impl Structure {
+    fn from_template(t: &StructureTemplate) -> Self {
+        let anchor = IVec3::new(t.anchor.0, t.anchor.1, t.anchor.2);
+        let mut blocks = Vec::new();
+        for (y, layer) in t.layers.iter().enumerate() {
+            for (z, row) in layer.iter().enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    if let Some(&b) = t.palette.get(&c) {
+                        blocks.push((IVec3::new(x as i32, y as i32, z as i32) - anchor, b));
+                    }
+                }
+            }
+        }
+        let min = blocks.iter().fold(IVec3::MAX, |m, (p, _)| m.min(*p));
+        let max = blocks.iter().fold(IVec3::MIN, |m, (p, _)| m.max(*p));
+        Self { blocks, min, max, replace: t.replace }
+    }
+}
+
+struct Feature {
+    structure: usize,
+    biomes: Vec<usize>,
+    /// pro Biom (Index wie in `biomes`)
+    density: Vec<f32>,
+    max_density: f32,
+}
+
+/// Eine gewürfelte Struktur in Weltkoordinaten (Anker).
+#[derive(Clone, Copy)]
+struct Placement {
+    structure: usize,
+    anchor: IVec3,
+}
+
+/// Struktur-Pass: Bäume, Felsen, Hütten.
+///
+/// Pro Region (mehrere Chunks) wird aus (Seed, Region) gewürfelt, wo was steht.
+/// Jeder Chunk fragt alle Regionen ab, deren Strukturen bis zu ihm reichen können,
+/// und übernimmt nur die Blöcke, die in ihm liegen. So werden Strukturen über
+/// Chunkgrenzen hinweg vollständig, egal in welcher Reihenfolge Chunks entstehen.
+pub struct StructurePlacer {
+    seed: u32,
+    region_blocks: i32,
+    structures: Vec<Structure>,
+    features: Vec<Feature>,
+    /// wie weit eine Struktur horizontal über ihren Anker hinausragt
+    reach: i32,
+    /// Regionen werden für jeden Chunk darin (und daneben) gebraucht -> merken
+    cache: Mutex<HashMap<IVec2, Arc<Vec<Placement>>>>,
+}
+
+const CACHE_LIMIT: usize = 4096;
+
+impl StructurePlacer {
+    pub fn new(seed: u32, cfg: &WorldGenConfig, biomes: &BiomeMap) -> Self {
+        let settings: &StructureSettings = &cfg.structures;
+        let mut structures = Vec::new();
+        let mut by_path: HashMap<&str, usize> = HashMap::new();
+        let mut features = Vec::new();
+
+        for f in &settings.features {
+            let Some(template) = cfg.templates.get(&f.template) else {
+                warn!("worldgen: Vorlage {} nicht geladen, Feature übersprungen", f.template);
+                continue;
+            };
+            let structure = *by_path.entry(&f.template).or_insert_with(|| {
+                structures.push(Structure::from_template(template));
+                structures.len() - 1
+            });
+
+            let ids: Vec<usize> = f
+                .biomes
+                .iter()
+                .filter_map(|name| {
+                    let id = biomes.find(name);
+                    if id.is_none() {
+                        warn!("worldgen: Biom {name} für {} unbekannt", f.template);
+                    }
+                    id
+                })
+                .collect();
+            let density: Vec<f32> = ids
+                .iter()
+                .map(|&b| f.density.unwrap_or(biomes.biome(b).vegetation))
+                .collect();
+            let max_density = density.iter().copied().fold(0.0, f32::max);
+            if max_density > 0.0 {
+                features.push(Feature { structure, biomes: ids, density, max_density });
+            }
+        }
+
+        let reach = structures
+            .iter()
+            .map(|s| s.min.x.abs().max(s.min.z.abs()).max(s.max.x).max(s.max.z))
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            seed,
+            region_blocks: settings.region_chunks.max(1) * CHUNK_SIZE.x,
+            structures,
+            features,
+            reach,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Alle Strukturen einer Region würfeln.
+    /// Pro Versuch wird immer gleich viel Zufall verbraucht, damit das Ergebnis
+    /// nicht davon abhängt, was an anderen Stellen passiert.
+    fn roll_region(&self, region: IVec2, biomes: &BiomeMap) -> Vec<Placement> {
+        let mut out = Vec::new();
+        let base = region * self.region_blocks;
+        let area = (self.region_blocks * self.region_blocks) as f32;
+
+        for (i, f) in self.features.iter().enumerate() {
+            let mut rng = Rng::new(hash_seed(self.seed, region.extend(0), 200 + i as u32));
+            let expected = area * f.max_density;
+            let mut attempts = expected.floor() as u32;
+            if rng.next_f32() < expected.fract() {
+                attempts += 1;
+            }
+
+            for _ in 0..attempts {
+                let x = base.x + rng.range(0, self.region_blocks);
+                let z = base.y + rng.range(0, self.region_blocks);
+                let roll = rng.next_f32();
+
+                let col = biomes.column(x, z);
+                let Some(k) = f.biomes.iter().position(|&b| b == col.biome) else {
+                    continue;
+                };
+                if roll * f.max_density < f.density[k] {
+                    out.push(Placement { structure: f.structure, anchor: IVec3::new(x, col.height + 1, z) });
+                }
+            }
+        }
+        out
+    }
+
+    fn region(&self, region: IVec2, biomes: &BiomeMap) -> Arc<Vec<Placement>> {
+        if let Some(p) = self.cache.lock().unwrap().get(&region) {
+            return p.clone();
+        }
+        // ohne Lock würfeln; doppelt rechnen ist harmlos, das Ergebnis ist gleich
+        let placed = Arc::new(self.roll_region(region, biomes));
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= CACHE_LIMIT {
+            cache.clear();
+        }
+        cache.insert(region, placed.clone());
+        placed
+    }
+
+    pub fn place(&self, pos: ChunkPos, data: &mut ChunkData, biomes: &BiomeMap) {
+        if self.structures.is_empty() {
+            return;
+        }
+        let origin = chunk_origin_world(pos).as_ivec3();
+        let lo = IVec2::new(origin.x, origin.z) - IVec2::splat(self.reach);
+        let hi = IVec2::new(origin.x + CHUNK_SIZE.x - 1, origin.z + CHUNK_SIZE.z - 1) + IVec2::splat(self.reach);
+        let r_lo = lo.div_euclid(IVec2::splat(self.region_blocks));
+        let r_hi = hi.div_euclid(IVec2::splat(self.region_blocks));
+
+        for rz in r_lo.y..=r_hi.y {
+            for rx in r_lo.x..=r_hi.x {
+                for p in self.region(IVec2::new(rx, rz), biomes).iter() {
+                    self.apply(&self.structures[p.structure], p.anchor, origin, data);
+                }
+            }
+        }
+    }
+
+    fn apply(&self, s: &Structure, anchor: IVec3, origin: IVec3, data: &mut ChunkData) {
+        // Bounding-Box trifft den Chunk nicht -> gar nicht erst durchlaufen
+        let (min, max) = (anchor + s.min - origin, anchor + s.max - origin);
+        if max.cmplt(IVec3::ZERO).any() || min.cmpge(CHUNK_SIZE).any() {
+            return;
+        }
+
+        for &(off, block) in &s.blocks {
+            let l = anchor + off - origin;
+            if l.cmplt(IVec3::ZERO).any() || l.cmpge(CHUNK_SIZE).any() {
+                continue;
+            }
+            let i = ChunkData::idx(l.x, l.y, l.z);
+            let cur = data.blocks[i];
+            if s.replace || cur == Block::Air || cur == Block::Leaves {
+                data.blocks[i] = block;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voxel::fixtures;
+    use crate::voxel::worldgen::WorldGenerator;
+
+    fn chunk_of(p: IVec3) -> ChunkPos {
+        ChunkPos(p.div_euclid(CHUNK_SIZE))
+    }
+
+    /// Ein Baum mit Anker in Chunk A schreibt seine überhängenden Blätter nach B,
+    /// egal ob A oder B zuerst generiert wird.
+    #[test]
+    fn tree_crosses_chunk_border_in_any_order() {
+        let cfg = fixtures::worldgen_config();
+        let seed = 11;
+        let generator = WorldGenerator::new(seed, &cfg);
+        let placer = &generator.structures;
+        // Gelände ohne Strukturen, um zu sehen, wo der Baum Platz hat
+        let mut bare_cfg = cfg.clone();
+        bare_cfg.structures.features.clear();
+        let bare = WorldGenerator::new(seed, &bare_cfg);
+
+        // erster Baum (Struktur 0, wie in worldgen.ron), dessen Blöcke über eine X-Grenze ragen
+        let (tree, anchor, a, b) = (-4..4)
+            .flat_map(|rz| (-4..4).map(move |rx| IVec2::new(rx, rz)))
+            .flat_map(|r| placer.region(r, &generator.biomes).iter().copied().collect::<Vec<_>>())
+            .filter(|p| p.structure == 0)
+            .find_map(|p| {
+                let s = &placer.structures[p.structure];
+                let a = chunk_of(p.anchor);
+                let b = s.blocks.iter().map(|(off, _)| chunk_of(p.anchor + *off)).find(|c| c.0.x != a.0.x)?;
+                Some((s, p.anchor, a, b))
+            })
+            .expect("kein Baum an einer Chunkgrenze");
+
+        let a_then_b = {
+            let g = WorldGenerator::new(seed, &cfg);
+            (g.generate_chunk(a), g.generate_chunk(b))
+        };
+        let b_then_a = {
+            let g = WorldGenerator::new(seed, &cfg);
+            let db = g.generate_chunk(b);
+            (g.generate_chunk(a), db)
+        };
+        assert!(a_then_b.0.blocks == b_then_a.0.blocks, "Chunk A hängt von der Reihenfolge ab");
+        assert!(a_then_b.1.blocks == b_then_a.1.blocks, "Chunk B hängt von der Reihenfolge ab");
+
+        let (data_b, terrain_b) = (&a_then_b.1, bare.generate_chunk(b));
+        let origin = chunk_origin_world(b).as_ivec3();
+        let mut from_tree = 0;
+        for &(off, block) in &tree.blocks {
+            let l = anchor + off - origin;
+            if l.cmplt(IVec3::ZERO).any() || l.cmpge(CHUNK_SIZE).any() {
+                continue;
+            }
+            let got = data_b.get_local(l.x, l.y, l.z);
+            // wo vorher Luft war, steht jetzt etwas
+            if terrain_b.get_local(l.x, l.y, l.z) == Block::Air {
+                assert_ne!(got, Block::Air, "Lücke bei {}", anchor + off);
+            }
+            from_tree += (got == block) as u32;
+        }
+        assert!(from_tree > 0, "nichts vom Baum bei {anchor} in {:?}", b.0);
+    }
+}